@@ -145,7 +145,22 @@ pub enum LogicalType {
   /// the number of days associated with the duration and the third identifies
   /// the number of milliseconds associated with the provided duration.
   /// This duration of time is independent of any particular timezone or date.
-  INTERVAL
+  INTERVAL,
+
+  /// A half-precision (16 bit) IEEE 754 floating point value.
+  ///
+  /// This type annotates data stored as a FIXED_LEN_BYTE_ARRAY of length 2, with bytes
+  /// stored in little endian order. It does not have an equivalent converted type, see
+  /// `data_type::f16_to_bytes` and `data_type::f16_from_bytes` for conversions.
+  FLOAT16,
+
+  /// A universally unique identifier (UUID).
+  ///
+  /// This type annotates data stored as a FIXED_LEN_BYTE_ARRAY of length 16, with bytes
+  /// in the order of the string representation of the UUID. It does not have an
+  /// equivalent converted type, see `data_type::uuid_to_bytes` and
+  /// `data_type::uuid_from_bytes` for conversions.
+  UUID
 }
 
 // ----------------------------------------------------------------------
@@ -192,7 +207,7 @@ pub enum Encoding {
   /// Usable for definition/repetition levels encoding and boolean values.
   RLE,
 
-  /// Bit packed encoding.
+  /// **Deprecated** bit packed encoding.
   ///
   /// This can only be used if the data has a known max width.
   /// Usable for definition/repetition levels encoding.
@@ -220,6 +235,17 @@ pub enum Encoding {
   RLE_DICTIONARY
 }
 
+impl Encoding {
+  /// Returns true if encoding is deprecated and should not be used by writers, i.e.
+  /// PLAIN_DICTIONARY and BIT_PACKED.
+  pub fn is_deprecated(self) -> bool {
+    match self {
+      Encoding::PLAIN_DICTIONARY | Encoding::BIT_PACKED => true,
+      _ => false
+    }
+  }
+}
+
 // ----------------------------------------------------------------------
 // Mirrors `parquet::CompressionCodec`
 
@@ -248,6 +274,20 @@ pub enum PageType {
   DATA_PAGE_V2
 }
 
+// ----------------------------------------------------------------------
+// Boundary order of the column index, not available in the pinned parquet-format
+// version, so there is no conversion from a Thrift type
+
+/// Order of min and max values of pages in a column chunk, stored in the column index.
+/// Pages are ordered if both min values and max values of consecutive pages are
+/// ordered in the same direction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundaryOrder {
+  UNORDERED,
+  ASCENDING,
+  DESCENDING
+}
+
 impl fmt::Display for Type {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     write!(f, "{:?}", self)
@@ -284,6 +324,12 @@ impl fmt::Display for PageType {
   }
 }
 
+impl fmt::Display for BoundaryOrder {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{:?}", self)
+  }
+}
+
 impl convert::From<parquet::Type> for Type {
   fn from(tp: parquet::Type) -> Self {
     match tp {
@@ -439,11 +485,38 @@ impl str::FromStr for LogicalType {
       "JSON" => Ok(LogicalType::JSON),
       "BSON" => Ok(LogicalType::BSON),
       "INTERVAL" => Ok(LogicalType::INTERVAL),
+      "FLOAT16" => Ok(LogicalType::FLOAT16),
+      "UUID" => Ok(LogicalType::UUID),
       other => Err(general_err!("Invalid logical type {}", other)),
     }
   }
 }
 
+/// Returns the physical type that logical type `logical` requires, e.g. INT64 for
+/// TIMESTAMP_MILLIS, or `None` if the logical type can annotate several physical types
+/// (DECIMAL), annotates group types (MAP, LIST), or is NONE.
+///
+/// INTERVAL, FLOAT16 and UUID also require a specific length of FIXED_LEN_BYTE_ARRAY.
+pub fn physical_type_for(logical: LogicalType) -> Option<Type> {
+  match logical {
+    LogicalType::UTF8 | LogicalType::ENUM | LogicalType::JSON | LogicalType::BSON => {
+      Some(Type::BYTE_ARRAY)
+    },
+    LogicalType::DATE | LogicalType::TIME_MILLIS | LogicalType::UINT_8 |
+    LogicalType::UINT_16 | LogicalType::UINT_32 |
+    LogicalType::INT_8 | LogicalType::INT_16 | LogicalType::INT_32 => Some(Type::INT32),
+    LogicalType::TIME_MICROS | LogicalType::TIMESTAMP_MILLIS |
+    LogicalType::TIMESTAMP_MICROS | LogicalType::UINT_64 | LogicalType::INT_64 => {
+      Some(Type::INT64)
+    },
+    LogicalType::INTERVAL | LogicalType::FLOAT16 | LogicalType::UUID => {
+      Some(Type::FIXED_LEN_BYTE_ARRAY)
+    },
+    LogicalType::NONE | LogicalType::MAP | LogicalType::MAP_KEY_VALUE |
+    LogicalType::LIST | LogicalType::DECIMAL => None
+  }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -518,6 +591,8 @@ mod tests {
     assert_eq!(LogicalType::JSON.to_string(), "JSON");
     assert_eq!(LogicalType::BSON.to_string(), "BSON");
     assert_eq!(LogicalType::INTERVAL.to_string(), "INTERVAL");
+    assert_eq!(LogicalType::FLOAT16.to_string(), "FLOAT16");
+    assert_eq!(LogicalType::UUID.to_string(), "UUID");
   }
 
     #[test]
@@ -710,6 +785,32 @@ mod tests {
       LogicalType::INTERVAL.to_string().parse::<LogicalType>().unwrap(),
       LogicalType::INTERVAL
     );
+    assert_eq!(
+      LogicalType::FLOAT16.to_string().parse::<LogicalType>().unwrap(),
+      LogicalType::FLOAT16
+    );
+    assert_eq!(
+      LogicalType::UUID.to_string().parse::<LogicalType>().unwrap(),
+      LogicalType::UUID
+    );
+  }
+
+  #[test]
+  fn test_physical_type_for() {
+    assert_eq!(physical_type_for(LogicalType::DATE), Some(Type::INT32));
+    assert_eq!(physical_type_for(LogicalType::UINT_16), Some(Type::INT32));
+    assert_eq!(physical_type_for(LogicalType::TIMESTAMP_MICROS), Some(Type::INT64));
+    assert_eq!(physical_type_for(LogicalType::TIMESTAMP_MILLIS), Some(Type::INT64));
+    assert_eq!(physical_type_for(LogicalType::UTF8), Some(Type::BYTE_ARRAY));
+    assert_eq!(physical_type_for(LogicalType::ENUM), Some(Type::BYTE_ARRAY));
+    assert_eq!(
+      physical_type_for(LogicalType::INTERVAL),
+      Some(Type::FIXED_LEN_BYTE_ARRAY)
+    );
+    assert_eq!(physical_type_for(LogicalType::UUID), Some(Type::FIXED_LEN_BYTE_ARRAY));
+    assert_eq!(physical_type_for(LogicalType::DECIMAL), None);
+    assert_eq!(physical_type_for(LogicalType::LIST), None);
+    assert_eq!(physical_type_for(LogicalType::NONE), None);
   }
 
   #[test]
@@ -763,6 +864,19 @@ mod tests {
     assert_eq!(Encoding::RLE_DICTIONARY.to_string(), "RLE_DICTIONARY");
   }
 
+  #[test]
+  fn test_encoding_is_deprecated() {
+    assert!(Encoding::PLAIN_DICTIONARY.is_deprecated());
+    assert!(Encoding::BIT_PACKED.is_deprecated());
+
+    assert!(!Encoding::PLAIN.is_deprecated());
+    assert!(!Encoding::RLE.is_deprecated());
+    assert!(!Encoding::DELTA_BINARY_PACKED.is_deprecated());
+    assert!(!Encoding::DELTA_LENGTH_BYTE_ARRAY.is_deprecated());
+    assert!(!Encoding::DELTA_BYTE_ARRAY.is_deprecated());
+    assert!(!Encoding::RLE_DICTIONARY.is_deprecated());
+  }
+
   #[test]
   fn test_from_encoding() {
     assert_eq!(
@@ -840,6 +954,13 @@ mod tests {
     assert_eq!(PageType::DATA_PAGE_V2.to_string(), "DATA_PAGE_V2");
   }
 
+  #[test]
+  fn test_display_boundary_order() {
+    assert_eq!(BoundaryOrder::UNORDERED.to_string(), "UNORDERED");
+    assert_eq!(BoundaryOrder::ASCENDING.to_string(), "ASCENDING");
+    assert_eq!(BoundaryOrder::DESCENDING.to_string(), "DESCENDING");
+  }
+
   #[test]
   fn test_from_page_type() {
     assert_eq!(PageType::from(parquet::PageType::DATA_PAGE), PageType::DATA_PAGE);
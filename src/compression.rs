@@ -50,6 +50,7 @@ use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
 use snap::{decompress_len, Decoder, Encoder};
 use lz4;
+use util::hash_util;
 
 /// Parquet compression codec interface.
 pub trait Codec {
@@ -62,6 +63,23 @@ pub trait Codec {
   /// Decompresses data stored in slice `input_buf` and writes output to `output_buf`.
   /// Returns the total number of bytes written.
   fn decompress(&mut self, input_buf: &[u8], output_buf: &mut Vec<u8>) -> Result<usize>;
+
+  /// Decompresses data stored in slice `input_buf` into `output_buf`, reserving
+  /// `uncompressed_size_hint` bytes (uncompressed page size from the page header)
+  /// upfront to avoid growing the output buffer while decompressing.
+  /// Returns the total number of bytes written.
+  ///
+  /// Codecs that know the uncompressed size before decompressing return an error if
+  /// it does not match the hint, other codecs only use the hint as a capacity.
+  fn decompress_into(
+    &mut self,
+    input_buf: &[u8],
+    output_buf: &mut Vec<u8>,
+    uncompressed_size_hint: usize
+  ) -> Result<usize> {
+    output_buf.reserve(uncompressed_size_hint);
+    self.decompress(input_buf, output_buf)
+  }
 }
 
 /// Given the compression type `codec`, returns a codec used to compress and decompress
@@ -78,6 +96,35 @@ pub fn create_codec(codec: CodecType) -> Result<Option<Box<Codec>>> {
   }
 }
 
+/// Prepares page bytes `raw` for writing: compresses them with `codec`, if provided,
+/// and computes CRC-32 checksum of the resulting bytes, if `with_crc` is true.
+///
+/// Checksum is always computed over the bytes that are written into the file, i.e.
+/// compressed bytes when `codec` is set, which matches Parquet page CRC convention.
+pub fn finalize_page(
+  raw: &[u8],
+  codec: Option<&mut Codec>,
+  with_crc: bool
+) -> Result<(Vec<u8>, Option<u32>)> {
+  let bytes = match codec {
+    Some(codec) => codec.compress(raw)?,
+    None => raw.to_vec()
+  };
+  let crc = if with_crc { Some(hash_util::crc32_ieee(&bytes[..])) } else { None };
+  Ok((bytes, crc))
+}
+
+/// Compresses `sample` with `codec` and returns the ratio of compressed size to the
+/// sample size, e.g. to choose a codec for a column based on a sample of its pages.
+/// Ratio below 1.0 means data is compressible. Empty sample has ratio 1.0.
+pub fn estimate_ratio(codec: &mut Codec, sample: &[u8]) -> Result<f32> {
+  if sample.is_empty() {
+    return Ok(1.0);
+  }
+  let compressed = codec.compress(sample)?;
+  Ok(compressed.len() as f32 / sample.len() as f32)
+}
+
 /// Codec for Snappy compression format.
 pub struct SnappyCodec {
   decoder: Decoder,
@@ -102,6 +149,24 @@ impl Codec for SnappyCodec {
       .map_err(|e| general_err!("Error when decompressing using Snappy: {}", e))
   }
 
+  fn decompress_into(
+    &mut self,
+    input_buf: &[u8],
+    output_buf: &mut Vec<u8>,
+    uncompressed_size_hint: usize
+  ) -> Result<usize> {
+    let len = decompress_len(input_buf)?;
+    if len != uncompressed_size_hint {
+      return Err(general_err!(
+        "Snappy decompressed size {} does not match expected size {}",
+        len,
+        uncompressed_size_hint
+      ));
+    }
+    output_buf.reserve(len);
+    self.decompress(input_buf, output_buf)
+  }
+
   fn compress(&mut self, input_buf: &[u8]) -> Result<Vec<u8>> {
     self.encoder.compress_vec(input_buf)
       .map_err(|e| general_err!("Error when compressing using Snappy: {}", e))
@@ -259,6 +324,94 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_finalize_page() {
+    let data = random_bytes(1000);
+
+    let (bytes, crc) = finalize_page(&data[..], None, false).unwrap();
+    assert_eq!(bytes, data);
+    assert_eq!(crc, None);
+
+    let (bytes, crc) = finalize_page(&data[..], None, true).unwrap();
+    assert_eq!(bytes, data);
+    assert_eq!(crc, Some(hash_util::crc32_ieee(&data[..])));
+
+    let mut codec = create_codec(CodecType::SNAPPY).unwrap().unwrap();
+    let expected = codec.compress(&data[..]).unwrap();
+
+    let (bytes, crc) = finalize_page(&data[..], Some(codec.as_mut()), false).unwrap();
+    assert_eq!(bytes, expected);
+    assert_eq!(crc, None);
+
+    let (bytes, crc) = finalize_page(&data[..], Some(codec.as_mut()), true).unwrap();
+    assert_eq!(bytes, expected);
+    assert_eq!(crc, Some(hash_util::crc32_ieee(&expected[..])));
+
+    let mut decompressed = vec![];
+    let size = codec.decompress(&bytes[..], &mut decompressed).unwrap();
+    decompressed.truncate(size);
+    assert_eq!(decompressed, data);
+  }
+
+  #[test]
+  fn test_estimate_ratio() {
+    let compressible: Vec<u8> = (0..64 * 1024).map(|i| (i % 16) as u8).collect();
+    let incompressible = random_bytes(64 * 1024);
+    for &c in &[CodecType::SNAPPY, CodecType::GZIP, CodecType::BROTLI, CodecType::LZ4] {
+      let mut codec = create_codec(c).unwrap().unwrap();
+      let ratio = estimate_ratio(&mut *codec, &compressible).unwrap();
+      assert!(ratio < 0.1, "{}: ratio {} for compressible data", c, ratio);
+      let ratio = estimate_ratio(&mut *codec, &incompressible).unwrap();
+      assert!(ratio > 0.9, "{}: ratio {} for random data", c, ratio);
+      assert_eq!(estimate_ratio(&mut *codec, &[]).unwrap(), 1.0);
+    }
+  }
+
+  #[test]
+  fn test_decompress_into() {
+    let data = random_bytes(10000);
+
+    // Exact hint
+    for codec_type in vec![CodecType::SNAPPY, CodecType::LZ4] {
+      let mut codec = create_codec(codec_type).unwrap().unwrap();
+      let compressed = codec.compress(&data[..]).unwrap();
+      let mut output = Vec::new();
+      // Buffer reserved with the exact hint is not reallocated while decompressing
+      output.reserve(data.len());
+      let ptr = output.as_ptr();
+      let capacity = output.capacity();
+      let size = codec.decompress_into(&compressed[..], &mut output, data.len()).unwrap();
+      assert_eq!(size, data.len());
+      assert_eq!(output.len(), data.len());
+      assert_eq!(output.as_ptr(), ptr);
+      assert_eq!(output.capacity(), capacity);
+      assert_eq!(output, data);
+    }
+
+    // Wrong hint, fails for Snappy, since size is known before decompressing
+    let mut codec = create_codec(CodecType::SNAPPY).unwrap().unwrap();
+    let compressed = codec.compress(&data[..]).unwrap();
+    let mut output = Vec::new();
+    let res = codec.decompress_into(&compressed[..], &mut output, 100);
+    assert!(res.is_err());
+    assert_eq!(
+      res.unwrap_err().to_string(),
+      "Parquet error: Snappy decompressed size 10000 does not match expected size 100"
+    );
+
+    // Wrong hint, other codecs only use it as a capacity
+    for codec_type in vec![CodecType::GZIP, CodecType::BROTLI, CodecType::LZ4] {
+      let mut codec = create_codec(codec_type).unwrap().unwrap();
+      let compressed = codec.compress(&data[..]).unwrap();
+      for hint in vec![0, 100, 20000] {
+        let mut output = Vec::new();
+        let size = codec.decompress_into(&compressed[..], &mut output, hint).unwrap();
+        assert_eq!(size, data.len());
+        assert_eq!(output, data);
+      }
+    }
+  }
+
   #[test]
   fn test_codec_snappy() {
     test_codec(CodecType::SNAPPY);
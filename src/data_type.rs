@@ -18,10 +18,12 @@
 //! Data types that connect Parquet physical types with their Rust-specific
 //! representations.
 
+use std::cmp;
 use std::mem;
 
 use basic::Type;
-use byteorder::{BigEndian, ByteOrder};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use errors::{ParquetError, Result};
 use rand::{Rand, Rng};
 use util::memory::{ByteBuffer, ByteBufferPtr};
 
@@ -243,6 +245,155 @@ impl PartialEq for Decimal {
   }
 }
 
+/// Converts `f32` value into bytes of FLOAT16 logical type, i.e. half-precision
+/// floating point value in little endian order.
+///
+/// Values are rounded to the nearest representable value (ties to even), values that
+/// are too large become infinity and values that are too small become (signed) zero.
+/// NaN is preserved as NaN.
+pub fn f16_to_bytes(value: f32) -> [u8; 2] {
+  let bits = value.to_bits();
+  let sign = ((bits >> 16) & 0x8000) as u16;
+  let exp = ((bits >> 23) & 0xFF) as i32;
+  let man = bits & 0x7FFFFF;
+
+  let half = if exp == 0xFF {
+    if man == 0 {
+      // Infinity
+      sign | 0x7C00
+    } else {
+      // NaN, set quiet bit, so that payload does not turn it into infinity
+      sign | 0x7E00 | (man >> 13) as u16
+    }
+  } else {
+    // Rebias exponent from f32 (127) to f16 (15)
+    let half_exp = exp - 127 + 15;
+    if half_exp >= 0x1F {
+      // Overflow, round to infinity
+      sign | 0x7C00
+    } else if half_exp <= 0 {
+      if half_exp < -10 {
+        // Less than half of the smallest subnormal, round to zero
+        sign
+      } else {
+        // Subnormal, add implicit leading bit and shift mantissa
+        let man = man | 0x800000;
+        let shift = (14 - half_exp) as u32;
+        sign | round_shift_right(man, shift) as u16
+      }
+    } else {
+      // Normal value, rounding carry may overflow into exponent, which is correct
+      sign | (((half_exp as u32) << 10) + round_shift_right(man, 13)) as u16
+    }
+  };
+
+  let mut bytes = [0; 2];
+  LittleEndian::write_u16(&mut bytes, half);
+  bytes
+}
+
+/// Converts bytes of FLOAT16 logical type (little endian half-precision floating point
+/// value) into `f32` value. Conversion is exact.
+pub fn f16_from_bytes(bytes: [u8; 2]) -> f32 {
+  let half = LittleEndian::read_u16(&bytes);
+  let sign = ((half & 0x8000) as u32) << 16;
+  let exp = ((half >> 10) & 0x1F) as u32;
+  let man = (half & 0x3FF) as u32;
+
+  let bits = if exp == 0x1F {
+    // Infinity or NaN
+    sign | 0x7F800000 | (man << 13)
+  } else if exp == 0 {
+    if man == 0 {
+      // Signed zero
+      sign
+    } else {
+      // Subnormal, normalize mantissa since every f16 subnormal is a normal f32
+      let mut exp = 127 - 15 + 1;
+      let mut man = man;
+      while man & 0x400 == 0 {
+        man <<= 1;
+        exp -= 1;
+      }
+      sign | (exp << 23) | ((man & 0x3FF) << 13)
+    }
+  } else {
+    sign | ((exp + 127 - 15) << 23) | (man << 13)
+  };
+  f32::from_bits(bits)
+}
+
+/// Converts bytes of UUID `uuid` into a value of UUID logical type, i.e. 16-byte
+/// FIXED_LEN_BYTE_ARRAY.
+pub fn uuid_to_bytes(uuid: [u8; 16]) -> ByteArray {
+  ByteArray::from(uuid.to_vec())
+}
+
+/// Converts value of UUID logical type into bytes of UUID.
+/// Returns an error if the value is not 16 bytes long.
+pub fn uuid_from_bytes(value: &ByteArray) -> Result<[u8; 16]> {
+  if value.len() != 16 {
+    return Err(general_err!("UUID must be 16 bytes long, found {} bytes", value.len()));
+  }
+  let mut uuid = [0; 16];
+  uuid.copy_from_slice(value.data());
+  Ok(uuid)
+}
+
+/// Rust representation for INTERVAL logical type, which is stored as 12-byte
+/// FIXED_LEN_BYTE_ARRAY of three little endian `u32` values: number of months, number
+/// of days and number of milliseconds. Components are independent of each other, e.g.
+/// a month is not a fixed number of days.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Interval {
+  pub months: u32,
+  pub days: u32,
+  pub millis: u32
+}
+
+impl Interval {
+  /// Creates new interval from months, days and milliseconds.
+  pub fn new(months: u32, days: u32, millis: u32) -> Self {
+    Interval { months: months, days: days, millis: millis }
+  }
+
+  /// Returns bytes of INTERVAL value, i.e. months, days and milliseconds in this order,
+  /// each as little endian `u32`.
+  pub fn to_bytes(&self) -> [u8; 12] {
+    let mut bytes = [0; 12];
+    LittleEndian::write_u32(&mut bytes[0..4], self.months);
+    LittleEndian::write_u32(&mut bytes[4..8], self.days);
+    LittleEndian::write_u32(&mut bytes[8..12], self.millis);
+    bytes
+  }
+
+  /// Creates interval from bytes of INTERVAL value.
+  /// Returns an error if `bytes` is not 12 bytes long.
+  pub fn from_bytes(bytes: &[u8]) -> Result<Interval> {
+    if bytes.len() != 12 {
+      return Err(general_err!(
+        "INTERVAL must be 12 bytes long, found {} bytes", bytes.len()));
+    }
+    Ok(Interval {
+      months: LittleEndian::read_u32(&bytes[0..4]),
+      days: LittleEndian::read_u32(&bytes[4..8]),
+      millis: LittleEndian::read_u32(&bytes[8..12])
+    })
+  }
+}
+
+/// Shifts `value` right by `shift` bits, rounding to nearest, ties to even.
+#[inline]
+fn round_shift_right(value: u32, shift: u32) -> u32 {
+  let result = value >> shift;
+  let half = 1 << (shift - 1);
+  let remainder = value & ((1 << shift) - 1);
+  if remainder > half || (remainder == half && result & 1 == 1) {
+    result + 1
+  } else {
+    result
+  }
+}
 
 /// Converts an instance of data type to a slice of bytes as `u8`.
 pub trait AsBytes {
@@ -299,6 +450,12 @@ impl AsBytes for Vec<u8> {
   }
 }
 
+impl<'a> AsBytes for &'a [u8] {
+  fn as_bytes(&self) -> &[u8] {
+    self
+  }
+}
+
 impl<'a> AsBytes for &'a str {
   fn as_bytes(&self) -> &[u8] {
     (self as &str).as_bytes()
@@ -322,10 +479,93 @@ pub trait DataType {
 
   /// Returns size in bytes for Rust representation of the physical type.
   fn get_type_size() -> usize;
+
+  /// Returns number of bytes a single encoded value of the physical type takes, or
+  /// `None` if the width is not known from the type alone.
+  /// `BYTE_ARRAY` values are variable length and `FIXED_LEN_BYTE_ARRAY` length is
+  /// defined per column, so both return `None`. For `BOOLEAN` this is an upper bound,
+  /// because values can be bit-packed.
+  fn byte_width() -> Option<usize>;
+
+  /// Returns true if every value of the physical type has the same width, which is
+  /// available through `byte_width()`.
+  fn is_fixed_width() -> bool {
+    Self::byte_width().is_some()
+  }
+
+  /// Compares two values using the default sort order of the physical type:
+  /// - BOOLEAN - false is less than true.
+  /// - INT32, INT64 - signed comparison.
+  /// - INT96 - signed comparison of 96-bit little-endian integers, since the sort order
+  /// of INT96 is undefined in the specification.
+  /// - FLOAT, DOUBLE - IEEE 754 comparison, where -0.0 and +0.0 are equal, and NaN is
+  /// greater than any other value and equal to NaN.
+  /// - BYTE_ARRAY, FIXED_LEN_BYTE_ARRAY - unsigned lexicographic comparison of bytes.
+  fn compare(a: &Self::T, b: &Self::T) -> cmp::Ordering;
+}
+
+fn compare_ord<T: Ord>(a: &T, b: &T) -> cmp::Ordering {
+  a.cmp(b)
+}
+
+fn compare_float<T: PartialOrd>(a: &T, b: &T) -> cmp::Ordering {
+  // NaN is the only value that is not comparable to itself
+  match (a.partial_cmp(a).is_none(), b.partial_cmp(b).is_none()) {
+    (true, true) => cmp::Ordering::Equal,
+    (true, false) => cmp::Ordering::Greater,
+    (false, true) => cmp::Ordering::Less,
+    (false, false) => a.partial_cmp(b).unwrap()
+  }
+}
+
+fn compare_int96(a: &Int96, b: &Int96) -> cmp::Ordering {
+  let (a, b) = (a.data(), b.data());
+  // Most significant element holds the sign
+  (a[2] as i32).cmp(&(b[2] as i32))
+    .then_with(|| a[1].cmp(&b[1]))
+    .then_with(|| a[0].cmp(&b[0]))
+}
+
+fn compare_byte_array(a: &ByteArray, b: &ByteArray) -> cmp::Ordering {
+  a.data().cmp(b.data())
+}
+
+/// Compares unscaled values of DECIMAL stored in BYTE_ARRAY or FIXED_LEN_BYTE_ARRAY,
+/// i.e. two's complement big-endian integers, numerically. Byte-lexicographic order
+/// of such values is wrong for negative values. Values can have different lengths,
+/// empty slice is zero.
+pub fn compare_decimal(a: &[u8], b: &[u8]) -> cmp::Ordering {
+  let is_negative = |v: &[u8]| v.first().map(|b| b & 0x80 != 0).unwrap_or(false);
+  let (a_negative, b_negative) = (is_negative(a), is_negative(b));
+  if a_negative != b_negative {
+    return if a_negative { cmp::Ordering::Less } else { cmp::Ordering::Greater };
+  }
+  // Values of the same sign compare as unsigned integers once sign extended to the
+  // same length
+  let sign_byte = if a_negative { 0xFF } else { 0 };
+  let len = cmp::max(a.len(), b.len());
+  let byte_at = |v: &[u8], i: usize| {
+    let padding = len - v.len();
+    if i < padding { sign_byte } else { v[i - padding] }
+  };
+  for i in 0..len {
+    match byte_at(a, i).cmp(&byte_at(b, i)) {
+      cmp::Ordering::Equal => continue,
+      ordering => return ordering
+    }
+  }
+  cmp::Ordering::Equal
 }
 
 macro_rules! make_type {
-  ($name:ident, $physical_ty:path, $native_ty:ty, $size:expr) => {
+  (
+    $name:ident,
+    $physical_ty:path,
+    $native_ty:ty,
+    $size:expr,
+    $byte_width:expr,
+    $compare:path
+  ) => {
     pub struct $name {
     }
 
@@ -339,24 +579,48 @@ macro_rules! make_type {
       fn get_type_size() -> usize {
         $size
       }
+
+      fn byte_width() -> Option<usize> {
+        $byte_width
+      }
+
+      fn compare(a: &$native_ty, b: &$native_ty) -> cmp::Ordering {
+        $compare(a, b)
+      }
     }
   };
 }
 
 /// Generate struct definitions for all physical types
 
-make_type!(BoolType, Type::BOOLEAN, bool, 1);
-make_type!(Int32Type, Type::INT32, i32, 4);
-make_type!(Int64Type, Type::INT64, i64, 8);
-make_type!(Int96Type, Type::INT96, Int96, mem::size_of::<Int96>());
-make_type!(FloatType, Type::FLOAT, f32, 4);
-make_type!(DoubleType, Type::DOUBLE, f64, 8);
-make_type!(ByteArrayType, Type::BYTE_ARRAY, ByteArray, mem::size_of::<ByteArray>());
+make_type!(BoolType, Type::BOOLEAN, bool, 1, Some(1), compare_ord);
+make_type!(Int32Type, Type::INT32, i32, 4, Some(4), compare_ord);
+make_type!(Int64Type, Type::INT64, i64, 8, Some(8), compare_ord);
+make_type!(
+  Int96Type,
+  Type::INT96,
+  Int96,
+  mem::size_of::<Int96>(),
+  Some(12),
+  compare_int96
+);
+make_type!(FloatType, Type::FLOAT, f32, 4, Some(4), compare_float);
+make_type!(DoubleType, Type::DOUBLE, f64, 8, Some(8), compare_float);
+make_type!(
+  ByteArrayType,
+  Type::BYTE_ARRAY,
+  ByteArray,
+  mem::size_of::<ByteArray>(),
+  None,
+  compare_byte_array
+);
 make_type!(
   FixedLenByteArrayType,
   Type::FIXED_LEN_BYTE_ARRAY,
   ByteArray,
-  mem::size_of::<ByteArray>()
+  mem::size_of::<ByteArray>(),
+  None,
+  compare_byte_array
 );
 
 
@@ -399,6 +663,93 @@ mod tests {
     assert_eq!(decimal.as_bytes(), &[1, 2, 3]);
   }
 
+  #[test]
+  fn test_compare() {
+    use std::cmp::Ordering::{Equal, Greater, Less};
+    use std::{f32, f64};
+
+    assert_eq!(BoolType::compare(&false, &true), Less);
+    assert_eq!(BoolType::compare(&true, &true), Equal);
+
+    assert_eq!(Int32Type::compare(&-1, &1), Less);
+    assert_eq!(Int32Type::compare(&i32::max_value(), &i32::min_value()), Greater);
+    assert_eq!(Int64Type::compare(&-1, &1), Less);
+    assert_eq!(Int64Type::compare(&7, &7), Equal);
+
+    let a = Int96::from(vec![1, 0, 0]);
+    let b = Int96::from(vec![0, 1, 0]);
+    let c = Int96::from(vec![0, 0, 1]);
+    let negative = Int96::from(vec![0, 0, 1 << 31]);
+    assert_eq!(Int96Type::compare(&a, &b), Less);
+    assert_eq!(Int96Type::compare(&c, &b), Greater);
+    assert_eq!(Int96Type::compare(&b, &b.clone()), Equal);
+    // Negative values are less than positive values
+    assert_eq!(Int96Type::compare(&negative, &a), Less);
+
+    assert_eq!(FloatType::compare(&-1.5, &2.0), Less);
+    assert_eq!(FloatType::compare(&-0.0, &0.0), Equal);
+    assert_eq!(FloatType::compare(&f32::NAN, &f32::INFINITY), Greater);
+    assert_eq!(FloatType::compare(&f32::NEG_INFINITY, &f32::NAN), Less);
+    assert_eq!(FloatType::compare(&f32::NAN, &f32::NAN), Equal);
+    assert_eq!(DoubleType::compare(&3.0, &-3.0), Greater);
+    assert_eq!(DoubleType::compare(&f64::NAN, &f64::MAX), Greater);
+    assert_eq!(DoubleType::compare(&f64::NAN, &f64::NAN), Equal);
+
+    let empty = ByteArray::from(vec![]);
+    let a = ByteArray::from(vec![1, 2]);
+    let b = ByteArray::from(vec![1, 2, 0]);
+    let c = ByteArray::from(vec![200]);
+    let d = ByteArray::from(vec![100, 255]);
+    assert_eq!(ByteArrayType::compare(&a, &b), Less);
+    assert_eq!(ByteArrayType::compare(&empty, &a), Less);
+    assert_eq!(ByteArrayType::compare(&a, &a.clone()), Equal);
+    // Bytes are compared as unsigned
+    assert_eq!(ByteArrayType::compare(&c, &d), Greater);
+    let e = ByteArray::from(vec![127]);
+    assert_eq!(FixedLenByteArrayType::compare(&c, &e), Greater);
+  }
+
+  #[test]
+  fn test_compare_decimal() {
+    use std::cmp::Ordering::{Equal, Greater, Less};
+
+    // -1 and 1, lexicographically 0xFF is greater than 0x01
+    assert_eq!(compare_decimal(&[0xFF, 0xFF], &[0x00, 0x01]), Less);
+    assert_eq!(compare_decimal(&[0x00, 0x01], &[0xFF, 0xFF]), Greater);
+    // -256 and -1
+    assert_eq!(compare_decimal(&[0xFF, 0x00], &[0xFF, 0xFF]), Less);
+    // -32768 and -2
+    assert_eq!(compare_decimal(&[0x80, 0x00], &[0xFF, 0xFE]), Less);
+    assert_eq!(compare_decimal(&[0x80, 0x00], &[0x80, 0x00]), Equal);
+    // Values of different length: -1 and -1, -1 and 1, 256 and -1, 256 and 255
+    assert_eq!(compare_decimal(&[0xFF], &[0xFF, 0xFF, 0xFF]), Equal);
+    assert_eq!(compare_decimal(&[0xFF, 0xFF, 0xFF], &[0x01]), Less);
+    assert_eq!(compare_decimal(&[0x01, 0x00], &[0xFF]), Greater);
+    assert_eq!(compare_decimal(&[0x01, 0x00], &[0x00, 0x00, 0xFF]), Greater);
+    assert_eq!(compare_decimal(&[], &[0x00]), Equal);
+    assert_eq!(compare_decimal(&[], &[0x80]), Greater);
+  }
+
+  #[test]
+  fn test_byte_width() {
+    assert_eq!(BoolType::byte_width(), Some(1));
+    assert!(BoolType::is_fixed_width());
+    assert_eq!(Int32Type::byte_width(), Some(4));
+    assert!(Int32Type::is_fixed_width());
+    assert_eq!(Int64Type::byte_width(), Some(8));
+    assert!(Int64Type::is_fixed_width());
+    assert_eq!(Int96Type::byte_width(), Some(12));
+    assert!(Int96Type::is_fixed_width());
+    assert_eq!(FloatType::byte_width(), Some(4));
+    assert!(FloatType::is_fixed_width());
+    assert_eq!(DoubleType::byte_width(), Some(8));
+    assert!(DoubleType::is_fixed_width());
+    assert_eq!(ByteArrayType::byte_width(), None);
+    assert!(!ByteArrayType::is_fixed_width());
+    assert_eq!(FixedLenByteArrayType::byte_width(), None);
+    assert!(!FixedLenByteArrayType::is_fixed_width());
+  }
+
   #[test]
   fn test_int96_from() {
     assert_eq!(
@@ -434,4 +785,107 @@ mod tests {
 
     assert!(Decimal::from_i64(222, 5, 2) != Decimal::from_i32(222, 5, 2));
   }
+
+  #[test]
+  fn test_f16_to_bytes() {
+    assert_eq!(f16_to_bytes(0.0), [0x00, 0x00]);
+    assert_eq!(f16_to_bytes(-0.0), [0x00, 0x80]);
+    assert_eq!(f16_to_bytes(1.0), [0x00, 0x3C]);
+    assert_eq!(f16_to_bytes(-2.0), [0x00, 0xC0]);
+    assert_eq!(f16_to_bytes(0.5), [0x00, 0x38]);
+    assert_eq!(f16_to_bytes(0.1), [0x66, 0x2E]);
+    assert_eq!(f16_to_bytes(1.0 / 3.0), [0x55, 0x35]);
+    // Max value
+    assert_eq!(f16_to_bytes(65504.0), [0xFF, 0x7B]);
+    // Smallest normal value (2^-14)
+    assert_eq!(f16_to_bytes(6.1035156e-5), [0x00, 0x04]);
+    // Smallest subnormal value (2^-24)
+    assert_eq!(f16_to_bytes(5.9604645e-8), [0x01, 0x00]);
+    // Largest subnormal value
+    assert_eq!(f16_to_bytes(6.097555e-5), [0xFF, 0x03]);
+    // Half of the smallest subnormal rounds to even (zero), slightly more rounds up
+    assert_eq!(f16_to_bytes(2.9802322e-8), [0x00, 0x00]);
+    assert_eq!(f16_to_bytes(2.9802326e-8), [0x01, 0x00]);
+    assert_eq!(f16_to_bytes(1e-10), [0x00, 0x00]);
+    assert_eq!(f16_to_bytes(-1e-10), [0x00, 0x80]);
+    // Overflow
+    assert_eq!(f16_to_bytes(65520.0), [0x00, 0x7C]);
+    assert_eq!(f16_to_bytes(1e10), [0x00, 0x7C]);
+    assert_eq!(f16_to_bytes(::std::f32::INFINITY), [0x00, 0x7C]);
+    assert_eq!(f16_to_bytes(::std::f32::NEG_INFINITY), [0x00, 0xFC]);
+    assert_eq!(f16_to_bytes(::std::f32::NAN)[1] & 0x7E, 0x7E);
+  }
+
+  #[test]
+  fn test_f16_from_bytes() {
+    assert_eq!(f16_from_bytes([0x00, 0x00]), 0.0);
+    assert!(f16_from_bytes([0x00, 0x80]).is_sign_negative());
+    assert_eq!(f16_from_bytes([0x00, 0x3C]), 1.0);
+    assert_eq!(f16_from_bytes([0x00, 0xC0]), -2.0);
+    assert_eq!(f16_from_bytes([0x66, 0x2E]), 0.099975586);
+    assert_eq!(f16_from_bytes([0xFF, 0x7B]), 65504.0);
+    assert_eq!(f16_from_bytes([0x00, 0x04]), 6.1035156e-5);
+    assert_eq!(f16_from_bytes([0x01, 0x00]), 5.9604645e-8);
+    assert_eq!(f16_from_bytes([0x01, 0x80]), -5.9604645e-8);
+    assert_eq!(f16_from_bytes([0xFF, 0x03]), 6.097555e-5);
+    assert_eq!(f16_from_bytes([0x00, 0x7C]), ::std::f32::INFINITY);
+    assert_eq!(f16_from_bytes([0x00, 0xFC]), ::std::f32::NEG_INFINITY);
+    assert!(f16_from_bytes([0x00, 0x7E]).is_nan());
+    assert!(f16_from_bytes([0x01, 0x7C]).is_nan());
+  }
+
+  #[test]
+  fn test_interval_bytes() {
+    let interval = Interval::new(14, 3, 45_296_789);
+    let bytes = interval.to_bytes();
+    assert_eq!(bytes, [14, 0, 0, 0, 3, 0, 0, 0, 0x95, 0x2C, 0xB3, 0x02]);
+    assert_eq!(Interval::from_bytes(&bytes).unwrap(), interval);
+
+    let interval = Interval::new(u32::max_value(), 0, 1);
+    assert_eq!(Interval::from_bytes(&interval.to_bytes()).unwrap(), interval);
+    assert_eq!(Interval::from_bytes(&[0; 12]).unwrap(), Interval::default());
+
+    // Value of FIXED_LEN_BYTE_ARRAY column
+    let value = ByteArray::from(Interval::new(1, 2, 3).to_bytes().to_vec());
+    assert_eq!(Interval::from_bytes(value.data()).unwrap(), Interval::new(1, 2, 3));
+
+    assert_eq!(
+      Interval::from_bytes(&[0; 11]).unwrap_err(),
+      general_err!("INTERVAL must be 12 bytes long, found 11 bytes")
+    );
+    assert!(Interval::from_bytes(&[0; 16]).is_err());
+  }
+
+  #[test]
+  fn test_uuid_bytes() {
+    let uuid = [
+      0x12, 0x3e, 0x45, 0x67, 0xe8, 0x9b, 0x12, 0xd3,
+      0xa4, 0x56, 0x42, 0x66, 0x14, 0x17, 0x40, 0x00
+    ];
+    let value = uuid_to_bytes(uuid);
+    assert_eq!(value.data(), &uuid[..]);
+    assert_eq!(uuid_from_bytes(&value).unwrap(), uuid);
+    assert_eq!(uuid_from_bytes(&uuid_to_bytes([0; 16])).unwrap(), [0; 16]);
+
+    assert_eq!(
+      uuid_from_bytes(&ByteArray::from(vec![1, 2, 3])).unwrap_err(),
+      general_err!("UUID must be 16 bytes long, found 3 bytes")
+    );
+    assert!(uuid_from_bytes(&ByteArray::from(vec![0; 17])).is_err());
+  }
+
+  #[test]
+  fn test_f16_roundtrip() {
+    // Every half-precision value converts to f32 and back exactly
+    for i in 0..(1u32 << 16) {
+      let mut bytes = [0; 2];
+      LittleEndian::write_u16(&mut bytes, i as u16);
+      let value = f16_from_bytes(bytes);
+      if value.is_nan() {
+        assert!(f16_from_bytes(f16_to_bytes(value)).is_nan());
+      } else {
+        assert_eq!(f16_to_bytes(value), bytes);
+      }
+    }
+  }
 }
@@ -52,12 +52,83 @@ pub trait Decoder<T: DataType> {
 
   /// Returns the encoding for this decoder.
   fn encoding(&self) -> Encoding;
+
+  /// Resets the state of this decoder, so it can be reused to decode another page
+  /// after calling `set_data`. Any buffers allocated by the decoder are retained.
+  fn reset(&mut self);
+
+  /// Checks that all values set with `set_data` have been decoded, e.g. to catch a
+  /// mismatch between the number of values in page metadata and page data.
+  ///
+  /// Returns an error if there are values left to decode.
+  fn finish(&mut self) -> Result<()> {
+    let values_left = self.values_left();
+    if values_left > 0 {
+      return Err(general_err!("Expected all values to be decoded, found {} values left",
+        values_left));
+    }
+    Ok(())
+  }
+
+  /// Appends `more` bytes to the remaining undecoded data of the page set with
+  /// `set_data`, e.g. when a page is delivered in several network buffers. Values that
+  /// have already been decoded are kept, and the number of values left does not change.
+  ///
+  /// Only supported by PLAIN decoders of fixed-width types, i.e. all types except
+  /// BOOLEAN and BYTE_ARRAY, other decoders return an error.
+  fn feed(&mut self, _more: ByteBufferPtr) -> Result<()> {
+    Err(nyi_err!("Decoder for encoding {} does not support feed", self.encoding()))
+  }
+
+  /// Decodes all remaining values in batches of at most `batch_size` values and passes
+  /// each batch to `f`. Decoding stops at the first error returned by `f`, which is
+  /// propagated to the caller.
+  ///
+  /// Returns the total number of values decoded.
+  fn decode_with(
+    &mut self,
+    batch_size: usize,
+    f: &mut FnMut(&[T::T]) -> Result<()>
+  ) -> Result<usize> {
+    assert!(batch_size > 0, "Batch size must be greater than 0");
+    let mut buffer = vec![T::T::default(); batch_size];
+    let mut total_values = 0;
+    while self.values_left() > 0 {
+      let len = cmp::min(batch_size, self.values_left());
+      let num_values = self.get(&mut buffer[..len])?;
+      if num_values == 0 {
+        break;
+      }
+      f(&buffer[..num_values])?;
+      total_values += num_values;
+    }
+    Ok(total_values)
+  }
+
+  /// Decodes all remaining values into a new vector, which is sized to `values_left()`
+  /// upfront, so callers do not need to manage buffers.
+  fn decode_all(&mut self) -> Result<Vec<T::T>> {
+    let mut result = vec![T::T::default(); self.values_left()];
+    let mut num_values = 0;
+    while num_values < result.len() {
+      let len = self.get(&mut result[num_values..])?;
+      if len == 0 {
+        break;
+      }
+      num_values += len;
+    }
+    result.truncate(num_values);
+    Ok(result)
+  }
 }
 
 /// Gets a decoder for the column descriptor `descr` and encoding type `encoding`.
 ///
 /// NOTE: the primitive type in `descr` MUST match the data type `T`, otherwise
 /// disastrous consequence could occur.
+///
+/// Returns an error for BIT_PACKED encoding of INT32 values, which requires bit width,
+/// see `BitPackedDecoder`.
 pub fn get_decoder<T: DataType>(
   descr: ColumnDescPtr,
   encoding: Encoding
@@ -81,6 +152,19 @@ pub fn get_decoder<T: DataType>(
     Encoding::DELTA_BYTE_ARRAY => {
       Box::new(DeltaByteArrayDecoder::new())
     },
+    Encoding::BIT_PACKED => {
+      match T::get_physical_type() {
+        Type::BOOLEAN => Box::new(BitPackedDecoder::new(1)),
+        Type::INT32 => {
+          return Err(general_err!(
+            "Column {}: BIT_PACKED encoding of INT32 values requires bit width, \
+             use BitPackedDecoder::new()",
+            descr.path()
+          ))
+        },
+        t => return Err(nyi_err!("Encoding BIT_PACKED is not supported for type {}", t))
+      }
+    },
     e => return Err(nyi_err!("Encoding {} is not supported", e))
   };
   Ok(decoder)
@@ -103,12 +187,23 @@ pub struct PlainDecoder<T: DataType> {
   // The length for the type `T`. Only used when `T` is `FixedLenByteArrayType`
   type_length: i32,
 
-  // The byte array to decode from. Not set if `T` is bool.
+  // The byte array to decode from. If `T` is bool, values are read with `bit_reader`
+  // and this is only used to find the number of bytes left.
   data: Option<ByteBufferPtr>,
 
   // Read `data` bit by bit. Only set if `T` is bool.
   bit_reader: Option<BitReader>,
 
+  // The number of values read with `bit_reader` since `set_data`. Only used if `T` is
+  // bool.
+  bits_read: usize,
+
+  // Whether the page must contain exactly `num_values` values, see `strict()`.
+  strict: bool,
+
+  // Whether `set_data` checks the page size against `num_values`, see `check_width()`.
+  check_width: bool,
+
   // To allow `T` in the generic parameter for this struct. This doesn't take any space.
   _phantom: PhantomData<T>
 }
@@ -119,17 +214,85 @@ impl<T: DataType> PlainDecoder<T> {
     PlainDecoder {
       data: None,
       bit_reader: None,
+      bits_read: 0,
       type_length: type_length,
       num_values: 0,
       start: 0,
+      strict: false,
+      check_width: false,
       _phantom: PhantomData
     }
   }
+
+  /// Sets strict mode, disabled by default. In strict mode, the page set with
+  /// `set_data` must contain exactly the provided number of values: `get` returns an
+  /// error if the page runs out of data before all values are decoded, or if the page
+  /// has bytes left after the last value is decoded. A failed `get` does not consume
+  /// any values.
+  ///
+  /// Strict mode is only available for the PLAIN decoder; other decoders read values
+  /// from runs or blocks that do not define an exact page size.
+  pub fn strict(mut self, strict: bool) -> Self {
+    self.strict = strict;
+    self
+  }
+
+  /// Sets whether `set_data` checks that the page is large enough to hold the provided
+  /// number of values of type `T`, disabled by default. If the page is too small,
+  /// e.g. when the decoder type does not match the column type, `set_data` returns
+  /// `ParquetError::TypeMismatch`.
+  ///
+  /// Should only be enabled for columns without nulls, because the number of values
+  /// passed to `set_data` includes null values, which are not stored in the page.
+  pub fn check_width(mut self, check_width: bool) -> Self {
+    self.check_width = check_width;
+    self
+  }
+
+  // If `check_width` is enabled, checks that `data_len` bytes can hold `num_values`
+  // values of type `T`. Byte arrays are checked against their length prefixes only.
+  fn check_type_width(&self, data_len: usize, num_values: usize) -> Result<()> {
+    if !self.check_width {
+      return Ok(());
+    }
+    let min_bytes = match T::get_physical_type() {
+      Type::BOOLEAN => (num_values + 7) / 8,
+      Type::BYTE_ARRAY => mem::size_of::<u32>() * num_values,
+      Type::FIXED_LEN_BYTE_ARRAY => cmp::max(self.type_length, 0) as usize * num_values,
+      _ => T::byte_width().unwrap_or(0) * num_values
+    };
+    if data_len < min_bytes {
+      return Err(type_mismatch_err!(
+        "{} values of type {} require at least {} bytes, but page has {} bytes",
+        num_values, T::get_physical_type(), min_bytes, data_len));
+    }
+    Ok(())
+  }
+
+  // Returns number of bytes in the page.
+  fn data_len(&self) -> usize {
+    self.data.as_ref().map(|data| data.len()).unwrap_or(0)
+  }
+
+  // In strict mode, checks that a read of `num_values` values ending at byte offset
+  // `end` leaves no bytes in the page, if it decodes the last values of the page.
+  // Called before the read is committed, so that an error does not consume values.
+  fn check_page_end(&self, end: usize, num_values: usize) -> Result<()> {
+    if self.strict && num_values == self.num_values {
+      let bytes_left = self.data_len().saturating_sub(end);
+      if bytes_left > 0 {
+        return Err(general_err!(
+          "Page has {} bytes left after decoding all values", bytes_left));
+      }
+    }
+    Ok(())
+  }
 }
 
 impl<T: DataType> Decoder<T> for PlainDecoder<T> {
   #[inline]
   default fn set_data(&mut self, data: ByteBufferPtr, num_values: usize) -> Result<()> {
+    self.check_type_width(data.len(), num_values)?;
     self.num_values = num_values;
     self.start = 0;
     self.data = Some(data);
@@ -146,17 +309,50 @@ impl<T: DataType> Decoder<T> for PlainDecoder<T> {
     Encoding::PLAIN
   }
 
+  #[inline]
+  fn reset(&mut self) {
+    self.num_values = 0;
+    self.start = 0;
+    self.data = None;
+    self.bit_reader = None;
+    self.bits_read = 0;
+  }
+
+  fn finish(&mut self) -> Result<()> {
+    if self.num_values > 0 {
+      return Err(general_err!("Expected all values to be decoded, found {} values left",
+        self.num_values));
+    }
+    let end = match self.bit_reader {
+      Some(ref bit_reader) => bit_reader.get_byte_offset(),
+      None => self.start
+    };
+    self.check_page_end(end, 0)
+  }
+
+  default fn feed(&mut self, more: ByteBufferPtr) -> Result<()> {
+    let mut data = match self.data {
+      Some(ref data) => data.start_from(self.start).data().to_vec(),
+      None => return Err(general_err!("Data is not set, call set_data() before feed()"))
+    };
+    data.extend_from_slice(more.data());
+    self.data = Some(ByteBufferPtr::new(data));
+    self.start = 0;
+    Ok(())
+  }
+
   #[inline]
   default fn get(&mut self, buffer: &mut [T::T]) -> Result<usize> {
     assert!(self.data.is_some());
 
-    let data = self.data.as_mut().unwrap();
+    let data = self.data.as_ref().unwrap();
     let num_values = cmp::min(buffer.len(), self.num_values);
     let bytes_left = data.len() - self.start;
     let bytes_to_decode = mem::size_of::<T::T>() * num_values;
     if bytes_left < bytes_to_decode {
       return Err(eof_err!("Not enough bytes to decode"));
     }
+    self.check_page_end(self.start + bytes_to_decode, num_values)?;
     let raw_buffer: &mut [u8] = unsafe {
       from_raw_parts_mut(buffer.as_ptr() as *mut u8, bytes_to_decode)
     };
@@ -179,6 +375,7 @@ impl Decoder<Int96Type> for PlainDecoder<Int96Type> {
     if bytes_left < bytes_to_decode {
       return Err(eof_err!("Not enough bytes to decode"));
     }
+    self.check_page_end(self.start + bytes_to_decode, num_values)?;
 
     let data_range = data.range(self.start, bytes_to_decode);
     let bytes: &[u8] = data_range.data();
@@ -200,16 +397,33 @@ impl Decoder<Int96Type> for PlainDecoder<Int96Type> {
 
 impl Decoder<BoolType> for PlainDecoder<BoolType> {
   fn set_data(&mut self, data: ByteBufferPtr, num_values: usize) -> Result<()> {
+    self.check_type_width(data.len(), num_values)?;
     self.num_values = num_values;
-    self.bit_reader = Some(BitReader::new(data));
+    self.bit_reader = Some(BitReader::new(data.all()));
+    self.bits_read = 0;
+    self.data = Some(data);
     Ok(())
   }
 
+  fn feed(&mut self, _more: ByteBufferPtr) -> Result<()> {
+    Err(nyi_err!("PLAIN decoder for BOOLEAN does not support feed"))
+  }
+
   fn get(&mut self, buffer: &mut [bool]) -> Result<usize> {
     assert!(self.bit_reader.is_some());
 
-    let bit_reader = self.bit_reader.as_mut().unwrap();
-    let values_read = bit_reader.get_batch::<bool>(buffer, 1);
+    let num_values = cmp::min(buffer.len(), self.num_values);
+    if self.strict {
+      // Each value takes one bit, the last byte is padded
+      let end = (self.bits_read + num_values + 7) / 8;
+      if end > self.data_len() {
+        return Err(eof_err!("Not enough bytes to decode"));
+      }
+      self.check_page_end(end, num_values)?;
+    }
+    let values_read = self.bit_reader.as_mut().unwrap()
+      .get_batch::<bool>(&mut buffer[..num_values], 1);
+    self.bits_read += values_read;
     self.num_values -= values_read;
 
     Ok(values_read)
@@ -217,39 +431,103 @@ impl Decoder<BoolType> for PlainDecoder<BoolType> {
 }
 
 impl Decoder<ByteArrayType> for PlainDecoder<ByteArrayType> {
+  fn feed(&mut self, _more: ByteBufferPtr) -> Result<()> {
+    Err(nyi_err!("PLAIN decoder for BYTE_ARRAY does not support feed"))
+  }
+
   fn get(&mut self, buffer: &mut [ByteArray]) -> Result<usize> {
     assert!(self.data.is_some());
 
-    let data = self.data.as_mut().unwrap();
+    let data = self.data.as_ref().unwrap();
     let num_values = cmp::min(buffer.len(), self.num_values);
+    // Values are decoded from a local offset, which is only committed on success
+    let mut start = self.start;
     for i in 0..num_values {
+      if data.len() < start + mem::size_of::<u32>() {
+        return Err(eof_err!("Not enough bytes to decode"));
+      }
       let len: usize = read_num_bytes!(
-        u32, 4, data.start_from(self.start).as_ref()) as usize;
-      self.start += mem::size_of::<u32>();
-      if data.len() < self.start + len {
+        u32, 4, data.start_from(start).as_ref()) as usize;
+      start += mem::size_of::<u32>();
+      // Length prefix of a corrupt or truncated page can point past the end of data
+      if data.len() < start + len {
         return Err(eof_err!("Not enough bytes to decode"));
       }
-      buffer[i].set_data(data.range(self.start, len));
-      self.start += len;
+      buffer[i].set_data(data.range(start, len));
+      start += len;
     }
+    self.check_page_end(start, num_values)?;
+    self.start = start;
     self.num_values -= num_values;
 
     Ok(num_values)
   }
 }
 
+impl PlainDecoder<ByteArrayType> {
+  /// Decodes all remaining values into a single contiguous buffer `out` and appends
+  /// Arrow-style offsets into `offsets`: value `i` is stored in
+  /// `out[offsets[i]..offsets[i + 1]]`. If `offsets` is empty, the starting offset is
+  /// added first, otherwise decoded values are appended to the existing content.
+  ///
+  /// Returns the number of values decoded.
+  pub fn get_into_buffer(
+    &mut self,
+    out: &mut Vec<u8>,
+    offsets: &mut Vec<i32>
+  ) -> Result<usize> {
+    assert!(self.data.is_some());
+
+    let data = self.data.as_ref().unwrap();
+    let num_values = self.num_values;
+    // Find the end of the values first, so that `out` and `offsets` are left
+    // unchanged on error
+    let mut end = self.start;
+    for _ in 0..num_values {
+      if data.len() < end + mem::size_of::<u32>() {
+        return Err(eof_err!("Not enough bytes to decode"));
+      }
+      let len: usize = read_num_bytes!(
+        u32, 4, data.start_from(end).as_ref()) as usize;
+      end += mem::size_of::<u32>();
+      if data.len() < end + len {
+        return Err(eof_err!("Not enough bytes to decode"));
+      }
+      end += len;
+    }
+    self.check_page_end(end, num_values)?;
+
+    if offsets.is_empty() {
+      offsets.push(out.len() as i32);
+    }
+    for _ in 0..num_values {
+      let len: usize = read_num_bytes!(
+        u32, 4, data.start_from(self.start).as_ref()) as usize;
+      self.start += mem::size_of::<u32>();
+      out.extend_from_slice(&data.as_ref()[self.start..self.start + len]);
+      offsets.push(out.len() as i32);
+      self.start += len;
+    }
+    self.num_values = 0;
+
+    Ok(num_values)
+  }
+}
+
 impl Decoder<FixedLenByteArrayType> for PlainDecoder<FixedLenByteArrayType> {
   fn get(&mut self, buffer: &mut [ByteArray]) -> Result<usize> {
     assert!(self.data.is_some());
     assert!(self.type_length > 0);
 
-    let data = self.data.as_mut().unwrap();
+    let data = self.data.as_ref().unwrap();
     let type_length = self.type_length as usize;
     let num_values = cmp::min(buffer.len(), self.num_values);
+    // Check all values upfront, so that no values are consumed on error
+    if data.len() < self.start + type_length * num_values {
+      return Err(eof_err!("Not enough bytes to decode"));
+    }
+    self.check_page_end(self.start + type_length * num_values, num_values)?;
     for i in 0..num_values {
-      if data.len() < self.start + type_length {
-        return Err(eof_err!("Not enough bytes to decode"));
-      }
       buffer[i].set_data(data.range(self.start, type_length));
       self.start += type_length;
     }
@@ -299,12 +577,76 @@ impl<T: DataType> DictDecoder<T> {
     self.has_dictionary = true;
     Ok(())
   }
+
+  /// Decodes and sets values for dictionary from a dictionary page with `encoding`
+  /// using `decoder`. Dictionary pages should be PLAIN encoded (PLAIN_DICTIONARY in
+  /// older files also means PLAIN), but some writers use other encodings, e.g.
+  /// DELTA_BINARY_PACKED, so any encoding with a decoder is accepted.
+  ///
+  /// Returns an error if `decoder` does not decode `encoding`, or if `encoding` is
+  /// RLE_DICTIONARY.
+  pub fn set_dict_with_encoding(
+    &mut self,
+    decoder: Box<Decoder<T>>,
+    encoding: Encoding
+  ) -> Result<()> {
+    let expected_encoding = match encoding {
+      Encoding::PLAIN_DICTIONARY => Encoding::PLAIN,
+      Encoding::RLE_DICTIONARY => {
+        return Err(general_err!("Invalid dictionary page encoding {}", encoding));
+      },
+      enc => enc
+    };
+    if decoder.encoding() != expected_encoding {
+      return Err(general_err!(
+        "Dictionary page encoding {} does not match decoder encoding {}",
+        encoding,
+        decoder.encoding()
+      ));
+    }
+    self.set_dict(decoder)
+  }
+
+  /// Decodes the remaining values of the data page as dictionary indices and returns
+  /// them together with a copy of the dictionary, so that callers, e.g. Arrow, can
+  /// build a dictionary array without looking up or re-deduplicating values. Value of
+  /// `i`th row is `dictionary[indices[i]]`.
+  ///
+  /// Returns an error if any index is out of range for the dictionary.
+  pub fn into_arrow_parts(&mut self) -> Result<(Vec<T::T>, Vec<i32>)> {
+    assert!(self.rle_decoder.is_some());
+    assert!(self.has_dictionary, "Must call set_dict() first!");
+
+    let rle = self.rle_decoder.as_mut().unwrap();
+    let mut indices = vec![0; self.num_values];
+    let values_read = rle.get_batch::<i32>(&mut indices[..])?;
+    indices.truncate(values_read);
+    self.num_values -= values_read;
+
+    for index in &indices {
+      if *index < 0 || *index as usize >= self.dictionary.len() {
+        return Err(general_err!(
+          "Dictionary index {} is out of range for dictionary with {} entries",
+          index,
+          self.dictionary.len()
+        ));
+      }
+    }
+    Ok((self.dictionary.clone(), indices))
+  }
 }
 
 impl<T: DataType> Decoder<T> for DictDecoder<T> {
   fn set_data(&mut self, data: ByteBufferPtr, num_values: usize) -> Result<()> {
     // First byte in `data` is bit width
+    if data.len() == 0 {
+      return Err(eof_err!("Not enough bytes to decode bit width"));
+    }
     let bit_width = data.as_ref()[0];
+    // Indices are non-negative 32-bit integers
+    if bit_width > 32 {
+      return Err(general_err!("Invalid dictionary index bit width {}", bit_width));
+    }
     let mut rle_decoder = RleDecoder::new(bit_width);
     rle_decoder.set_data(data.start_from(1));
     self.num_values = num_values;
@@ -318,7 +660,9 @@ impl<T: DataType> Decoder<T> for DictDecoder<T> {
 
     let rle = self.rle_decoder.as_mut().unwrap();
     let num_values = cmp::min(buffer.len(), self.num_values);
-    rle.get_batch_with_dict(&self.dictionary[..], buffer, num_values)
+    let values_read = rle.get_batch_with_dict(&self.dictionary[..], buffer, num_values)?;
+    self.num_values -= values_read;
+    Ok(values_read)
   }
 
   /// Number of values left in this decoder stream
@@ -329,29 +673,56 @@ impl<T: DataType> Decoder<T> for DictDecoder<T> {
   fn encoding(&self) -> Encoding {
     Encoding::RLE_DICTIONARY
   }
+
+  /// Resets data page state, dictionary is kept until a new one is set with
+  /// `set_dict()`.
+  fn reset(&mut self) {
+    self.rle_decoder = None;
+    self.num_values = 0;
+  }
 }
 
 // ----------------------------------------------------------------------
 // RLE Decoding
 
 /// RLE/Bit-Packing hybrid decoding for values.
-/// Currently is used only for data pages v2 and supports boolean types.
+/// Currently is used only for data pages v2 and supports boolean and INT32 types.
 /// See [`RleValueEncoder`](`::encoding::RleValueEncoder`) for more information.
 pub struct RleValueDecoder<T: DataType> {
   values_left: usize,
+  bit_width: u8,
   decoder: Option<RleDecoder>,
   _phantom: PhantomData<T>
 }
 
 impl<T: DataType> RleValueDecoder<T> {
   pub fn new() -> Self {
+    let bit_width = match T::get_physical_type() {
+      Type::BOOLEAN => 1,
+      _ => 32
+    };
     Self {
       values_left: 0,
+      bit_width: bit_width,
       decoder: None,
       _phantom: PhantomData
     }
   }
 
+  /// Sets bit width of encoded values and returns itself, must match the bit width of
+  /// [`RleValueEncoder`](`::encoding::RleValueEncoder`).
+  ///
+  /// Panics if `bit_width` is 0 or greater than 32.
+  pub fn with_bit_width(mut self, bit_width: u8) -> Self {
+    assert!(
+      bit_width > 0 && bit_width <= 32,
+      "Invalid bit width {} for RLE encoding",
+      bit_width
+    );
+    self.bit_width = bit_width;
+    self
+  }
+
   #[inline]
   fn set_data_internal(
     &mut self, data: ByteBufferPtr,
@@ -374,7 +745,7 @@ impl<T: DataType> Decoder<T> for RleValueDecoder<T> {
     _data: ByteBufferPtr,
     _num_values: usize
   ) -> Result<()> {
-    panic!("RleValueDecoder only supports BoolType");
+    panic!("RleValueDecoder only supports BoolType and Int32Type");
   }
 
   #[inline]
@@ -387,10 +758,17 @@ impl<T: DataType> Decoder<T> for RleValueDecoder<T> {
     Encoding::RLE
   }
 
+  #[inline]
+  fn reset(&mut self) {
+    self.values_left = 0;
+    self.decoder = None;
+  }
+
   #[inline]
   fn get(&mut self, buffer: &mut [T::T]) -> Result<usize> {
     let rle_decoder = self.decoder.as_mut().expect("RLE decoder is not initialized");
-    let values_read = rle_decoder.get_batch(buffer)?;
+    let num_values = cmp::min(buffer.len(), self.values_left);
+    let values_read = rle_decoder.get_batch(&mut buffer[..num_values])?;
     self.values_left -= values_read;
     Ok(values_read)
   }
@@ -399,12 +777,163 @@ impl<T: DataType> Decoder<T> for RleValueDecoder<T> {
 impl Decoder<BoolType> for RleValueDecoder<BoolType> {
   #[inline]
   fn set_data(&mut self, data: ByteBufferPtr, num_values: usize) -> Result<()> {
-    // Only support RLE value reader for boolean values with bit width of 1.
-    self.decoder = Some(RleDecoder::new(1));
+    self.decoder = Some(RleDecoder::new(self.bit_width));
+    self.set_data_internal(data, num_values)
+  }
+}
+
+impl Decoder<Int32Type> for RleValueDecoder<Int32Type> {
+  #[inline]
+  fn set_data(&mut self, data: ByteBufferPtr, num_values: usize) -> Result<()> {
+    self.decoder = Some(RleDecoder::new(self.bit_width));
     self.set_data_internal(data, num_values)
   }
 }
 
+// ----------------------------------------------------------------------
+// BIT_PACKED Decoding
+
+/// Bit-packed decoding for values, supports boolean and INT32 types. Values are packed
+/// from the most significant bit to the least significant bit.
+/// See [`BitPackedEncoder`](`::encoding::BitPackedEncoder`) for more information.
+pub struct BitPackedDecoder<T: DataType> {
+  bit_width: u8,
+  values_left: usize,
+  data: Option<ByteBufferPtr>,
+  // Offset of the next value in bits
+  bit_offset: usize,
+  _phantom: PhantomData<T>
+}
+
+impl<T: DataType> BitPackedDecoder<T> {
+  /// Creates new bit packed decoder for values packed with `bit_width` bits.
+  ///
+  /// Panics if `bit_width` is greater than 32.
+  pub fn new(bit_width: u8) -> Self {
+    assert!(bit_width <= 32, "Invalid bit width {} for BIT_PACKED encoding", bit_width);
+    Self {
+      bit_width: bit_width,
+      values_left: 0,
+      data: None,
+      bit_offset: 0,
+      _phantom: PhantomData
+    }
+  }
+
+  #[inline]
+  fn set_data_internal(&mut self, data: ByteBufferPtr, num_values: usize) -> Result<()> {
+    let num_bytes = (num_values * self.bit_width as usize + 7) / 8;
+    if data.len() < num_bytes {
+      return Err(eof_err!(
+        "{} values of {} bits require {} bytes, but page has {} bytes",
+        num_values,
+        self.bit_width,
+        num_bytes,
+        data.len()
+      ));
+    }
+    self.data = Some(data);
+    self.bit_offset = 0;
+    self.values_left = num_values;
+    Ok(())
+  }
+
+  // Decodes up to `buffer.len()` values with `convert` applied to each value.
+  #[inline]
+  fn get_internal<F: Fn(u64) -> T::T>(
+    &mut self,
+    buffer: &mut [T::T],
+    convert: F
+  ) -> Result<usize> {
+    let data = self.data.as_ref().expect("Data is not set");
+    let bit_width = self.bit_width as usize;
+    let num_values = cmp::min(buffer.len(), self.values_left);
+    for i in 0..num_values {
+      let offset = self.bit_offset + i * bit_width;
+      buffer[i] = convert(read_msb_value(data.data(), offset, bit_width));
+    }
+    self.bit_offset += num_values * bit_width;
+    self.values_left -= num_values;
+    Ok(num_values)
+  }
+}
+
+// Reads value of `bit_width` bits at bit offset `offset` of `data`, where bits are
+// ordered from the most significant bit of each byte.
+#[inline]
+fn read_msb_value(data: &[u8], offset: usize, bit_width: usize) -> u64 {
+  let mut value = 0u64;
+  let mut offset = offset;
+  let mut bits_left = bit_width;
+  while bits_left > 0 {
+    let bit_in_byte = offset % 8;
+    let num_bits = cmp::min(bits_left, 8 - bit_in_byte);
+    let byte = data[offset / 8] as u64;
+    let bits = (byte >> (8 - bit_in_byte - num_bits)) & ((1 << num_bits) - 1);
+    value = (value << num_bits) | bits;
+    offset += num_bits;
+    bits_left -= num_bits;
+  }
+  value
+}
+
+impl<T: DataType> Decoder<T> for BitPackedDecoder<T> {
+  #[inline]
+  default fn set_data(
+    &mut self,
+    _data: ByteBufferPtr,
+    _num_values: usize
+  ) -> Result<()> {
+    panic!("BitPackedDecoder only supports BoolType and Int32Type");
+  }
+
+  #[inline]
+  default fn get(&mut self, _buffer: &mut [T::T]) -> Result<usize> {
+    panic!("BitPackedDecoder only supports BoolType and Int32Type");
+  }
+
+  #[inline]
+  fn values_left(&self) -> usize {
+    self.values_left
+  }
+
+  #[inline]
+  fn encoding(&self) -> Encoding {
+    Encoding::BIT_PACKED
+  }
+
+  #[inline]
+  fn reset(&mut self) {
+    self.values_left = 0;
+    self.data = None;
+    self.bit_offset = 0;
+  }
+}
+
+impl Decoder<BoolType> for BitPackedDecoder<BoolType> {
+  #[inline]
+  fn set_data(&mut self, data: ByteBufferPtr, num_values: usize) -> Result<()> {
+    self.set_data_internal(data, num_values)
+  }
+
+  #[inline]
+  fn get(&mut self, buffer: &mut [bool]) -> Result<usize> {
+    self.get_internal(buffer, |v| v != 0)
+  }
+}
+
+impl Decoder<Int32Type> for BitPackedDecoder<Int32Type> {
+  #[inline]
+  fn set_data(&mut self, data: ByteBufferPtr, num_values: usize) -> Result<()> {
+    self.set_data_internal(data, num_values)
+  }
+
+  #[inline]
+  fn get(&mut self, buffer: &mut [i32]) -> Result<usize> {
+    self.get_internal(buffer, |v| v as u32 as i32)
+  }
+}
+
 // ----------------------------------------------------------------------
 // DELTA_BINARY_PACKED Decoding
 
@@ -417,6 +946,7 @@ pub struct DeltaBitPackDecoder<T: DataType> {
 
   // Header info
   num_values: usize,
+  header_num_values: usize,
   num_mini_blocks: i64,
   values_per_mini_block: usize,
   values_current_mini_block: usize,
@@ -433,6 +963,11 @@ pub struct DeltaBitPackDecoder<T: DataType> {
 
   current_value: i64,
 
+  // Number of values returned by `get` and number of bytes in the page, used to verify
+  // decoder state, see `verify_consistency()`
+  num_decoded_values: usize,
+  num_bytes: usize,
+
   _phantom: PhantomData<T>
 }
 
@@ -443,6 +978,7 @@ impl<T: DataType> DeltaBitPackDecoder<T> {
       bit_reader: BitReader::from(vec![]),
       initialized: false,
       num_values: 0,
+      header_num_values: 0,
       num_mini_blocks: 0,
       values_per_mini_block: 0,
       values_current_mini_block: 0,
@@ -455,6 +991,8 @@ impl<T: DataType> DeltaBitPackDecoder<T> {
       deltas_in_mini_block: vec![],
       use_batch: mem::size_of::<T::T>() == 4,
       current_value: 0,
+      num_decoded_values: 0,
+      num_bytes: 0,
       _phantom: PhantomData
     }
   }
@@ -465,6 +1003,55 @@ impl<T: DataType> DeltaBitPackDecoder<T> {
     self.bit_reader.get_byte_offset()
   }
 
+  /// Checks that the decoder state agrees with the page header: number of values left
+  /// and number of values decoded add up to the total number of values in the header,
+  /// and values left that do not fit into the current block can still be read from
+  /// the page, i.e. the page has enough bytes left for the next block header.
+  ///
+  /// Returns an error if data is not set or the state is not consistent, e.g. when the
+  /// header declares more values than the page contains.
+  pub fn verify_consistency(&self) -> Result<()> {
+    if !self.initialized {
+      return Err(general_err!("Data is not set, call set_data() before verification"));
+    }
+    if self.num_values + self.num_decoded_values != self.header_num_values {
+      return Err(general_err!(
+        "Decoder has {} values left and {} values decoded, but page header has {} values",
+        self.num_values,
+        self.num_decoded_values,
+        self.header_num_values
+      ));
+    }
+    // Values that can be decoded without reading another block: the first value and
+    // the rest of the values in the current block
+    let mut block_values = self.values_current_mini_block;
+    if self.delta_bit_widths.size() > 0 {
+      // Mini block index is out of range when the next block failed to load
+      let mini_blocks_left = self.delta_bit_widths.size()
+        .checked_sub(self.mini_block_idx + 1)
+        .ok_or_else(|| general_err!(
+          "Mini block {} is out of range, block has {} mini blocks",
+          self.mini_block_idx,
+          self.delta_bit_widths.size()
+        ))?;
+      block_values += mini_blocks_left * self.values_per_mini_block;
+    }
+    if !self.first_value_read {
+      block_values += 1;
+    }
+    // Next block starts with at least 1 byte of min delta and bit widths of mini blocks
+    let bytes_left = self.num_bytes.saturating_sub(self.bit_reader.get_byte_offset());
+    if self.num_values > block_values &&
+        bytes_left < 1 + self.num_mini_blocks as usize {
+      return Err(general_err!(
+        "Page header has {} values left, but page has only {} bytes left",
+        self.num_values,
+        bytes_left
+      ));
+    }
+    Ok(())
+  }
+
   /// Initializes new mini block.
   #[inline]
   fn init_block(&mut self) -> Result<()> {
@@ -515,6 +1102,7 @@ impl<T: DataType> Decoder<T> for DeltaBitPackDecoder<T> {
   // # of total values is derived from encoding
   #[inline]
   default fn set_data(&mut self, data: ByteBufferPtr, _: usize) -> Result<()> {
+    self.num_bytes = data.len();
     self.bit_reader = BitReader::new(data);
     self.initialized = true;
 
@@ -532,6 +1120,8 @@ impl<T: DataType> Decoder<T> for DeltaBitPackDecoder<T> {
       .ok_or(eof_err!("Not enough data to decode 'first_value'"))?;
 
     // Reset decoding state
+    self.header_num_values = self.num_values;
+    self.num_decoded_values = 0;
     self.first_value_read = false;
     self.mini_block_idx = 0;
     self.delta_bit_widths.clear();
@@ -579,7 +1169,18 @@ impl<T: DataType> Decoder<T> for DeltaBitPackDecoder<T> {
       self.values_current_mini_block -= 1;
     }
 
+    // Mini block index only goes out of range when loading the next block fails
+    debug_assert!(
+      self.delta_bit_widths.size() == 0 ||
+        self.mini_block_idx < self.delta_bit_widths.size(),
+      "Mini block {} is out of range, block has {} mini blocks",
+      self.mini_block_idx,
+      self.delta_bit_widths.size()
+    );
+    debug_assert!(self.values_current_mini_block <= self.deltas_in_mini_block.len());
+
     self.num_values -= num_values;
+    self.num_decoded_values += num_values;
     Ok(num_values)
   }
 
@@ -590,6 +1191,21 @@ impl<T: DataType> Decoder<T> for DeltaBitPackDecoder<T> {
   fn encoding(&self) -> Encoding {
     Encoding::DELTA_BINARY_PACKED
   }
+
+  fn reset(&mut self) {
+    self.bit_reader = BitReader::from(vec![]);
+    self.initialized = false;
+    self.num_values = 0;
+    self.header_num_values = 0;
+    self.values_current_mini_block = 0;
+    self.first_value_read = false;
+    self.mini_block_idx = 0;
+    self.delta_bit_widths.clear();
+    self.deltas_in_mini_block.clear();
+    self.current_value = 0;
+    self.num_decoded_values = 0;
+    self.num_bytes = 0;
+  }
 }
 
 /// Helper trait to define specific conversions when decoding values
@@ -664,6 +1280,9 @@ pub struct DeltaLengthByteArrayDecoder<T: DataType> {
   // Number of values left in this decoder stream
   num_values: usize,
 
+  // Number of values in the header of lengths, set by `set_data`
+  inferred_num_values: Option<usize>,
+
   // Placeholder to allow `T` as generic parameter
   _phantom: PhantomData<T>
 }
@@ -677,9 +1296,17 @@ impl<T: DataType> DeltaLengthByteArrayDecoder<T> {
       data: None,
       offset: 0,
       num_values: 0,
+      inferred_num_values: None,
       _phantom: PhantomData
     }
   }
+
+  /// Returns total number of values in the page set with `set_data`, as stored in the
+  /// header of DELTA_BINARY_PACKED encoded lengths, or `None` if data is not set. Can
+  /// be compared with the number of values in page metadata.
+  pub fn inferred_num_values(&self) -> Option<usize> {
+    self.inferred_num_values
+  }
 }
 
 impl<T: DataType> Decoder<T> for DeltaLengthByteArrayDecoder<T> {
@@ -698,6 +1325,15 @@ impl<T: DataType> Decoder<T> for DeltaLengthByteArrayDecoder<T> {
   fn encoding(&self) -> Encoding {
     Encoding::DELTA_LENGTH_BYTE_ARRAY
   }
+
+  fn reset(&mut self) {
+    self.lengths.clear();
+    self.current_idx = 0;
+    self.data = None;
+    self.offset = 0;
+    self.num_values = 0;
+    self.inferred_num_values = None;
+  }
 }
 
 impl Decoder<ByteArrayType> for DeltaLengthByteArrayDecoder<ByteArrayType> {
@@ -708,10 +1344,29 @@ impl Decoder<ByteArrayType> for DeltaLengthByteArrayDecoder<ByteArrayType> {
     self.lengths.resize(num_lengths, 0);
     len_decoder.get(&mut self.lengths[..])?;
 
-    self.data = Some(data.start_from(len_decoder.get_offset()));
+    // Lengths of a corrupt page may not cover the data region exactly, which would
+    // result in short or overlong values
+    let data = data.start_from(len_decoder.get_offset());
+    let mut total_len: usize = 0;
+    for len in &self.lengths {
+      if *len < 0 {
+        return Err(general_err!("Invalid byte array length {}", len));
+      }
+      total_len += *len as usize;
+    }
+    if total_len != data.len() {
+      return Err(general_err!(
+        "Sum of byte array lengths {} does not match data length {}",
+        total_len,
+        data.len()
+      ));
+    }
+
+    self.data = Some(data);
     self.offset = 0;
     self.current_idx = 0;
     self.num_values = num_lengths;
+    self.inferred_num_values = Some(num_lengths);
     Ok(())
   }
 
@@ -757,6 +1412,9 @@ pub struct DeltaByteArrayDecoder<T: DataType> {
   // Number of values left
   num_values: usize,
 
+  // Number of values in the header of prefix lengths, set by `set_data`
+  inferred_num_values: Option<usize>,
+
   // Placeholder to allow `T` as generic parameter
   _phantom: PhantomData<T>
 }
@@ -770,9 +1428,17 @@ impl<T: DataType> DeltaByteArrayDecoder<T> {
       suffix_decoder: None,
       previous_value: vec![],
       num_values: 0,
+      inferred_num_values: None,
       _phantom: PhantomData
     }
   }
+
+  /// Returns total number of values in the page set with `set_data`, as stored in the
+  /// header of DELTA_BINARY_PACKED encoded prefix lengths, or `None` if data is not
+  /// set. Can be compared with the number of values in page metadata.
+  pub fn inferred_num_values(&self) -> Option<usize> {
+    self.inferred_num_values
+  }
 }
 
 impl<'m, T: DataType> Decoder<T> for DeltaByteArrayDecoder<T> {
@@ -791,6 +1457,15 @@ impl<'m, T: DataType> Decoder<T> for DeltaByteArrayDecoder<T> {
   fn encoding(&self) -> Encoding {
     Encoding::DELTA_BYTE_ARRAY
   }
+
+  fn reset(&mut self) {
+    self.prefix_lengths.clear();
+    self.current_idx = 0;
+    self.suffix_decoder = None;
+    self.previous_value.clear();
+    self.num_values = 0;
+    self.inferred_num_values = None;
+  }
 }
 
 impl<> Decoder<ByteArrayType> for DeltaByteArrayDecoder<ByteArrayType> {
@@ -806,6 +1481,7 @@ impl<> Decoder<ByteArrayType> for DeltaByteArrayDecoder<ByteArrayType> {
       data.start_from(prefix_len_decoder.get_offset()), num_values)?;
     self.suffix_decoder = Some(suffix_decoder);
     self.num_values = num_prefixes;
+    self.inferred_num_values = Some(num_prefixes);
     self.current_idx = 0;
     self.previous_value.clear();
     Ok(())
@@ -846,13 +1522,13 @@ impl<> Decoder<ByteArrayType> for DeltaByteArrayDecoder<ByteArrayType> {
 #[cfg(test)]
 mod tests {
   use super::super::encoding::*;
+  use super::super::rle::RleEncoder;
   use super::*;
-  use schema::types::{ColumnDescriptor, ColumnPath, Type as Tpe};
   use std::mem;
   use std::rc::Rc;
   use util::bit_util::set_array_bit;
   use util::memory::MemTracker;
-  use util::test_common::RandGen;
+  use util::test_common::{make_col_desc, RandGen};
 
   #[test]
   fn test_get_decoders() {
@@ -862,6 +1538,7 @@ mod tests {
     test_get_decoder::<Int32Type>(Encoding::DELTA_LENGTH_BYTE_ARRAY, None);
     test_get_decoder::<Int32Type>(Encoding::DELTA_BYTE_ARRAY, None);
     test_get_decoder::<BoolType>(Encoding::RLE, None);
+    test_get_decoder::<BoolType>(Encoding::BIT_PACKED, None);
 
     // error when initializing
     test_get_decoder::<Int32Type>(
@@ -873,13 +1550,265 @@ mod tests {
       Some(general_err!("Cannot initialize this encoding through this function"))
     );
 
-    // unsupported
     test_get_decoder::<Int32Type>(
       Encoding::BIT_PACKED,
-      Some(nyi_err!("Encoding BIT_PACKED is not supported"))
+      Some(general_err!(
+        "Column \"col\": BIT_PACKED encoding of INT32 values requires bit width, \
+         use BitPackedDecoder::new()"
+      ))
+    );
+
+    // unsupported
+    test_get_decoder::<Int64Type>(
+      Encoding::BIT_PACKED,
+      Some(nyi_err!("Encoding BIT_PACKED is not supported for type INT64"))
     );
   }
 
+  #[test]
+  fn test_decoder_encoding_matches_encoder() {
+    test_decoder_encoding::<Int32Type>(Encoding::PLAIN);
+    test_decoder_encoding::<ByteArrayType>(Encoding::PLAIN);
+    test_decoder_encoding::<BoolType>(Encoding::RLE);
+    test_decoder_encoding::<BoolType>(Encoding::BIT_PACKED);
+    test_decoder_encoding::<Int32Type>(Encoding::DELTA_BINARY_PACKED);
+    test_decoder_encoding::<Int64Type>(Encoding::DELTA_BINARY_PACKED);
+    test_decoder_encoding::<ByteArrayType>(Encoding::DELTA_LENGTH_BYTE_ARRAY);
+    test_decoder_encoding::<ByteArrayType>(Encoding::DELTA_BYTE_ARRAY);
+
+    // Dictionary encoder writes PLAIN_DICTIONARY (data page V1) encoding, while decoder
+    // reports RLE_DICTIONARY, both describe the same RLE encoded indices.
+    let mem_tracker = Rc::new(MemTracker::new());
+    let encoder = DictEncoder::<Int32Type>::new(get_test_column_desc_ptr(), mem_tracker);
+    let decoder = DictDecoder::<Int32Type>::new();
+    assert_eq!(encoder.encoding(), Encoding::PLAIN_DICTIONARY);
+    assert_eq!(decoder.encoding(), Encoding::RLE_DICTIONARY);
+  }
+
+  #[test]
+  fn test_decode_with() {
+    let data: Vec<i32> = (0..1000).collect();
+    let mem_tracker = Rc::new(MemTracker::new());
+    let mut encoder = get_encoder::<Int32Type>(
+      get_test_column_desc_ptr(), Encoding::DELTA_BINARY_PACKED, mem_tracker
+    ).unwrap();
+    encoder.put(&data[..]).unwrap();
+    let bytes = encoder.flush_buffer().unwrap();
+
+    let mut decoder =
+      get_decoder::<Int32Type>(get_test_column_desc_ptr(), Encoding::DELTA_BINARY_PACKED)
+        .unwrap();
+    decoder.set_data(bytes.all(), data.len()).unwrap();
+    let mut batch_lengths = vec![];
+    let mut result = vec![];
+    let num_values = decoder.decode_with(64, &mut |values: &[i32]| {
+      batch_lengths.push(values.len());
+      result.extend_from_slice(values);
+      Ok(())
+    }).unwrap();
+    assert_eq!(num_values, data.len());
+    assert_eq!(batch_lengths.iter().sum::<usize>(), data.len());
+    assert!(batch_lengths.iter().all(|len| *len <= 64));
+    assert_eq!(result, data);
+    assert_eq!(decoder.values_left(), 0);
+
+    // Error from the closure is propagated
+    decoder.set_data(bytes, data.len()).unwrap();
+    let mut num_batches = 0;
+    let err = decoder.decode_with(100, &mut |_: &[i32]| {
+      num_batches += 1;
+      if num_batches == 3 { Err(general_err!("sink is full")) } else { Ok(()) }
+    }).unwrap_err();
+    assert_eq!(err, general_err!("sink is full"));
+    assert_eq!(num_batches, 3);
+    assert_eq!(decoder.values_left(), data.len() - 300);
+  }
+
+  #[test]
+  fn test_decode_all() {
+    let data: Vec<i32> = (0..1000).map(|i| i * 3 - 700).collect();
+    let mut encoder = get_encoder::<Int32Type>(get_test_column_desc_ptr(),
+      Encoding::DELTA_BINARY_PACKED, Rc::new(MemTracker::new())).unwrap();
+    encoder.put(&data[..]).unwrap();
+    let bytes = encoder.flush_buffer().unwrap();
+
+    let mut decoder = DeltaBitPackDecoder::<Int32Type>::new();
+    decoder.set_data(bytes.all(), data.len()).unwrap();
+    let mut expected = vec![0; data.len()];
+    assert_eq!(decoder.get(&mut expected[..]).unwrap(), data.len());
+    decoder.set_data(bytes.all(), data.len()).unwrap();
+    assert_eq!(decoder.decode_all().unwrap(), expected);
+    assert_eq!(decoder.values_left(), 0);
+    assert!(decoder.decode_all().unwrap().is_empty());
+
+    // Only remaining values are decoded
+    decoder.set_data(bytes, data.len()).unwrap();
+    let mut buffer = vec![0; 100];
+    decoder.get(&mut buffer[..]).unwrap();
+    assert_eq!(decoder.decode_all().unwrap(), &data[100..]);
+
+    let values: Vec<ByteArray> =
+      vec!["parquet", "", "decode", "all"].into_iter().map(ByteArray::from).collect();
+    let data_bytes = ByteArrayType::to_byte_array(&values[..]);
+    let mut decoder = PlainDecoder::<ByteArrayType>::new(-1);
+    decoder.set_data(ByteBufferPtr::new(data_bytes), values.len()).unwrap();
+    assert_eq!(decoder.decode_all().unwrap(), values);
+  }
+
+  #[test]
+  fn test_plain_decode_strict_underflow() {
+    // Page metadata says 3 values, but page only has 2
+    let bytes = Int32Type::to_byte_array(&[1, 2][..]);
+    let mut decoder = PlainDecoder::<Int32Type>::new(-1).strict(true);
+    decoder.set_data(ByteBufferPtr::new(bytes), 3).unwrap();
+    let mut buffer = vec![0; 3];
+    assert_eq!(decoder.get(&mut buffer[..2]).unwrap(), 2);
+    assert!(decoder.get(&mut buffer[2..]).is_err());
+    assert!(decoder.finish().is_err());
+
+    // Booleans are only partially decoded in non-strict mode
+    let bytes = BoolType::to_byte_array(&[true; 8][..]);
+    let mut decoder = PlainDecoder::<BoolType>::new(-1);
+    decoder.set_data(ByteBufferPtr::new(bytes.clone()), 10).unwrap();
+    let mut buffer = vec![false; 10];
+    assert_eq!(decoder.get(&mut buffer[..]).unwrap(), 8);
+    assert!(decoder.finish().is_err());
+
+    let mut decoder = PlainDecoder::<BoolType>::new(-1).strict(true);
+    decoder.set_data(ByteBufferPtr::new(bytes), 10).unwrap();
+    assert!(decoder.get(&mut buffer[..]).is_err());
+    assert_eq!(decoder.values_left(), 10);
+  }
+
+  #[test]
+  fn test_plain_decode_strict_overflow() {
+    // Page metadata says 2 values, but page has 3
+    let bytes = Int32Type::to_byte_array(&[1, 2, 3][..]);
+    let mut buffer = vec![0; 3];
+    let mut decoder = PlainDecoder::<Int32Type>::new(-1);
+    decoder.set_data(ByteBufferPtr::new(bytes.clone()), 2).unwrap();
+    assert_eq!(decoder.get(&mut buffer[..]).unwrap(), 2);
+    assert!(decoder.finish().is_ok());
+
+    let mut decoder = PlainDecoder::<Int32Type>::new(-1).strict(true);
+    decoder.set_data(ByteBufferPtr::new(bytes.clone()), 2).unwrap();
+    assert_eq!(decoder.get(&mut buffer[..1]).unwrap(), 1);
+    assert!(decoder.get(&mut buffer[1..]).is_err());
+    // Failed read does not consume values
+    assert_eq!(decoder.values_left(), 1);
+    assert!(decoder.finish().is_err());
+
+    // Exact page passes in strict mode
+    decoder.set_data(ByteBufferPtr::new(bytes), 3).unwrap();
+    assert_eq!(decoder.get(&mut buffer[..]).unwrap(), 3);
+    assert_eq!(buffer, vec![1, 2, 3]);
+    assert!(decoder.finish().is_ok());
+
+    let values: Vec<ByteArray> =
+      vec!["a", "bc"].into_iter().map(ByteArray::from).collect();
+    let bytes = ByteArrayType::to_byte_array(&values[..]);
+    let mut decoder = PlainDecoder::<ByteArrayType>::new(-1).strict(true);
+    decoder.set_data(ByteBufferPtr::new(bytes.clone()), 1).unwrap();
+    let mut buffer = vec![ByteArray::new(); 2];
+    assert!(decoder.get(&mut buffer[..]).is_err());
+    assert_eq!(decoder.values_left(), 1);
+    let mut out = vec![];
+    let mut offsets = vec![];
+    decoder.set_data(ByteBufferPtr::new(bytes), 1).unwrap();
+    assert!(decoder.get_into_buffer(&mut out, &mut offsets).is_err());
+    assert_eq!(decoder.values_left(), 1);
+    assert!(out.is_empty());
+    assert!(offsets.is_empty());
+
+    // Booleans are padded to a byte, padding is not reported as bytes left
+    let bytes = BoolType::to_byte_array(&[true, false, true, false, true][..]);
+    let mut decoder = PlainDecoder::<BoolType>::new(-1).strict(true);
+    decoder.set_data(ByteBufferPtr::new(bytes.clone()), 3).unwrap();
+    let mut buffer = vec![false; 3];
+    assert_eq!(decoder.get(&mut buffer[..]).unwrap(), 3);
+    assert!(decoder.finish().is_ok());
+
+    let mut bytes = bytes;
+    bytes.push(0);
+    decoder.set_data(ByteBufferPtr::new(bytes), 3).unwrap();
+    assert_eq!(decoder.get(&mut buffer[..1]).unwrap(), 1);
+    assert!(decoder.get(&mut buffer[1..]).is_err());
+    assert_eq!(decoder.values_left(), 2);
+  }
+
+  #[test]
+  fn test_plain_decode_check_width() {
+    // INT64 decoder for a page with 3 INT32 values
+    let bytes = Int32Type::to_byte_array(&[1, 2, 3][..]);
+    let mut decoder = PlainDecoder::<Int64Type>::new(-1).check_width(true);
+    assert_eq!(
+      decoder.set_data(ByteBufferPtr::new(bytes.clone()), 3).unwrap_err(),
+      type_mismatch_err!(
+        "3 values of type INT64 require at least 24 bytes, but page has 12 bytes")
+    );
+    let mut decoder = PlainDecoder::<Int32Type>::new(-1).check_width(true);
+    assert!(decoder.set_data(ByteBufferPtr::new(bytes.clone()), 3).is_ok());
+    // Page size is not checked by default
+    let mut decoder = PlainDecoder::<Int64Type>::new(-1);
+    assert!(decoder.set_data(ByteBufferPtr::new(bytes), 3).is_ok());
+
+    let mut decoder = PlainDecoder::<FixedLenByteArrayType>::new(4).check_width(true);
+    assert!(decoder.set_data(ByteBufferPtr::new(vec![0; 7]), 2).is_err());
+    let mut decoder = PlainDecoder::<BoolType>::new(-1).check_width(true);
+    assert!(decoder.set_data(ByteBufferPtr::new(vec![0; 2]), 16).is_ok());
+    assert!(decoder.set_data(ByteBufferPtr::new(vec![0; 2]), 17).is_err());
+  }
+
+  #[test]
+  fn test_decoder_finish() {
+    let bytes = Int32Type::to_byte_array(&[1, 2, 3][..]);
+    let mut decoder = get_decoder::<Int32Type>(get_test_column_desc_ptr(),
+      Encoding::PLAIN).unwrap();
+    decoder.set_data(ByteBufferPtr::new(bytes), 3).unwrap();
+    let mut buffer = vec![0; 2];
+    assert_eq!(decoder.get(&mut buffer[..]).unwrap(), 2);
+    assert!(decoder.finish().is_err());
+    assert_eq!(decoder.get(&mut buffer[..]).unwrap(), 1);
+    assert!(decoder.finish().is_ok());
+  }
+
+  #[test]
+  fn test_plain_decode_feed() {
+    let data: Vec<i32> = (0..10).collect();
+    let bytes = Int32Type::to_byte_array(&data[..]);
+    let mut decoder = PlainDecoder::<Int32Type>::new(-1);
+    // First chunk ends in the middle of the 4th value
+    decoder.set_data(ByteBufferPtr::new(bytes[..14].to_vec()), data.len()).unwrap();
+    let mut buffer = vec![0; 10];
+    assert_eq!(decoder.get(&mut buffer[..3]).unwrap(), 3);
+    assert!(decoder.get(&mut buffer[3..]).is_err());
+    assert_eq!(decoder.values_left(), 7);
+
+    decoder.feed(ByteBufferPtr::new(bytes[14..].to_vec())).unwrap();
+    assert_eq!(decoder.values_left(), 7);
+    assert_eq!(decoder.get(&mut buffer[3..]).unwrap(), 7);
+    assert_eq!(buffer, data);
+
+    // Fixed length byte arrays, value split between chunks
+    let values: Vec<ByteArray> =
+      vec!["abc", "def", "ghi"].into_iter().map(ByteArray::from).collect();
+    let mut decoder = PlainDecoder::<FixedLenByteArrayType>::new(3);
+    decoder.set_data(ByteBufferPtr::new(b"abcde".to_vec()), 3).unwrap();
+    let mut buffer = vec![ByteArray::new(); 3];
+    assert!(decoder.get(&mut buffer[..]).is_err());
+    decoder.feed(ByteBufferPtr::new(b"fghi".to_vec())).unwrap();
+    assert_eq!(decoder.get(&mut buffer[..]).unwrap(), 3);
+    assert_eq!(buffer, values);
+
+    let mut decoder = PlainDecoder::<Int64Type>::new(-1);
+    assert!(decoder.feed(ByteBufferPtr::new(vec![0; 8])).is_err());
+    let mut decoder = PlainDecoder::<ByteArrayType>::new(-1);
+    decoder.set_data(ByteBufferPtr::new(vec![]), 0).unwrap();
+    assert!(decoder.feed(ByteBufferPtr::new(vec![0; 8])).is_err());
+    let mut decoder = DeltaBitPackDecoder::<Int32Type>::new();
+    assert!(decoder.feed(ByteBufferPtr::new(vec![0; 8])).is_err());
+  }
+
   #[test]
   fn test_plain_decode_int32() {
     let data = vec![42, 18, 52];
@@ -944,6 +1873,36 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_plain_decode_byte_array_length_overrun() {
+    // Two values: "ab" and a value that declares 10 bytes, but only 3 are left
+    let mut data = vec![2, 0, 0, 0, b'a', b'b'];
+    data.extend_from_slice(&[10, 0, 0, 0, b'c', b'd', b'e']);
+    let mut decoder = PlainDecoder::<ByteArrayType>::new(-1);
+    decoder.set_data(ByteBufferPtr::new(data), 2).unwrap();
+    let mut buffer = vec![ByteArray::new(); 2];
+    assert_eq!(
+      decoder.get(&mut buffer[..]).unwrap_err(),
+      eof_err!("Not enough bytes to decode")
+    );
+    assert_eq!(decoder.values_left(), 2);
+    // Same error as decoding into a contiguous buffer
+    let mut out = vec![];
+    let mut offsets = vec![];
+    assert_eq!(
+      decoder.get_into_buffer(&mut out, &mut offsets).unwrap_err(),
+      eof_err!("Not enough bytes to decode")
+    );
+
+    // Truncated length prefix
+    let mut decoder = PlainDecoder::<ByteArrayType>::new(-1);
+    decoder.set_data(ByteBufferPtr::new(vec![1, 0, 0, 0, b'a', 5, 0]), 2).unwrap();
+    assert_eq!(
+      decoder.get(&mut buffer[..]).unwrap_err(),
+      eof_err!("Not enough bytes to decode")
+    );
+  }
+
   #[test]
   fn test_plain_decode_byte_array() {
     let mut data = vec![ByteArray::new(); 2];
@@ -956,6 +1915,32 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_plain_decode_byte_array_empty_values() {
+    // Empty byte arrays are non-null values and must not be skipped when decoding
+    let values: Vec<ByteArray> = vec!["", "a", "", "", "parquet", ""]
+      .into_iter()
+      .map(|v| ByteArray::from(v))
+      .collect();
+    let desc = make_col_desc("col", Type::BYTE_ARRAY, LogicalType::UTF8, 0, 0, 0);
+    let mut encoder =
+      PlainEncoder::<ByteArrayType>::new(desc, Rc::new(MemTracker::new()), vec![]);
+    encoder.put(&values[..]).unwrap();
+    let data = encoder.flush_buffer().unwrap();
+    // Each empty value is only a 4-byte zero length
+    assert_eq!(data.len(), 4 * values.len() + 1 + 7);
+    assert_eq!(&data.as_ref()[0..4], &[0, 0, 0, 0]);
+
+    let mut decoder = PlainDecoder::<ByteArrayType>::new(-1);
+    decoder.set_data(data, values.len()).unwrap();
+    let mut buffer = vec![ByteArray::new(); values.len()];
+    assert_eq!(decoder.get(&mut buffer[..]).unwrap(), values.len());
+    assert_eq!(decoder.values_left(), 0);
+    assert_eq!(buffer, values);
+    let lengths: Vec<usize> = buffer.iter().map(|v| v.len()).collect();
+    assert_eq!(lengths, vec![0, 1, 0, 0, 7, 0]);
+  }
+
   #[test]
   fn test_plain_decode_fixed_len_byte_array() {
     let mut data = vec![ByteArray::default(); 3];
@@ -970,19 +1955,54 @@ mod tests {
   }
 
   #[test]
-  #[should_panic(expected = "RleValueEncoder only supports BoolType")]
-  fn test_rle_value_encode_int32_not_supported() {
-    let mut encoder = RleValueEncoder::<Int32Type>::new();
+  #[should_panic(expected = "RleValueEncoder only supports BoolType and Int32Type")]
+  fn test_rle_value_encode_int64_not_supported() {
+    let mut encoder = RleValueEncoder::<Int64Type>::new();
     encoder.put(&vec![1, 2, 3, 4]).unwrap();
   }
 
   #[test]
-  #[should_panic(expected = "RleValueDecoder only supports BoolType")]
-  fn test_rle_value_decode_int32_not_supported() {
-    let mut decoder = RleValueDecoder::<Int32Type>::new();
+  #[should_panic(expected = "RleValueDecoder only supports BoolType and Int32Type")]
+  fn test_rle_value_decode_int64_not_supported() {
+    let mut decoder = RleValueDecoder::<Int64Type>::new();
     decoder.set_data(ByteBufferPtr::new(vec![5, 0, 0, 0]), 1).unwrap();
   }
 
+  #[test]
+  fn test_rle_value_decode_int32() {
+    let data = vec![
+      Int32Type::gen_vec(-1, 256),
+      Int32Type::gen_vec(-1, 257),
+      Int32Type::gen_vec(-1, 126)
+    ];
+    test_rle_value_decode::<Int32Type>(data);
+
+    // Dictionary indices with a small bit width
+    let indices: Vec<i32> =
+      (0..1000).map(|i| if i % 100 < 50 { i % 7 } else { 3 }).collect();
+    let mut encoder = RleValueEncoder::<Int32Type>::new().with_bit_width(3);
+    encoder.put(&indices[..]).unwrap();
+    encoder.put_repeated(5, 100).unwrap();
+    let data = encoder.flush_buffer().unwrap();
+    // Bit-packed runs take less than a byte per value
+    assert!(data.len() < 600, "{} bytes", data.len());
+
+    let mut decoder = RleValueDecoder::<Int32Type>::new().with_bit_width(3);
+    decoder.set_data(data, indices.len() + 100).unwrap();
+    let result = decoder.decode_all().unwrap();
+    assert_eq!(&result[..indices.len()], &indices[..]);
+    assert_eq!(&result[indices.len()..], &[5; 100][..]);
+
+    assert_eq!(
+      encoder.put(&[8]).unwrap_err(),
+      general_err!("Value 8 does not fit into 3 bits of RLE encoding")
+    );
+    assert_eq!(
+      encoder.put(&[-1]).unwrap_err(),
+      general_err!("Value -1 does not fit into 3 bits of RLE encoding")
+    );
+  }
+
   #[test]
   fn test_rle_value_decode_bool_decode() {
     // Test multiple 'put' calls on the same encoder
@@ -994,6 +2014,21 @@ mod tests {
     test_rle_value_decode::<BoolType>(data);
   }
 
+  #[test]
+  fn test_rle_value_decode_bool_bit_packed_only() {
+    // Length prefix, followed by a single bit-packed run of 2 groups with 11 values
+    // and 5 padding 0s: 10011101 011
+    let data = ByteBufferPtr::new(vec![3, 0, 0, 0, 0x05, 0xB9, 0x06]);
+    let expected =
+      vec![true, false, false, true, true, true, false, true, false, true, true];
+    let mut decoder: RleValueDecoder<BoolType> = RleValueDecoder::new();
+    decoder.set_data(data, expected.len()).unwrap();
+    let mut buffer = vec![false; 16];
+    assert_eq!(decoder.get(&mut buffer[..]).unwrap(), expected.len());
+    assert_eq!(&buffer[..expected.len()], &expected[..]);
+    assert_eq!(decoder.values_left(), 0);
+  }
+
   #[test]
   #[should_panic(expected = "Bit reader is not initialized")]
   fn test_delta_bit_packed_not_initialized_offset() {
@@ -1017,6 +2052,21 @@ mod tests {
     test_delta_bit_packed_decode::<Int32Type>(data);
   }
 
+  #[test]
+  fn test_delta_bit_packed_int32_single_value() {
+    test_delta_bit_packed_single_value::<Int32Type>(
+      vec![0, 1, -1, i32::min_value(), i32::max_value()]);
+  }
+
+  #[test]
+  fn test_delta_bit_packed_int32_interleaved_empty() {
+    let data = vec![vec![], vec![7], vec![], vec![], vec![-3, 12], vec![]];
+    test_delta_bit_packed_decode::<Int32Type>(data);
+
+    let data = vec![vec![], vec![1, 2, 3, 4, 5, 6, 7, 8, 9], vec![], vec![-10]];
+    test_delta_bit_packed_decode::<Int32Type>(data);
+  }
+
   #[test]
   fn test_delta_bit_packed_int32_repeat() {
     let block_data = vec![
@@ -1071,6 +2121,58 @@ mod tests {
     test_delta_bit_packed_decode::<Int32Type>(data);
   }
 
+  #[test]
+  fn test_delta_bit_packed_verify_consistency() {
+    // First value and 129 deltas, which take 2 blocks
+    let values: Vec<i32> = (0..130).map(|i| i * i % 97).collect();
+    let mut encoder = DeltaBitPackEncoder::<Int32Type>::new();
+    encoder.put(&values[..]).unwrap();
+    let data = encoder.flush_buffer().unwrap();
+
+    let mut decoder = DeltaBitPackDecoder::<Int32Type>::new();
+    assert!(decoder.verify_consistency().is_err());
+    decoder.set_data(data.all(), values.len()).unwrap();
+    let mut result = vec![];
+    let mut buffer = vec![0; 7];
+    loop {
+      assert!(decoder.verify_consistency().is_ok());
+      let num_values = decoder.get(&mut buffer[..]).unwrap();
+      if num_values == 0 {
+        break;
+      }
+      result.extend_from_slice(&buffer[..num_values]);
+    }
+    assert_eq!(result, values);
+
+    // Header declares 300 values, but the second block is the last one in the page,
+    // which is detected once it is loaded
+    let mut bytes = data.data().to_vec();
+    assert_eq!(&bytes[..5], &[0x80, 0x01, 0x04, 0x82, 0x01]);
+    bytes[3] = 0xAC;
+    bytes[4] = 0x02;
+    decoder.set_data(ByteBufferPtr::new(bytes.clone()), 300).unwrap();
+    let mut buffer = vec![0; 130];
+    assert!(decoder.verify_consistency().is_ok());
+    assert_eq!(decoder.get(&mut buffer[..129]).unwrap(), 129);
+    assert!(decoder.verify_consistency().is_ok());
+    assert_eq!(decoder.get(&mut buffer[129..]).unwrap(), 1);
+    assert_eq!(buffer, values);
+    assert_eq!(
+      decoder.verify_consistency().unwrap_err(),
+      general_err!("Page header has 170 values left, but page has only 0 bytes left")
+    );
+
+    // Padding of the last mini blocks is decoded as values, loading the next block
+    // fails and leaves mini block index out of range
+    decoder.set_data(ByteBufferPtr::new(bytes), 300).unwrap();
+    let mut buffer = vec![0; 300];
+    assert!(decoder.get(&mut buffer[..]).is_err());
+    assert_eq!(
+      decoder.verify_consistency().unwrap_err(),
+      general_err!("Mini block 4 is out of range, block has 4 mini blocks")
+    );
+  }
+
   #[test]
   fn test_delta_bit_packed_int32_data_across_blocks() {
     // Test multiple 'put' calls on the same encoder
@@ -1097,6 +2199,12 @@ mod tests {
     test_delta_bit_packed_decode::<Int64Type>(data);
   }
 
+  #[test]
+  fn test_delta_bit_packed_int64_single_value() {
+    test_delta_bit_packed_single_value::<Int64Type>(
+      vec![0, 1, -1, i64::min_value(), i64::max_value()]);
+  }
+
   #[test]
   fn test_delta_bit_packed_int64_min_max() {
     let block_data = vec![
@@ -1108,6 +2216,25 @@ mod tests {
     test_delta_bit_packed_decode::<Int64Type>(vec![block_data]);
   }
 
+  #[test]
+  fn test_delta_bit_packed_int64_extreme_deltas() {
+    let (min, max) = (i64::min_value(), i64::max_value());
+    // Deltas of i64::MIN and i64::MAX in the same mini block, i.e. min delta is
+    // i64::MIN and packed values use all 64 bits
+    test_delta_bit_packed_decode::<Int64Type>(vec![
+      vec![0, max, -1, min, 0, min, max, -1, 1, min + 1, max - 1, 0]
+    ]);
+    // All deltas are i64::MIN: bit width is 0, values are restored from min delta only
+    test_delta_bit_packed_decode::<Int64Type>(vec![vec![min, 0, min, 0, min, 0, min]]);
+    // All deltas are i64::MAX
+    test_delta_bit_packed_decode::<Int64Type>(vec![vec![min, -1, max - 1, -3]]);
+    // Deltas that differ from the extremes by one across multiple blocks
+    let pattern = vec![min + 1, 0, max, max - 1, min, -1];
+    let data: Vec<i64> = (0..1000).map(|i| pattern[i % pattern.len()]).collect();
+    test_delta_bit_packed_decode::<Int64Type>(
+      vec![data[..300].to_vec(), data[300..].to_vec()]);
+  }
+
   #[test]
   fn test_delta_bit_packed_int64_multiple_blocks() {
     // Test multiple 'put' calls on the same encoder
@@ -1119,6 +2246,36 @@ mod tests {
     test_delta_bit_packed_decode::<Int64Type>(data);
   }
 
+  #[test]
+  fn test_delta_bit_packed_uint32() {
+    // UINT_32 values are stored as INT32, values with the top bit set must round-trip
+    let values: Vec<u32> = vec![
+      0, 1, 0xFFFFFFFF, 0x80000000, 0x7FFFFFFF, 0xFFFFFFFE, 0x80000001, 2,
+      0xFFFFFFFF, 0xFFFFFFFF, 0, 0x80000000, 42, 0xDEADBEEF, 0x7FFFFFFF, 1
+    ];
+    let block_data: Vec<i32> = values.iter().map(|v| *v as i32).collect();
+    test_delta_bit_packed_decode::<Int32Type>(vec![block_data.clone()]);
+
+    let ascending: Vec<i32> = (0..300u32).map(|i| (0xFFFFFF00u32 + i) as i32).collect();
+    test_delta_bit_packed_decode::<Int32Type>(vec![block_data, ascending]);
+  }
+
+  #[test]
+  fn test_delta_bit_packed_uint64() {
+    // UINT_64 values are stored as INT64, values with the top bit set must round-trip
+    let values: Vec<u64> = vec![
+      0, 1, 0xFFFFFFFFFFFFFFFF, 0x8000000000000000, 0x7FFFFFFFFFFFFFFF,
+      0xFFFFFFFFFFFFFFFE, 0x8000000000000001, 2, 0xFFFFFFFF, 0x100000000,
+      0xFFFFFFFFFFFFFFFF, 0, 0x8000000000000000, 42, 0xDEADBEEFDEADBEEF, 1
+    ];
+    let block_data: Vec<i64> = values.iter().map(|v| *v as i64).collect();
+    test_delta_bit_packed_decode::<Int64Type>(vec![block_data.clone()]);
+
+    let ascending: Vec<i64> =
+      (0..300u64).map(|i| (0xFFFFFFFFFFFFFF00u64 + i) as i64).collect();
+    test_delta_bit_packed_decode::<Int64Type>(vec![block_data, ascending]);
+  }
+
   #[test]
   fn test_delta_bit_packed_decoder_sample() {
     let data_bytes = vec![
@@ -1140,6 +2297,41 @@ mod tests {
     assert_eq!(result, vec![29, 43, 89]);
   }
 
+  #[test]
+  fn test_delta_length_byte_array_validate_lengths() {
+    // Builds a page with delta encoded `lengths` followed by `values`
+    let make_page = |lengths: &[i32], values: &[u8]| {
+      let mut encoder = DeltaBitPackEncoder::<Int32Type>::new();
+      encoder.put(lengths).unwrap();
+      let mut page = encoder.flush_buffer().unwrap().data().to_vec();
+      page.extend_from_slice(values);
+      ByteBufferPtr::new(page)
+    };
+    let mut decoder = DeltaLengthByteArrayDecoder::<ByteArrayType>::new();
+    let mut buffer = vec![ByteArray::new(); 2];
+
+    decoder.set_data(make_page(&[2, 3], b"abcde"), 2).unwrap();
+    assert_eq!(decoder.get(&mut buffer[..]).unwrap(), 2);
+    assert_eq!(buffer, vec![ByteArray::from("ab"), ByteArray::from("cde")]);
+
+    // Lengths overrun the data
+    assert_eq!(
+      decoder.set_data(make_page(&[2, 3], b"abcd"), 2).unwrap_err(),
+      general_err!("Sum of byte array lengths 5 does not match data length 4")
+    );
+
+    // Lengths underrun the data
+    assert_eq!(
+      decoder.set_data(make_page(&[2, 3], b"abcdef"), 2).unwrap_err(),
+      general_err!("Sum of byte array lengths 5 does not match data length 6")
+    );
+
+    assert_eq!(
+      decoder.set_data(make_page(&[2, -3], b"ab"), 2).unwrap_err(),
+      general_err!("Invalid byte array length -3")
+    );
+  }
+
   #[test]
   fn test_delta_byte_array_same_arrays() {
     let data = vec![
@@ -1174,12 +2366,290 @@ mod tests {
     test_delta_byte_array_decode(data);
   }
 
+  #[test]
+  fn test_delta_byte_array_inferred_num_values() {
+    let values = ByteArrayType::gen_vec(-1, 1000);
+
+    let mut encoder = DeltaLengthByteArrayEncoder::<ByteArrayType>::new();
+    encoder.put(&values[..]).unwrap();
+    let data = encoder.flush_buffer().unwrap();
+    let mut decoder = DeltaLengthByteArrayDecoder::<ByteArrayType>::new();
+    assert_eq!(decoder.inferred_num_values(), None);
+    // Number of values passed to `set_data` is not used
+    decoder.set_data(data, 1).unwrap();
+    assert_eq!(decoder.inferred_num_values(), Some(values.len()));
+    let mut buffer = vec![ByteArray::new(); 10];
+    decoder.get(&mut buffer[..]).unwrap();
+    assert_eq!(decoder.inferred_num_values(), Some(values.len()));
+    decoder.reset();
+    assert_eq!(decoder.inferred_num_values(), None);
+
+    let mut encoder = DeltaByteArrayEncoder::<ByteArrayType>::new();
+    encoder.put(&values[..]).unwrap();
+    let data = encoder.flush_buffer().unwrap();
+    let mut decoder = DeltaByteArrayDecoder::<ByteArrayType>::new();
+    assert_eq!(decoder.inferred_num_values(), None);
+    decoder.set_data(data, 1).unwrap();
+    assert_eq!(decoder.inferred_num_values(), Some(values.len()));
+    decoder.reset();
+    assert_eq!(decoder.inferred_num_values(), None);
+  }
+
+  #[test]
+  fn test_plain_decode_byte_array_into_buffer() {
+    let data = vec![
+      ByteArray::from("hello"),
+      ByteArray::from(""),
+      ByteArray::from("parquet"),
+      ByteArray::from("rs")
+    ];
+    let data_bytes = ByteArrayType::to_byte_array(&data[..]);
+
+    let mut decoder = PlainDecoder::<ByteArrayType>::new(-1);
+    decoder.set_data(ByteBufferPtr::new(data_bytes.clone()), data.len()).unwrap();
+    let mut expected = vec![ByteArray::new(); data.len()];
+    assert_eq!(decoder.get(&mut expected[..]).unwrap(), data.len());
+
+    decoder.set_data(ByteBufferPtr::new(data_bytes), data.len()).unwrap();
+    let mut out = vec![];
+    let mut offsets = vec![];
+    assert_eq!(decoder.get_into_buffer(&mut out, &mut offsets).unwrap(), data.len());
+    assert_eq!(decoder.values_left(), 0);
+    assert_eq!(offsets, vec![0, 5, 5, 12, 14]);
+    assert_eq!(out, b"helloparquetrs".to_vec());
+
+    let actual: Vec<ByteArray> = offsets.windows(2)
+      .map(|w| ByteArray::from(out[w[0] as usize..w[1] as usize].to_vec()))
+      .collect();
+    assert_eq!(actual, expected);
+  }
+
+  #[test]
+  fn test_reset_decoder() {
+    let pages = vec![vec![1, 2, 3, 4], vec![5, 6, 7], vec![-1, 8, 9, 10, 11]];
+    test_reset_decode::<Int32Type>(Encoding::PLAIN, pages.clone());
+    test_reset_decode::<Int32Type>(Encoding::DELTA_BINARY_PACKED, pages);
+
+    let pages = vec![vec![true, false], vec![false; 17], vec![true, true, false]];
+    test_reset_decode::<BoolType>(Encoding::PLAIN, pages.clone());
+    test_reset_decode::<BoolType>(Encoding::RLE, pages);
+
+    let pages = vec![
+      vec![ByteArray::from("ab"), ByteArray::from("abc")],
+      vec![ByteArray::from("bcd")],
+      vec![ByteArray::from("b"), ByteArray::from(""), ByteArray::from("bcd")]
+    ];
+    test_reset_decode::<ByteArrayType>(Encoding::PLAIN, pages.clone());
+    test_reset_decode::<ByteArrayType>(Encoding::DELTA_LENGTH_BYTE_ARRAY, pages.clone());
+    test_reset_decode::<ByteArrayType>(Encoding::DELTA_BYTE_ARRAY, pages);
+  }
+
+  #[test]
+  fn test_reset_dict_decoder_keeps_dictionary() {
+    let pages = vec![vec![1, 2, 1, 3], vec![3, 3, 2], vec![2, 1, 1, 1, 3]];
+
+    let mut encoder = DictEncoder::<Int32Type>::new(
+      get_test_column_desc_ptr(), Rc::new(MemTracker::new()));
+    let mut data_pages = vec![];
+    for page in &pages {
+      encoder.put(&page[..]).expect("ok to encode");
+      data_pages.push(encoder.flush_buffer().expect("ok to flush buffer"));
+    }
+
+    let mut dict_decoder = PlainDecoder::<Int32Type>::new(-1);
+    dict_decoder.set_data(encoder.write_dict().unwrap(), encoder.num_entries()).unwrap();
+    let mut decoder = DictDecoder::<Int32Type>::new();
+    decoder.set_dict(Box::new(dict_decoder)).unwrap();
+
+    for (page, data) in pages.iter().zip(data_pages.into_iter()) {
+      decoder.reset();
+      assert_eq!(decoder.values_left(), 0);
+      decoder.set_data(data, page.len()).expect("ok to set data");
+      let mut result = vec![0; page.len()];
+      assert_eq!(decoder.get(&mut result[..]).unwrap(), page.len());
+      assert_eq!(&result, page);
+    }
+  }
+
+  #[test]
+  fn test_get_oversized_buffer() {
+    let data: Vec<i32> = (0..13).map(|i| i % 3).collect();
+    let bools: Vec<bool> = data.iter().map(|v| *v == 0).collect();
+
+    for encoding in &[Encoding::PLAIN, Encoding::DELTA_BINARY_PACKED] {
+      let mut encoder = get_encoder::<Int32Type>(
+        get_test_column_desc_ptr(), *encoding, Rc::new(MemTracker::new())).unwrap();
+      encoder.put(&data[..]).unwrap();
+      let mut decoder = get_decoder::<Int32Type>(get_test_column_desc_ptr(), *encoding)
+        .unwrap();
+      decoder.set_data(encoder.flush_buffer().unwrap(), data.len()).unwrap();
+      test_get_oversized::<Int32Type>(&mut *decoder, &data[..]);
+    }
+
+    for encoding in &[Encoding::PLAIN, Encoding::RLE] {
+      let mut encoder = get_encoder::<BoolType>(
+        get_test_column_desc_ptr(), *encoding, Rc::new(MemTracker::new())).unwrap();
+      encoder.put(&bools[..]).unwrap();
+      let mut decoder = get_decoder::<BoolType>(get_test_column_desc_ptr(), *encoding)
+        .unwrap();
+      decoder.set_data(encoder.flush_buffer().unwrap(), bools.len()).unwrap();
+      test_get_oversized::<BoolType>(&mut *decoder, &bools[..]);
+    }
+
+    let mut encoder = DictEncoder::<Int32Type>::new(
+      get_test_column_desc_ptr(), Rc::new(MemTracker::new()));
+    encoder.put(&data[..]).unwrap();
+    let mut dict_decoder = PlainDecoder::<Int32Type>::new(-1);
+    dict_decoder.set_data(encoder.write_dict().unwrap(), encoder.num_entries()).unwrap();
+    let mut decoder = DictDecoder::<Int32Type>::new();
+    decoder.set_dict(Box::new(dict_decoder)).unwrap();
+    decoder.set_data(encoder.write_indices().unwrap(), data.len()).unwrap();
+    test_get_oversized::<Int32Type>(&mut decoder, &data[..]);
+  }
+
+  // Decodes `expected` into a buffer larger than the number of values in the page and
+  // checks that only the prefix of the buffer is filled.
+  fn test_get_oversized<T: DataType>(decoder: &mut Decoder<T>, expected: &[T::T]) {
+    let mut buffer = vec![T::T::default(); expected.len() * 2 + 5];
+    assert_eq!(decoder.get(&mut buffer[..]).unwrap(), expected.len());
+    assert_eq!(decoder.values_left(), 0);
+    assert_eq!(&buffer[..expected.len()], expected);
+    assert!(buffer[expected.len()..].iter().all(|v| *v == T::T::default()));
+    assert_eq!(decoder.get(&mut buffer[..]).unwrap(), 0);
+  }
+
+  #[test]
+  fn test_dict_decoder_set_dict_with_encoding() {
+    let values = vec![300, 100, 300, 200, -5, 100, 100, 200];
+    let mut encoder = DictEncoder::<Int32Type>::new(
+      get_test_column_desc_ptr(), Rc::new(MemTracker::new()));
+    encoder.put(&values[..]).unwrap();
+    let indices = encoder.write_indices().unwrap();
+
+    // Dictionary page with DELTA_BINARY_PACKED encoded entries
+    let mut dict_encoder = DeltaBitPackEncoder::<Int32Type>::new();
+    dict_encoder.put(encoder.entries()).unwrap();
+    let mut dict_decoder = DeltaBitPackDecoder::<Int32Type>::new();
+    dict_decoder.set_data(dict_encoder.flush_buffer().unwrap(), encoder.num_entries())
+      .unwrap();
+
+    let mut decoder = DictDecoder::<Int32Type>::new();
+    decoder.set_dict_with_encoding(Box::new(dict_decoder),
+      Encoding::DELTA_BINARY_PACKED).unwrap();
+    decoder.set_data(indices, values.len()).unwrap();
+    assert_eq!(decoder.decode_all().unwrap(), values);
+
+    // PLAIN_DICTIONARY dictionary page is PLAIN encoded
+    let mut dict_decoder = PlainDecoder::<Int32Type>::new(-1);
+    dict_decoder.set_data(ByteBufferPtr::new(Int32Type::to_byte_array(&[7][..])), 1)
+      .unwrap();
+    let mut decoder = DictDecoder::<Int32Type>::new();
+    decoder.set_dict_with_encoding(Box::new(dict_decoder), Encoding::PLAIN_DICTIONARY)
+      .unwrap();
+    decoder.set_data(ByteBufferPtr::new(vec![1, 4, 0]), 2).unwrap();
+    assert_eq!(decoder.decode_all().unwrap(), vec![7, 7]);
+
+    assert_eq!(
+      decoder.set_dict_with_encoding(Box::new(PlainDecoder::<Int32Type>::new(-1)),
+        Encoding::DELTA_BINARY_PACKED).unwrap_err(),
+      general_err!("Dictionary page encoding DELTA_BINARY_PACKED does not match \
+        decoder encoding PLAIN")
+    );
+    assert_eq!(
+      decoder.set_dict_with_encoding(Box::new(DictDecoder::<Int32Type>::new()),
+        Encoding::RLE_DICTIONARY).unwrap_err(),
+      general_err!("Invalid dictionary page encoding RLE_DICTIONARY")
+    );
+  }
+
+  #[test]
+  fn test_dict_decoder_index_validation() {
+    // Bit width is larger than needed for 3 entries, indices are in range
+    let indices = vec![0, 1, 2, 2, 1, 0, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 1];
+    let mut decoder = new_dict_decoder_with_indices(&[10, 20, 30], 8, &indices);
+    let mut result = vec![0; indices.len()];
+    assert_eq!(decoder.get(&mut result[..]).unwrap(), indices.len());
+    let expected: Vec<i32> = indices.iter().map(|i| (*i as i32 + 1) * 10).collect();
+    assert_eq!(result, expected);
+
+    // Out of range index in bit packed run
+    let mut decoder = new_dict_decoder_with_indices(&[10, 20, 30], 2, &[0, 1, 3, 2]);
+    assert_eq!(
+      decoder.get(&mut result[..4]).unwrap_err(),
+      general_err!("Dictionary index 3 is out of range for dictionary with 3 entries")
+    );
+
+    // Out of range index in RLE run
+    let mut decoder = new_dict_decoder_with_indices(&[10, 20, 30], 4, &[5; 16]);
+    assert_eq!(
+      decoder.get(&mut result[..16]).unwrap_err(),
+      general_err!("Dictionary index 5 is out of range for dictionary with 3 entries")
+    );
+
+    // Invalid bit width
+    let mut decoder = DictDecoder::<Int32Type>::new();
+    assert_eq!(
+      decoder.set_data(ByteBufferPtr::new(vec![33, 0]), 1).unwrap_err(),
+      general_err!("Invalid dictionary index bit width 33")
+    );
+    assert!(decoder.set_data(ByteBufferPtr::new(vec![]), 1).is_err());
+  }
+
+  #[test]
+  fn test_dict_decoder_into_arrow_parts() {
+    let values = vec![300, 100, 300, 200, -5, 100, 100, 200, 200, 200, 200, 200, 200,
+      200, 200, 200, 200, 300];
+    let mut encoder = DictEncoder::<Int32Type>::new(
+      get_test_column_desc_ptr(), Rc::new(MemTracker::new()));
+    encoder.put(&values[..]).unwrap();
+    let mut dict_decoder = PlainDecoder::<Int32Type>::new(-1);
+    dict_decoder.set_data(encoder.write_dict().unwrap(), encoder.num_entries()).unwrap();
+    let mut decoder = DictDecoder::<Int32Type>::new();
+    decoder.set_dict(Box::new(dict_decoder)).unwrap();
+    decoder.set_data(encoder.write_indices().unwrap(), values.len()).unwrap();
+
+    let (dictionary, indices) = decoder.into_arrow_parts().unwrap();
+    assert_eq!(dictionary, vec![300, 100, 200, -5]);
+    assert_eq!(indices.len(), values.len());
+    assert_eq!(decoder.values_left(), 0);
+    let result: Vec<i32> = indices.iter().map(|i| dictionary[*i as usize]).collect();
+    assert_eq!(result, values);
+
+    // Out of range index
+    let mut decoder = new_dict_decoder_with_indices(&[10, 20, 30], 2, &[0, 1, 3, 2]);
+    assert_eq!(
+      decoder.into_arrow_parts().unwrap_err(),
+      general_err!("Dictionary index 3 is out of range for dictionary with 3 entries")
+    );
+  }
+
+  // Returns dictionary decoder for `dict` entries with data page of `indices` encoded
+  // with `bit_width`.
+  fn new_dict_decoder_with_indices(
+    dict: &[i32],
+    bit_width: u8,
+    indices: &[u64]
+  ) -> DictDecoder<Int32Type> {
+    let mut dict_decoder = PlainDecoder::<Int32Type>::new(-1);
+    dict_decoder.set_data(
+      ByteBufferPtr::new(Int32Type::to_byte_array(dict)), dict.len()).unwrap();
+    let mut decoder = DictDecoder::<Int32Type>::new();
+    decoder.set_dict(Box::new(dict_decoder)).unwrap();
+
+    let mut encoder = RleEncoder::new(bit_width, 1024);
+    for index in indices {
+      assert!(encoder.put(*index).unwrap());
+    }
+    let mut data = vec![bit_width];
+    data.extend_from_slice(&encoder.consume().unwrap());
+    decoder.set_data(ByteBufferPtr::new(data), indices.len()).unwrap();
+    decoder
+  }
+
   // Test column descriptor for the column.
   // Used for testing of Int32Type decoders and as a placeholder for delta encodings.
   fn get_test_column_desc_ptr() -> ColumnDescPtr {
-    let type_ptr =
-      Rc::new(Tpe::primitive_type_builder("col", Type::INT32).build().unwrap());
-    Rc::new(ColumnDescriptor::new(type_ptr, None, 0, 0, ColumnPath::from("col")))
+    make_col_desc("col", Type::INT32, LogicalType::NONE, 0, 0, 0)
   }
 
   fn test_get_decoder<T: 'static + DataType>(
@@ -1199,6 +2669,15 @@ mod tests {
     }
   }
 
+  fn test_decoder_encoding<T: 'static + DataType>(encoding: Encoding) {
+    let descr = get_test_column_desc_ptr();
+    let mem_tracker = Rc::new(MemTracker::new());
+    let encoder = get_encoder::<T>(descr.clone(), encoding, mem_tracker).unwrap();
+    let decoder = get_decoder::<T>(descr, encoding).unwrap();
+    assert_eq!(decoder.encoding(), encoding);
+    assert_eq!(decoder.encoding(), encoder.encoding());
+  }
+
   fn test_plain_decode<T: DataType>(
     data: ByteBufferPtr,
     num_values: usize,
@@ -1223,6 +2702,30 @@ mod tests {
     test_encode_decode::<T>(data, Encoding::DELTA_BINARY_PACKED);
   }
 
+  // Encodes each value as a separate page with only the first value in the header and
+  // no deltas, and also an empty page, reusing the same encoder and decoder.
+  fn test_delta_bit_packed_single_value<T: 'static + DataType>(values: Vec<T::T>) {
+    let mut encoder = get_encoder::<T>(get_test_column_desc_ptr(),
+      Encoding::DELTA_BINARY_PACKED, Rc::new(MemTracker::new())).expect("get encoder");
+    let mut decoder = DeltaBitPackDecoder::<T>::new();
+    let mut buffer = vec![T::T::default(); 2];
+
+    for value in values {
+      encoder.put(&[value.clone()]).expect("ok to encode");
+      let bytes = encoder.flush_buffer().expect("ok to flush buffer");
+      decoder.set_data(bytes, 1).expect("ok to set data");
+      assert_eq!(decoder.values_left(), 1);
+      assert_eq!(decoder.get(&mut buffer[..]).expect("ok to decode"), 1);
+      assert_eq!(buffer[0], value);
+      assert_eq!(decoder.values_left(), 0);
+
+      let bytes = encoder.flush_buffer().expect("ok to flush buffer");
+      decoder.set_data(bytes, 0).expect("ok to set data");
+      assert_eq!(decoder.values_left(), 0);
+      assert_eq!(decoder.get(&mut buffer[..]).expect("ok to decode"), 0);
+    }
+  }
+
   fn test_delta_byte_array_decode(data: Vec<Vec<ByteArray>>) {
     test_encode_decode::<ByteArrayType>(data, Encoding::DELTA_BYTE_ARRAY);
   }
@@ -1262,6 +2765,31 @@ mod tests {
     assert_eq!(result, expected);
   }
 
+  // Encodes each page separately and decodes all pages with the same decoder, which is
+  // reset before every page.
+  fn test_reset_decode<T: 'static + DataType>(
+    encoding: Encoding,
+    pages: Vec<Vec<T::T>>
+  ) {
+    let mut encoder = get_encoder::<T>(get_test_column_desc_ptr(), encoding,
+      Rc::new(MemTracker::new())).expect("get encoder");
+    let mut decoder = get_decoder::<T>(get_test_column_desc_ptr(), encoding)
+      .expect("get decoder");
+
+    for page in &pages {
+      encoder.put(&page[..]).expect("ok to encode");
+      let bytes = encoder.flush_buffer().expect("ok to flush buffer");
+
+      decoder.reset();
+      assert_eq!(decoder.values_left(), 0);
+      decoder.set_data(bytes, page.len()).expect("ok to set data");
+      let mut result = vec![T::T::default(); page.len()];
+      assert_eq!(decoder.get(&mut result[..]).expect("ok to decode"), page.len());
+      assert_eq!(decoder.values_left(), 0);
+      assert_eq!(&result, page);
+    }
+  }
+
   fn usize_to_bytes(v: usize) -> [u8; 4] {
     unsafe { mem::transmute::<u32, [u8; 4]>(v as u32) }
   }
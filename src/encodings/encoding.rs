@@ -17,19 +17,29 @@
 
 //! Contains all supported encoders for Parquet.
 
+use std::cell::{Cell, RefCell};
 use std::cmp;
+use std::fmt;
 use std::io::Write;
 use std::marker::PhantomData;
 use std::mem;
+use std::ops::Range;
+use std::rc::Rc;
 use std::slice;
+use std::str;
+#[cfg(feature = "bench")]
+use std::time::{Duration, Instant};
 
 use basic::*;
 use data_type::*;
-use encodings::rle::RleEncoder;
+use encodings::rle::{RleEncoder, MAX_MIN_RLE_RUN_LENGTH, MIN_RLE_RUN_LENGTH};
 use errors::{ParquetError, Result};
-use schema::types::ColumnDescPtr;
-use util::bit_util::{log2, num_required_bits, BitWriter};
-use util::memory::{Buffer, ByteBuffer, ByteBufferPtr, MemTrackerPtr};
+use schema::types::{ColumnDescPtr, ColumnDescriptor};
+use util::bit_util::{
+  bit_pack, bit_width_for_max, ceil, get_array_bit, num_required_bits, BitWriter,
+  MAX_VLQ_BYTE_LEN
+};
+use util::memory::{Buffer, ByteBuffer, ByteBufferPtr, MemTracker, MemTrackerPtr};
 use util::hash_util;
 
 // ----------------------------------------------------------------------
@@ -43,27 +53,207 @@ pub trait Encoder<T: DataType> {
   /// Encodes data from `values`.
   fn put(&mut self, values: &[T::T]) -> Result<()>;
 
+  /// Encodes `count` copies of `value`, same as `put` of a slice with `count` copies.
+  fn put_repeated(&mut self, value: T::T, count: usize) -> Result<()> {
+    let values = [value];
+    for _ in 0..count {
+      self.put(&values)?;
+    }
+    Ok(())
+  }
+
+  /// Encodes the non-null values of `values`, where a slot is null if its bit in the
+  /// validity bitmap `valid_bits` is not set, e.g. of an Arrow array. Bits are in LSB
+  /// order, the same as Arrow validity bitmaps. Null slots are skipped, so callers do
+  /// not need to compact values into a dense slice first.
+  ///
+  /// Returns the number of encoded values, or an error if `valid_bits` has fewer than
+  /// `values.len()` bits.
+  fn put_spaced(&mut self, values: &[T::T], valid_bits: &[u8]) -> Result<usize> {
+    if valid_bits.len() * 8 < values.len() {
+      return Err(general_err!(
+        "Validity bitmap of {} bytes is too short for {} values",
+        valid_bits.len(),
+        values.len()
+      ));
+    }
+    let mut buffer = Vec::with_capacity(values.len());
+    for (i, value) in values.iter().enumerate() {
+      if get_array_bit(valid_bits, i) {
+        buffer.push(value.clone());
+      }
+    }
+    self.put(&buffer[..])?;
+    Ok(buffer.len())
+  }
+
+  /// Checks that all `values` can be encoded, without changing the state of this
+  /// encoder, so that a batch can be rejected as a whole before any of its values is
+  /// put. Returns an error for the first invalid value.
+  ///
+  /// Default implementation accepts all values. Encoders that know the column check
+  /// length of FIXED_LEN_BYTE_ARRAY values and that UTF8 values are valid UTF-8, and
+  /// dictionary encoders with a shared dictionary check that values are in it.
+  fn validate_batch(&self, _values: &[T::T]) -> Result<()> {
+    Ok(())
+  }
+
   /// Returns the encoding type of this encoder.
   fn encoding(&self) -> Encoding;
 
+  /// Returns the number of values that have been put into this encoder since the last
+  /// `flush_buffer()` call.
+  fn num_buffered_values(&self) -> usize;
+
+  /// Returns an estimate of the number of bytes that `flush_buffer()` would return at
+  /// this point. Used to decide when to close the current data page.
+  fn estimated_data_encoded_size(&self) -> usize;
+
+  /// Returns an upper bound of the number of bytes that `flush_buffer()` would return
+  /// at this point, e.g. to size the output slice for `flush_into()`.
+  fn max_encoded_size(&self) -> usize;
+
   /// Flushes the underlying byte buffer that's being processed by this encoder, and
   /// return the immutable copy of it. This will also reset the internal state.
   fn flush_buffer(&mut self) -> Result<ByteBufferPtr>;
+
+  /// Flushes encoded values into `out`, e.g. a preallocated page region, and returns
+  /// the number of bytes written. Resets the internal state same as `flush_buffer()`.
+  ///
+  /// Returns an error if `out` is smaller than `max_encoded_size()`, in which case
+  /// nothing is flushed and all values stay buffered.
+  fn flush_into(&mut self, out: &mut [u8]) -> Result<usize> {
+    let max_size = self.max_encoded_size();
+    if max_size > out.len() {
+      return Err(general_err!(
+        "Output buffer of {} bytes is too small for up to {} encoded bytes",
+        out.len(),
+        max_size
+      ));
+    }
+    let buffer = self.flush_buffer()?;
+    debug_assert!(
+      buffer.len() <= max_size,
+      "Encoded {} bytes, expected at most {} bytes",
+      buffer.len(),
+      max_size
+    );
+    out[..buffer.len()].copy_from_slice(buffer.data());
+    Ok(buffer.len())
+  }
+
+  /// Returns summary of the values buffered in this encoder, e.g. for logging.
+  fn encode_summary(&self) -> EncodeSummary {
+    EncodeSummary {
+      encoding: self.encoding(),
+      num_values: self.num_buffered_values(),
+      estimated_size: self.estimated_data_encoded_size(),
+      num_dict_entries: None
+    }
+  }
+}
+
+/// Number of values converted at a time by `put_u32` and `put_u64`.
+const UNSIGNED_BATCH_SIZE: usize = 256;
+
+/// Extension of INT32 encoders to encode `u32` values, implemented for all encoders,
+/// including `Box<Encoder<Int32Type>>` returned by `get_encoder`.
+pub trait UInt32Encoder {
+  /// Encodes `u32` values, e.g. of a UINT_32 column, as INT32 values. Bits of the values
+  /// are preserved, so values greater than `i32::MAX` are stored as negative INT32
+  /// values, and decoded INT32 values are converted back with `as u32`.
+  fn put_u32(&mut self, values: &[u32]) -> Result<()>;
+}
+
+impl<E: Encoder<Int32Type> + ?Sized> UInt32Encoder for E {
+  fn put_u32(&mut self, values: &[u32]) -> Result<()> {
+    let mut buffer = [0i32; UNSIGNED_BATCH_SIZE];
+    for chunk in values.chunks(UNSIGNED_BATCH_SIZE) {
+      for (i, value) in chunk.iter().enumerate() {
+        buffer[i] = *value as i32;
+      }
+      self.put(&buffer[..chunk.len()])?;
+    }
+    Ok(())
+  }
+}
+
+/// Extension of INT64 encoders to encode `u64` values, implemented for all encoders,
+/// including `Box<Encoder<Int64Type>>` returned by `get_encoder`.
+pub trait UInt64Encoder {
+  /// Encodes `u64` values, e.g. of a UINT_64 column, as INT64 values. Bits of the values
+  /// are preserved, so values greater than `i64::MAX` are stored as negative INT64
+  /// values, and decoded INT64 values are converted back with `as u64`.
+  fn put_u64(&mut self, values: &[u64]) -> Result<()>;
+}
+
+impl<E: Encoder<Int64Type> + ?Sized> UInt64Encoder for E {
+  fn put_u64(&mut self, values: &[u64]) -> Result<()> {
+    let mut buffer = [0i64; UNSIGNED_BATCH_SIZE];
+    for chunk in values.chunks(UNSIGNED_BATCH_SIZE) {
+      for (i, value) in chunk.iter().enumerate() {
+        buffer[i] = *value as i64;
+      }
+      self.put(&buffer[..chunk.len()])?;
+    }
+    Ok(())
+  }
+}
+
+/// Summary of the encoder state, see `Encoder::encode_summary()`.
+/// Displayed as a single line, e.g.
+/// `encoding: PLAIN_DICTIONARY, values: 100, estimated bytes: 27, dictionary entries: 5`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EncodeSummary {
+  /// Encoding of the encoder.
+  pub encoding: Encoding,
+  /// Number of values buffered since the last flush.
+  pub num_values: usize,
+  /// Estimated number of bytes of the encoded values.
+  pub estimated_size: usize,
+  /// Number of dictionary entries, only set for dictionary encoders.
+  pub num_dict_entries: Option<usize>
+}
+
+impl fmt::Display for EncodeSummary {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "encoding: {}, values: {}, estimated bytes: {}",
+      self.encoding,
+      self.num_values,
+      self.estimated_size
+    )?;
+    if let Some(num_entries) = self.num_dict_entries {
+      write!(f, ", dictionary entries: {}", num_entries)?;
+    }
+    Ok(())
+  }
 }
 
 /// Gets a encoder for the particular data type `T` and encoding `encoding`. Memory usage
 /// for the encoder instance is tracked by `mem_tracker`.
+///
+/// Returns an error for FIXED_LEN_BYTE_ARRAY columns with negative type length, and for
+/// BIT_PACKED encoding of INT32 values, which requires bit width, see `BitPackedEncoder`.
 pub fn get_encoder<T: DataType>(
   desc: ColumnDescPtr,
   encoding: Encoding,
   mem_tracker: MemTrackerPtr
 ) -> Result<Box<Encoder<T>>> where T: 'static {
+  if T::get_physical_type() == Type::FIXED_LEN_BYTE_ARRAY && desc.type_length() < 0 {
+    return Err(general_err!(
+      "Column {}: invalid FIXED_LEN_BYTE_ARRAY length {}",
+      desc.path(),
+      desc.type_length()
+    ));
+  }
   let encoder: Box<Encoder<T>> = match encoding {
     Encoding::PLAIN => {
       Box::new(PlainEncoder::new(desc, mem_tracker, vec![]))
     },
     Encoding::RLE_DICTIONARY | Encoding::PLAIN_DICTIONARY => {
-      Box::new(DictEncoder::new(desc, mem_tracker))
+      Box::new(DictEncoder::new_labeled(desc, mem_tracker, encoding))
     },
     Encoding::RLE => {
       Box::new(RleValueEncoder::new())
@@ -77,11 +267,308 @@ pub fn get_encoder<T: DataType>(
     Encoding::DELTA_BYTE_ARRAY => {
       Box::new(DeltaByteArrayEncoder::new())
     },
+    Encoding::BIT_PACKED => {
+      match T::get_physical_type() {
+        Type::BOOLEAN => Box::new(BitPackedEncoder::new(1)),
+        Type::INT32 => {
+          return Err(general_err!(
+            "Column {}: BIT_PACKED encoding of INT32 values requires bit width, \
+             use BitPackedEncoder::new()",
+            desc.path()
+          ))
+        },
+        t => return Err(nyi_err!("Encoding BIT_PACKED is not supported for type {}", t))
+      }
+    },
     e => return Err(nyi_err!("Encoding {} is not supported.", e))
   };
   Ok(encoder)
 }
 
+/// Splits `values` into chunks, so that each chunk encoded with `encoding` takes at most
+/// `max_page_bytes` bytes according to `Encoder::estimated_data_encoded_size`, and
+/// returns index ranges of the chunks in order.
+///
+/// Values are trial-encoded one at a time. A value that does not fit into the budget on
+/// its own is returned as a single value chunk.
+pub fn chunk_by_encoded_size<T: DataType>(
+  values: &[T::T],
+  encoding: Encoding,
+  desc: ColumnDescPtr,
+  max_page_bytes: usize
+) -> Result<Vec<Range<usize>>> where T: 'static {
+  let mut encoder = get_encoder::<T>(desc, encoding, Rc::new(MemTracker::new()))?;
+  let mut chunks = vec![];
+  let mut start = 0;
+  for i in 0..values.len() {
+    encoder.put(&values[i..i + 1])?;
+    if encoder.estimated_data_encoded_size() > max_page_bytes && i > start {
+      // Value does not fit into the current chunk, start a new chunk with it
+      chunks.push(start..i);
+      start = i;
+      encoder.flush_buffer()?;
+      encoder.put(&values[i..i + 1])?;
+    }
+  }
+  if start < values.len() {
+    chunks.push(start..values.len());
+  }
+  Ok(chunks)
+}
+
+/// Returns true if `values` are sorted in non-decreasing order, e.g. to decide whether
+/// to use DELTA_BINARY_PACKED encoding, which benefits from small positive deltas.
+/// Empty and single value slices are sorted. Values that are not comparable, such as
+/// NaN, are treated as unsorted.
+pub fn is_sorted<T: DataType>(values: &[T::T]) -> bool where T::T: PartialOrd {
+  values.windows(2).all(|pair| pair[0] <= pair[1])
+}
+
+/// Number of values inspected by `recommend_encoding`.
+const RECOMMEND_SAMPLE_SIZE: usize = 8 * 1024;
+
+/// Recommends encoding for `values` of a column based on a sample of the first values.
+///
+/// For BOOLEAN values RLE is recommended, if runs of the same value are long enough for
+/// RLE encoding to be smaller than PLAIN bit-packing, e.g. for almost all-true or
+/// all-false columns. For INT32 and INT64 values DELTA_BINARY_PACKED is recommended, if
+/// the sample has at least 2 values and is sorted, see `is_sorted`. PLAIN is
+/// recommended otherwise and for all other types.
+pub fn recommend_encoding<T: DataType>(values: &[T::T]) -> Encoding {
+  let sample = &values[..cmp::min(values.len(), RECOMMEND_SAMPLE_SIZE)];
+  match T::get_physical_type() {
+    Type::BOOLEAN => {},
+    Type::INT32 | Type::INT64 => {
+      return if sample.len() > 1 && T::is_sorted_integers(sample) {
+        Encoding::DELTA_BINARY_PACKED
+      } else {
+        Encoding::PLAIN
+      };
+    },
+    _ => return Encoding::PLAIN
+  }
+  let num_runs = sample.windows(2).filter(|pair| pair[0] != pair[1]).count() + 1;
+  // Each RLE run takes at least 2 bytes, indicator and value, after the 4-byte length
+  let rle_size = mem::size_of::<i32>() + 2 * num_runs;
+  let plain_size = ceil(sample.len() as i64, 8) as usize;
+  if rle_size < plain_size { Encoding::RLE } else { Encoding::PLAIN }
+}
+
+/// Helper trait to check sortedness of INT32 and INT64 values in `recommend_encoding`,
+/// whose values are not comparable for all types.
+trait SortedIntegers: DataType {
+  // Returns true if values are INT32 or INT64 values and are sorted
+  fn is_sorted_integers(values: &[Self::T]) -> bool;
+}
+
+impl<T: DataType> SortedIntegers for T {
+  default fn is_sorted_integers(_values: &[T::T]) -> bool {
+    false
+  }
+}
+
+impl SortedIntegers for Int32Type {
+  fn is_sorted_integers(values: &[i32]) -> bool {
+    is_sorted::<Int32Type>(values)
+  }
+}
+
+impl SortedIntegers for Int64Type {
+  fn is_sorted_integers(values: &[i64]) -> bool {
+    is_sorted::<Int64Type>(values)
+  }
+}
+
+/// Returns the minimum of integer `values` (frame of reference) and offsets of the
+/// values from it, so that `values[i] == min + offsets[i]`. Pre-subtracting the minimum
+/// can shrink magnitudes of the values before DELTA_BINARY_PACKED encoding.
+///
+/// Offsets are computed with wrapping arithmetic, so offsets of INT64 values whose range
+/// does not fit into `i64` wrap around and the originals are reconstructed with
+/// `wrapping_add`. Minimum of empty `values` is 0.
+pub fn frame_of_reference<T: DataType>(values: &[T::T]) -> (i64, Vec<i64>)
+    where T::T: Copy + Into<i64> {
+  let min = values.iter().map(|v| Into::<i64>::into(*v)).min().unwrap_or(0);
+  let offsets = values.iter().map(|v| Into::<i64>::into(*v).wrapping_sub(min)).collect();
+  (min, offsets)
+}
+
+/// Encodes a single statistic `value`, e.g. min or max value of a page in the column
+/// index, into the byte form used by Parquet statistics: PLAIN encoding of the value,
+/// except that BYTE_ARRAY values are stored as raw bytes without the 4-byte length
+/// prefix. BOOLEAN values take one byte.
+///
+/// Returns an error if FIXED_LEN_BYTE_ARRAY `value` does not match the type length.
+pub fn encode_stat_value<T: DataType>(
+  value: &T::T,
+  desc: ColumnDescPtr
+) -> Result<Vec<u8>> {
+  let bytes = value.as_bytes();
+  if desc.physical_type() == Type::FIXED_LEN_BYTE_ARRAY &&
+      bytes.len() != desc.type_length() as usize {
+    return Err(general_err!(
+      "Column {}: expected value of length {}, found {}",
+      desc.path(),
+      desc.type_length(),
+      bytes.len()
+    ));
+  }
+  Ok(bytes.to_vec())
+}
+
+/// Compares statistic values `a` and `b` of the column `desc`, e.g. to find min and max
+/// values of a page. Uses the default order of the physical type, see
+/// `DataType::compare`, except for DECIMAL values stored in BYTE_ARRAY or
+/// FIXED_LEN_BYTE_ARRAY, which are compared numerically as signed big-endian integers.
+pub fn compare_stat_values<T: DataType>(
+  a: &T::T,
+  b: &T::T,
+  desc: &ColumnDescriptor
+) -> cmp::Ordering {
+  match (desc.logical_type(), desc.physical_type()) {
+    (LogicalType::DECIMAL, Type::BYTE_ARRAY) |
+    (LogicalType::DECIMAL, Type::FIXED_LEN_BYTE_ARRAY) => {
+      compare_decimal(a.as_bytes(), b.as_bytes())
+    },
+    _ => T::compare(a, b)
+  }
+}
+
+/// Returns boundary order of pages with min values `page_mins` and max values
+/// `page_maxes`, compared with `DataType::compare`, for the column index. Pages are
+/// ascending (or descending) if min and max values of every page are not less (or not
+/// greater) than those of the previous page. A single page, no pages, and pages with
+/// equal values are ascending.
+///
+/// Panics if `page_mins` and `page_maxes` have different lengths.
+pub fn boundary_order<T: DataType>(
+  page_mins: &[T::T],
+  page_maxes: &[T::T]
+) -> BoundaryOrder {
+  assert_eq!(
+    page_mins.len(),
+    page_maxes.len(),
+    "Number of page min values does not match number of page max values"
+  );
+  let is_ordered = |order: cmp::Ordering| {
+    page_mins.windows(2).all(|w| T::compare(&w[0], &w[1]) != order) &&
+      page_maxes.windows(2).all(|w| T::compare(&w[0], &w[1]) != order)
+  };
+  if is_ordered(cmp::Ordering::Greater) {
+    BoundaryOrder::ASCENDING
+  } else if is_ordered(cmp::Ordering::Less) {
+    BoundaryOrder::DESCENDING
+  } else {
+    BoundaryOrder::UNORDERED
+  }
+}
+
+// Checks constraints of the column `desc` on `values`: length of FIXED_LEN_BYTE_ARRAY
+// values and UTF-8 encoding of UTF8 values.
+fn validate_column_values<T: DataType>(
+  values: &[T::T],
+  desc: &ColumnDescriptor
+) -> Result<()> {
+  if desc.physical_type() == Type::FIXED_LEN_BYTE_ARRAY {
+    let type_length = desc.type_length();
+    for v in values {
+      let len = v.as_bytes().len();
+      if len != type_length as usize {
+        return Err(general_err!(
+          "Column {}: expected value of length {}, found {}",
+          desc.path(),
+          type_length,
+          len
+        ));
+      }
+    }
+  }
+  if desc.logical_type() == LogicalType::UTF8 {
+    for v in values {
+      if let Err(e) = str::from_utf8(v.as_bytes()) {
+        return Err(general_err!("Column {}: invalid UTF-8 value: {}", desc.path(), e));
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Returns the minimum number of bytes that `encoding` produces for `num_values` values
+/// of `physical` type with type length `type_length` (only used for
+/// FIXED_LEN_BYTE_ARRAY), e.g. to pre-allocate page buffers.
+///
+/// For PLAIN encoding BYTE_ARRAY values are assumed to be empty, for dictionary
+/// encoding this is the 1-byte bit width, and for DELTA_BINARY_PACKED encoding the page
+/// header with a block header for each block of deltas that are all the same. Returns 0
+/// for encodings that are not supported by encoders.
+pub fn min_encoded_size(
+  encoding: Encoding,
+  num_values: usize,
+  physical: Type,
+  type_length: i32
+) -> usize {
+  // Number of bytes of a VLQ encoded `value`, at least 1 byte
+  let vlq_len =
+    |value: u64| cmp::max(1, ceil(num_required_bits(value) as i64, 7) as usize);
+  // Page header followed by min delta and mini block bit widths of each block
+  let delta_size = |num_values: usize| {
+    let header_len = vlq_len(DEFAULT_BLOCK_SIZE as u64) +
+      vlq_len(DEFAULT_NUM_MINI_BLOCKS as u64) + vlq_len(num_values as u64) + 1;
+    let num_deltas = num_values.saturating_sub(1);
+    let num_blocks = ceil(num_deltas as i64, DEFAULT_BLOCK_SIZE as i64) as usize;
+    header_len + num_blocks * (1 + DEFAULT_NUM_MINI_BLOCKS)
+  };
+
+  match encoding {
+    Encoding::PLAIN => match physical {
+      Type::BOOLEAN => ceil(num_values as i64, 8) as usize,
+      Type::INT32 | Type::FLOAT => num_values * 4,
+      Type::INT64 | Type::DOUBLE => num_values * 8,
+      Type::INT96 => num_values * 12,
+      // Length prefix of each value
+      Type::BYTE_ARRAY => num_values * mem::size_of::<u32>(),
+      Type::FIXED_LEN_BYTE_ARRAY => num_values * cmp::max(type_length, 0) as usize
+    },
+    Encoding::PLAIN_DICTIONARY | Encoding::RLE_DICTIONARY => 1,
+    // 4 bytes for the length of encoded data
+    Encoding::RLE => mem::size_of::<i32>(),
+    Encoding::DELTA_BINARY_PACKED => delta_size(num_values),
+    // Delta encoded lengths of empty values
+    Encoding::DELTA_LENGTH_BYTE_ARRAY => delta_size(num_values),
+    // Delta encoded prefix lengths and suffix lengths of empty values
+    Encoding::DELTA_BYTE_ARRAY => 2 * delta_size(num_values),
+    _ => 0
+  }
+}
+
+/// Measures time spent encoding `values` with `encoding` `iters` times, e.g. to track
+/// encoding throughput of a particular workload.
+///
+/// A fresh encoder is created for each iteration, so that every iteration encodes
+/// values from the same initial state, e.g. dictionary encoders start with an empty
+/// dictionary. Only `put()` and `flush_buffer()` are timed.
+///
+/// Panics if encoding is not supported or values fail to encode.
+#[cfg(feature = "bench")]
+pub fn bench_encode<T: DataType>(
+  encoding: Encoding,
+  values: &[T::T],
+  desc: ColumnDescPtr,
+  iters: usize
+) -> Duration where T: 'static {
+  let mut elapsed = Duration::new(0, 0);
+  for _ in 0..iters {
+    let mut encoder =
+      get_encoder::<T>(desc.clone(), encoding, Rc::new(MemTracker::new()))
+        .expect("get encoder");
+    let start = Instant::now();
+    encoder.put(values).expect("put values");
+    encoder.flush_buffer().expect("flush buffer");
+    elapsed += start.elapsed();
+  }
+  elapsed
+}
+
 // ----------------------------------------------------------------------
 // Plain encoding
 
@@ -100,6 +587,15 @@ pub struct PlainEncoder<T: DataType> {
   buffer: ByteBuffer,
   bit_writer: BitWriter,
   desc: ColumnDescPtr,
+  num_values: usize,
+  // If true, infinite float/double values are rejected, see `reject_non_finite`
+  reject_infinite: bool,
+  // If true, NaN float/double values are rejected, see `reject_non_finite`
+  reject_nan: bool,
+  // If true, `first_last` is updated when values are put, see `track_first_last`
+  track_first_last: bool,
+  // First and last values buffered since the last flush, see `first_last`
+  first_last: Option<(T::T, T::T)>,
   _phantom: PhantomData<T>
 }
 
@@ -112,13 +608,60 @@ impl<T: DataType> PlainEncoder<T> {
       buffer: byte_buffer,
       bit_writer: BitWriter::new(256),
       desc: desc,
+      num_values: 0,
+      reject_infinite: false,
+      reject_nan: false,
+      track_first_last: false,
+      first_last: None,
       _phantom: PhantomData
     }
   }
-}
 
-impl<T: DataType> Encoder<T> for PlainEncoder<T> {
-  default fn put(&mut self, values: &[T::T]) -> Result<()> {
+  /// Sets whether the first and the last buffered values are tracked as values are
+  /// put, disabled by default, see `first_last()`. Tracking keeps a copy of the first
+  /// and the last value, which requires copying the values put with `put_slices()`.
+  pub fn track_first_last(mut self, track: bool) -> Self {
+    self.track_first_last = track;
+    self
+  }
+
+  /// Returns the first and the last value buffered since the last flush, or `None` if
+  /// no values have been buffered or tracking is not enabled with
+  /// `track_first_last()`. This is cheaper than computing min and max values, e.g.
+  /// when only page boundaries are needed.
+  pub fn first_last(&self) -> Option<(T::T, T::T)> {
+    self.first_last.clone()
+  }
+
+  // Updates the first and the last buffered values with `values` that are encoded.
+  #[inline]
+  fn update_first_last(&mut self, values: &[T::T]) {
+    if !self.track_first_last {
+      return;
+    }
+    if let Some(last) = values.last() {
+      let first = match self.first_last.take() {
+        Some((first, _)) => first,
+        None => values[0].clone()
+      };
+      self.first_last = Some((first, last.clone()));
+    }
+  }
+
+  /// Enables strict mode for FLOAT and DOUBLE columns, in which `put` returns an error
+  /// if any of the values is positive or negative infinity, or NaN when `reject_nan`
+  /// is true. Values are validated before any of them is encoded.
+  ///
+  /// Disabled by default, since non-finite values are valid according to the spec.
+  /// Has no effect for other types.
+  pub fn reject_non_finite(mut self, reject_nan: bool) -> Self {
+    self.reject_infinite = true;
+    self.reject_nan = reject_nan;
+    self
+  }
+
+  #[inline]
+  fn put_raw(&mut self, values: &[T::T]) -> Result<()> {
     let bytes = unsafe {
       slice::from_raw_parts(
         values as *const [T::T] as *const u8,
@@ -126,18 +669,59 @@ impl<T: DataType> Encoder<T> for PlainEncoder<T> {
       )
     };
     self.buffer.write(bytes)?;
+    self.num_values += values.len();
+    self.update_first_last(values);
     Ok(())
   }
+}
+
+// Buffers are summarized by their size, values are not printed.
+impl<T: DataType> fmt::Debug for PlainEncoder<T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("PlainEncoder")
+      .field("column", &self.desc.path().string())
+      .field("encoding", &self.encoding())
+      .field("num_values", &self.num_buffered_values())
+      .field("buffered_bytes", &self.estimated_data_encoded_size())
+      .field("reject_infinite", &self.reject_infinite)
+      .field("reject_nan", &self.reject_nan)
+      .finish()
+  }
+}
+
+impl<T: DataType> Encoder<T> for PlainEncoder<T> {
+  default fn put(&mut self, values: &[T::T]) -> Result<()> {
+    self.put_raw(values)
+  }
+
+  default fn validate_batch(&self, values: &[T::T]) -> Result<()> {
+    validate_column_values::<T>(values, &self.desc)
+  }
 
   fn encoding(&self) -> Encoding {
     Encoding::PLAIN
   }
 
+  fn num_buffered_values(&self) -> usize {
+    self.num_values
+  }
+
+  fn estimated_data_encoded_size(&self) -> usize {
+    self.buffer.size() + self.bit_writer.bytes_written()
+  }
+
+  fn max_encoded_size(&self) -> usize {
+    // Size of plain encoded values is exact
+    self.estimated_data_encoded_size()
+  }
+
   #[inline]
   default fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
     self.buffer.write(self.bit_writer.flush_buffer())?;
     self.buffer.flush()?;
     self.bit_writer.clear();
+    self.num_values = 0;
+    self.first_last = None;
 
     Ok(self.buffer.consume())
   }
@@ -146,7 +730,60 @@ impl<T: DataType> Encoder<T> for PlainEncoder<T> {
 impl Encoder<BoolType> for PlainEncoder<BoolType> {
   fn put(&mut self, values: &[bool]) -> Result<()> {
     for v in values {
-      self.bit_writer.put_value(*v as u64, 1);
+      if !self.bit_writer.put_value(*v as u64, 1) {
+        // Bit writer is full, move its content into the byte buffer. Each value takes
+        // exactly 1 bit, so the writer is byte aligned at this point and flushing does
+        // not introduce any padding between values.
+        self.buffer.write(self.bit_writer.flush_buffer())?;
+        self.bit_writer.clear();
+        self.bit_writer.put_value(*v as u64, 1);
+      }
+    }
+    self.num_values += values.len();
+    self.update_first_last(values);
+    Ok(())
+  }
+}
+
+impl Encoder<FloatType> for PlainEncoder<FloatType> {
+  fn put(&mut self, values: &[f32]) -> Result<()> {
+    self.validate_batch(values)?;
+    self.put_raw(values)
+  }
+
+  fn validate_batch(&self, values: &[f32]) -> Result<()> {
+    if self.reject_infinite {
+      for v in values {
+        if v.is_infinite() || (self.reject_nan && v.is_nan()) {
+          return Err(general_err!(
+            "Column {}: value {} is not finite",
+            self.desc.path(),
+            v
+          ));
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+impl Encoder<DoubleType> for PlainEncoder<DoubleType> {
+  fn put(&mut self, values: &[f64]) -> Result<()> {
+    self.validate_batch(values)?;
+    self.put_raw(values)
+  }
+
+  fn validate_batch(&self, values: &[f64]) -> Result<()> {
+    if self.reject_infinite {
+      for v in values {
+        if v.is_infinite() || (self.reject_nan && v.is_nan()) {
+          return Err(general_err!(
+            "Column {}: value {} is not finite",
+            self.desc.path(),
+            v
+          ));
+        }
+      }
     }
     Ok(())
   }
@@ -158,27 +795,65 @@ impl Encoder<Int96Type> for PlainEncoder<Int96Type> {
       self.buffer.write(v.as_bytes())?;
     }
     self.buffer.flush()?;
+    self.num_values += values.len();
+    self.update_first_last(values);
     Ok(())
   }
 }
 
 impl Encoder<ByteArrayType> for PlainEncoder<ByteArrayType> {
   fn put(&mut self, values: &[ByteArray]) -> Result<()> {
+    // Grow buffer once for all values, each value is prefixed with 4 bytes of length
+    let num_bytes = values.iter().fold(0, |acc, v| acc + v.len() + mem::size_of::<u32>());
+    self.buffer.reserve(num_bytes);
     for v in values {
       self.buffer.write(&(v.len().to_le() as u32).as_bytes())?;
       self.buffer.write(v.data())?;
     }
     self.buffer.flush()?;
+    self.num_values += values.len();
+    self.update_first_last(values);
+    Ok(())
+  }
+}
+
+impl PlainEncoder<ByteArrayType> {
+  /// Encodes byte array values from borrowed slices.
+  /// Output is identical to `put` with the equivalent `ByteArray` values, but does not
+  /// require converting each slice into `ByteArray` first.
+  pub fn put_slices(&mut self, values: &[&[u8]]) -> Result<()> {
+    let num_bytes = values.iter().fold(0, |acc, v| acc + v.len() + mem::size_of::<u32>());
+    self.buffer.reserve(num_bytes);
+    for v in values {
+      self.buffer.write(&(v.len().to_le() as u32).as_bytes())?;
+      self.buffer.write(v)?;
+    }
+    self.buffer.flush()?;
+    self.num_values += values.len();
+    if self.track_first_last {
+      if let Some(last) = values.last() {
+        // First value is only copied at the start of the page
+        let first = match self.first_last.take() {
+          Some((first, _)) => first,
+          None => ByteArray::from(values[0].to_vec())
+        };
+        self.first_last = Some((first, ByteArray::from(last.to_vec())));
+      }
+    }
     Ok(())
   }
 }
 
 impl Encoder<FixedLenByteArrayType> for PlainEncoder<FixedLenByteArrayType> {
   fn put(&mut self, values: &[ByteArray]) -> Result<()> {
+    // Check all values first, so that no values are written on error
+    self.validate_batch(values)?;
     for v in values {
       self.buffer.write(v.data())?;
     }
     self.buffer.flush()?;
+    self.num_values += values.len();
+    self.update_first_last(values);
     Ok(())
   }
 }
@@ -190,6 +865,103 @@ const INITIAL_HASH_TABLE_SIZE: usize = 1024;
 const MAX_HASH_LOAD: f32 = 0.7;
 const HASH_SLOT_EMPTY: i32 = -1;
 
+/// Records the point at which a dictionary encoder fell back to another encoding.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FallbackInfo {
+  /// Number of values encoded with the dictionary before fallback, i.e. index of the
+  /// first value that must be written with the fallback encoding.
+  pub num_dict_values: u64,
+  /// Whether the dictionary page had already been written out before fallback.
+  pub dict_page_written: bool
+}
+
+/// Immutable dictionary that can be shared between multiple dictionary encoders, e.g.
+/// for columns with the same small domain of values, so that all of them are encoded
+/// with the same indices and can reuse one dictionary page.
+///
+/// Cloning is cheap: entries and lookup table are reference counted.
+pub struct SharedDictionary<T: DataType> {
+  // Dictionary entries, entry at position `i` is encoded with index `i`.
+  entries: Rc<[T::T]>,
+
+  // Hash table with linear probing that maps values to indices in `entries`.
+  // Size is a power of 2 and is larger than number of entries, so probing always
+  // finds an empty slot for an absent value.
+  hash_slots: Rc<[i32]>,
+
+  // Size of the hash table - 1.
+  mod_bitmask: u32
+}
+
+impl<T: DataType> SharedDictionary<T> {
+  /// Creates shared dictionary from `entries`, returns an error if entries are not
+  /// unique.
+  pub fn new(entries: Vec<T::T>) -> Result<Self> {
+    let hash_table_size = cmp::max(entries.len() * 2, 1).next_power_of_two();
+    let mod_bitmask = (hash_table_size - 1) as u32;
+    let mut hash_slots = vec![HASH_SLOT_EMPTY; hash_table_size];
+    for (i, value) in entries.iter().enumerate() {
+      let mut j = (hash_util::hash(value, 0) & mod_bitmask) as usize;
+      while hash_slots[j] != HASH_SLOT_EMPTY {
+        if entries[hash_slots[j] as usize] == *value {
+          return Err(general_err!("Duplicate dictionary entry {:?}", value));
+        }
+        j = (j + 1) & (hash_table_size - 1);
+      }
+      hash_slots[j] = i as i32;
+    }
+
+    Ok(Self {
+      entries: Rc::from(entries),
+      hash_slots: Rc::from(hash_slots),
+      mod_bitmask: mod_bitmask
+    })
+  }
+
+  /// Returns dictionary entries.
+  pub fn entries(&self) -> &[T::T] {
+    &self.entries
+  }
+
+  /// Returns index of `value` in the dictionary or `None` if value is absent.
+  pub fn index_of(&self, value: &T::T) -> Option<i32> {
+    self.probe(hash_util::hash(value, 0), |entry| entry == value)
+  }
+
+  #[inline]
+  fn probe<F: Fn(&T::T) -> bool>(&self, hash: u32, is_equal: F) -> Option<i32> {
+    let mut j = (hash & self.mod_bitmask) as usize;
+    loop {
+      let index = self.hash_slots[j];
+      if index == HASH_SLOT_EMPTY {
+        return None;
+      }
+      if is_equal(&self.entries[index as usize]) {
+        return Some(index);
+      }
+      j = (j + 1) & self.mod_bitmask as usize;
+    }
+  }
+}
+
+impl SharedDictionary<ByteArrayType> {
+  /// Returns index of byte array `value` in the dictionary or `None` if value is
+  /// absent.
+  pub fn index_of_slice(&self, value: &[u8]) -> Option<i32> {
+    self.probe(hash_util::hash(&value, 0), |entry| entry.data() == value)
+  }
+}
+
+impl<T: DataType> Clone for SharedDictionary<T> {
+  fn clone(&self) -> Self {
+    Self {
+      entries: self.entries.clone(),
+      hash_slots: self.hash_slots.clone(),
+      mod_bitmask: self.mod_bitmask
+    }
+  }
+}
+
 /// Dictionary encoder.
 /// The dictionary encoding builds a dictionary of values encountered in a given column.
 /// The dictionary page is written first, before the data pages of the column chunk.
@@ -211,6 +983,10 @@ pub struct DictEncoder<T: DataType> {
   // `j % hash_table_size`, but uses far fewer CPU cycles.
   mod_bitmask: u32,
 
+  // Maximum ratio of unique values to `hash_table_size`, the table is doubled once it
+  // is exceeded. See `with_load_factor()`.
+  max_hash_load: f32,
+
   // Stores indices which map (many-to-one) to the values in the `uniques` array.
   // Here we are using fix-sized array with linear probing.
   // A slot with `HASH_SLOT_EMPTY` indicates the slot is not currently occupied.
@@ -225,6 +1001,34 @@ pub struct DictEncoder<T: DataType> {
   // The number of bytes needed to encode this dictionary
   dict_encoded_size: u64,
 
+  // Index of the last encoded value, so that runs of the same value skip hash table
+  // lookup. `HASH_SLOT_EMPTY` if no value has been encoded yet.
+  last_index: i32,
+
+  // Number of hash table lookups, used in tests to verify the fast path.
+  #[cfg(test)]
+  num_hash_lookups: u64,
+
+  // Number of values written out by `write_indices()`.
+  num_values_written: u64,
+
+  // Whether `write_dict()` has been called.
+  dict_page_written: bool,
+
+  // Set once the encoder falls back, see `fallback()`.
+  fallback_info: Option<FallbackInfo>,
+
+  // Dictionary to look up indices in, see `with_shared_dict()`. When set, `uniques`
+  // and the hash table of this encoder are not used.
+  shared_dict: Option<SharedDictionary<T>>,
+
+  // Encoding of data pages reported by `encoding()`, see `new_labeled()`.
+  data_page_encoding: Encoding,
+
+  // Minimum length of a run of the same index that is RLE encoded by `write_indices()`,
+  // see `with_min_rle_run_length()`.
+  min_rle_run_length: usize,
+
   // Tracking memory usage for the various data structures in this struct.
   mem_tracker: MemTrackerPtr
 }
@@ -238,55 +1042,315 @@ impl<T: DataType> DictEncoder<T> {
       desc: desc,
       hash_table_size: INITIAL_HASH_TABLE_SIZE,
       mod_bitmask: (INITIAL_HASH_TABLE_SIZE - 1) as u32,
+      max_hash_load: MAX_HASH_LOAD,
       hash_slots: slots,
       buffered_indices: Buffer::new().with_mem_tracker(mem_tracker.clone()),
       uniques: Buffer::new().with_mem_tracker(mem_tracker.clone()),
       dict_encoded_size: 0,
+      last_index: HASH_SLOT_EMPTY,
+      #[cfg(test)]
+      num_hash_lookups: 0,
+      num_values_written: 0,
+      dict_page_written: false,
+      fallback_info: None,
+      shared_dict: None,
+      data_page_encoding: Encoding::PLAIN_DICTIONARY,
+      min_rle_run_length: MIN_RLE_RUN_LENGTH,
       mem_tracker: mem_tracker
     }
   }
 
-  /// Returns number of unique entries in the dictionary.
-  pub fn num_entries(&self) -> usize {
-    self.uniques.size()
+  /// Creates new dictionary encoder that reports `data_page_encoding` as encoding of
+  /// data pages, either PLAIN_DICTIONARY (default, deprecated for data pages) or
+  /// RLE_DICTIONARY. Encoded indices are the same for both, and dictionary page written
+  /// with `write_dict()` is always PLAIN encoded.
+  ///
+  /// Panics if `data_page_encoding` is not a dictionary encoding.
+  pub fn new_labeled(
+    desc: ColumnDescPtr,
+    mem_tracker: MemTrackerPtr,
+    data_page_encoding: Encoding
+  ) -> Self {
+    assert!(
+      data_page_encoding == Encoding::PLAIN_DICTIONARY ||
+        data_page_encoding == Encoding::RLE_DICTIONARY,
+      "Invalid dictionary data page encoding {}",
+      data_page_encoding
+    );
+    let mut encoder = Self::new(desc, mem_tracker);
+    encoder.data_page_encoding = data_page_encoding;
+    encoder
   }
 
-  /// Writes out the dictionary values with PLAIN encoding in a byte buffer, and return
-  /// the result.
-  #[inline]
-  pub fn write_dict(&self) -> Result<ByteBufferPtr> {
-    let mut plain_encoder = PlainEncoder::<T>::new(
-      self.desc.clone(), self.mem_tracker.clone(), vec![]);
-    plain_encoder.put(self.uniques.data())?;
-    plain_encoder.flush_buffer()
+  /// Creates new dictionary encoder with hash table load factor `load_factor` instead
+  /// of the default 0.7. Higher load factor resizes the table less often and uses less
+  /// memory, but takes more probing to look up values; lower load factor is the
+  /// opposite. Encoded output does not depend on the load factor.
+  ///
+  /// Panics if `load_factor` is not in range (0.0, 1.0).
+  pub fn with_load_factor(
+    desc: ColumnDescPtr,
+    mem_tracker: MemTrackerPtr,
+    load_factor: f32
+  ) -> Self {
+    assert!(
+      load_factor > 0.0 && load_factor < 1.0,
+      "Invalid hash table load factor {}, must be in range (0.0, 1.0)",
+      load_factor
+    );
+    let mut encoder = Self::new(desc, mem_tracker);
+    encoder.max_hash_load = load_factor;
+    encoder
   }
 
-  /// Writes out the dictionary values with RLE encoding in a byte buffer, and return the
-  /// result.
-  #[inline]
-  pub fn write_indices(&mut self) -> Result<ByteBufferPtr> {
-    let bit_width = self.bit_width();
-    // TODO: the caller should allocate the buffer
-    let buffer_len = 1 + RleEncoder::min_buffer_size(bit_width) +
-      RleEncoder::max_buffer_size(bit_width, self.buffered_indices.size());
-    let mut buffer: Vec<u8> = vec![0; buffer_len as usize];
-    buffer[0] = bit_width as u8;
-    self.mem_tracker.alloc(buffer.capacity() as i64);
+  /// Sets minimum number of same indices that are RLE encoded in `write_indices()`
+  /// instead of the default `MIN_RLE_RUN_LENGTH`, and returns itself, so that it can be
+  /// combined with any constructor. Shorter runs are bit-packed. Larger values favour
+  /// bit-packing, e.g. for high cardinality columns with occasional short runs, and the
+  /// default favours RLE runs, e.g. for low cardinality columns.
+  /// See `RleEncoder::with_min_run_length()`.
+  ///
+  /// Panics if `min_run_length` is less than `MIN_RLE_RUN_LENGTH` or greater than
+  /// `MAX_MIN_RLE_RUN_LENGTH`.
+  pub fn with_min_rle_run_length(mut self, min_run_length: usize) -> Self {
+    assert!(
+      min_run_length >= MIN_RLE_RUN_LENGTH,
+      "Minimum RLE run length {} must be at least {}",
+      min_run_length,
+      MIN_RLE_RUN_LENGTH
+    );
+    assert!(
+      min_run_length <= MAX_MIN_RLE_RUN_LENGTH,
+      "Minimum RLE run length {} must be at most {}",
+      min_run_length,
+      MAX_MIN_RLE_RUN_LENGTH
+    );
+    self.min_rle_run_length = min_run_length;
+    self
+  }
 
-    // Write bit width in the first byte
-    buffer.write((self.bit_width() as u8).as_bytes())?;
-    let mut encoder = RleEncoder::new_from_buf(self.bit_width(), buffer, 1);
-    for index in self.buffered_indices.data() {
-      if !encoder.put(*index as u64)? {
-        return Err(general_err!("Encoder doesn't have enough space"));
-      }
-    }
-    self.buffered_indices.clear();
-    Ok(ByteBufferPtr::new(encoder.consume()?))
+  /// Creates new dictionary encoder that encodes values with indices of the shared
+  /// dictionary `dict` instead of building its own dictionary. Values that are not in
+  /// the shared dictionary are rejected with an error.
+  pub fn with_shared_dict(
+    desc: ColumnDescPtr,
+    dict: SharedDictionary<T>,
+    mem_tracker: MemTrackerPtr
+  ) -> Self {
+    let mut encoder = Self::new(desc, mem_tracker);
+    encoder.shared_dict = Some(dict);
+    encoder
   }
 
-  #[inline]
-  fn put_one(&mut self, value: &T::T) -> Result<()> {
+  /// Returns number of unique entries in the dictionary.
+  pub fn num_entries(&self) -> usize {
+    self.entries().len()
+  }
+
+  /// Returns unique entries of the dictionary in the order they were first seen, i.e.
+  /// entry at position `i` is encoded with index `i`.
+  pub fn entries(&self) -> &[T::T] {
+    match self.shared_dict {
+      Some(ref dict) => dict.entries(),
+      None => self.uniques.data()
+    }
+  }
+
+  /// Returns the first and the last value buffered since the last call to
+  /// `write_indices()`, or `None` if no values have been buffered. Values are looked up
+  /// by buffered indices, so nothing is tracked while values are encoded.
+  pub fn first_last(&self) -> Option<(T::T, T::T)> {
+    let indices = self.buffered_indices.data();
+    match (indices.first(), indices.last()) {
+      (Some(&first), Some(&last)) => {
+        let entries = self.entries();
+        Some((entries[first as usize].clone(), entries[last as usize].clone()))
+      },
+      _ => None
+    }
+  }
+
+  /// Returns number of bytes needed to encode the dictionary entries.
+  pub fn dict_encoded_size(&self) -> u64 {
+    match self.shared_dict {
+      // Shared dictionary is PLAIN encoded the same way as own entries
+      Some(_) => self.estimated_dict_page_size() as u64,
+      None => self.dict_encoded_size
+    }
+  }
+
+  /// Returns size in bytes of the dictionary page that `write_dict()` would return,
+  /// i.e. size of the entries encoded with PLAIN encoding. Computed from all entries.
+  pub fn estimated_dict_page_size(&self) -> usize {
+    let entries = self.entries();
+    match self.desc.physical_type() {
+      Type::BOOLEAN => ceil(entries.len() as i64, 8) as usize,
+      // Each value is prefixed with 4 bytes of its length
+      Type::BYTE_ARRAY => {
+        entries.iter().map(|v| mem::size_of::<u32>() + v.as_bytes().len()).sum()
+      },
+      _ => entries.iter().map(|v| v.as_bytes().len()).sum()
+    }
+  }
+
+  /// Returns estimated total number of bytes written with dictionary encoding: the
+  /// dictionary page and the index page for all buffered values, including its 1-byte
+  /// bit width prefix. Can be compared with PLAIN encoded size to decide on fallback.
+  pub fn estimated_total_size(&self) -> usize {
+    self.estimated_dict_page_size() + self.estimated_data_encoded_size()
+  }
+
+  /// Marks this encoder as fallen back, e.g. when the dictionary grows too large, and
+  /// returns the split point. Values buffered so far are still dictionary encoded and
+  /// must be written out with `write_indices()`.
+  ///
+  /// Calling this method again returns the information recorded at the first call.
+  pub fn fallback(&mut self) -> FallbackInfo {
+    if self.fallback_info.is_none() {
+      self.fallback_info = Some(FallbackInfo {
+        num_dict_values: self.num_values_written + self.buffered_indices.size() as u64,
+        dict_page_written: self.dict_page_written
+      });
+    }
+    self.fallback_info.unwrap()
+  }
+
+  /// Returns fallback information if `fallback()` has been called, `None` otherwise.
+  pub fn fallback_info(&self) -> Option<FallbackInfo> {
+    self.fallback_info
+  }
+
+  /// Buffers already dictionary encoded `indices` into the current dictionary, e.g. when
+  /// transcoding dictionary encoded data, without looking up values in the hash table.
+  /// Indices are written out with `write_indices()` together with other buffered values.
+  ///
+  /// Returns an error, and buffers none of the indices, if any of them is out of range
+  /// of the dictionary.
+  pub fn put_indices(&mut self, indices: &[i32]) -> Result<()> {
+    let num_entries = self.num_entries();
+    if let Some(index) = indices.iter().find(|i| **i < 0 || **i as usize >= num_entries) {
+      return Err(general_err!(
+        "Column {}: dictionary index {} is out of range for dictionary with {} entries",
+        self.desc.path(),
+        index,
+        num_entries
+      ));
+    }
+    for index in indices {
+      self.buffered_indices.push(*index);
+    }
+    Ok(())
+  }
+
+  /// Writes out the dictionary values with PLAIN encoding in a byte buffer, and return
+  /// the result. Empty dictionary results in an empty buffer.
+  #[inline]
+  pub fn write_dict(&mut self) -> Result<ByteBufferPtr> {
+    let mut plain_encoder = PlainEncoder::<T>::new(
+      self.desc.clone(), self.mem_tracker.clone(), vec![]);
+    plain_encoder.put(self.entries())?;
+    let buffer = plain_encoder.flush_buffer()?;
+    // Only mark the page as written once it has been encoded successfully
+    self.dict_page_written = true;
+    Ok(buffer)
+  }
+
+  /// Returns true if the dictionary page has been written, either with `write_dict()`
+  /// or `take_dict_page()`.
+  pub fn dict_page_written(&self) -> bool {
+    self.dict_page_written
+  }
+
+  /// Returns the dictionary page, same as `write_dict()`, if it has not been written
+  /// yet, and `None` otherwise. After fallback the dictionary page is still needed for
+  /// the dictionary encoded values, and this ensures it is written exactly once.
+  pub fn take_dict_page(&mut self) -> Result<Option<ByteBufferPtr>> {
+    if self.dict_page_written {
+      return Ok(None);
+    }
+    self.write_dict().map(Some)
+  }
+
+  /// Clears the dictionary and buffered indices, e.g. to start a new row group, so that
+  /// the next value is encoded with index 0. The hash table keeps its current size, and
+  /// allocated memory is reused, so that the table is not grown again for a similar
+  /// number of unique values. Shared dictionary, if any, is kept.
+  pub fn reset_dictionary(&mut self) {
+    self.hash_slots.clear();
+    self.hash_slots.resize(self.hash_table_size, HASH_SLOT_EMPTY);
+    self.buffered_indices.clear();
+    self.uniques.clear();
+    self.dict_encoded_size = 0;
+    self.last_index = HASH_SLOT_EMPTY;
+    self.num_values_written = 0;
+    self.dict_page_written = false;
+    self.fallback_info = None;
+  }
+
+  /// Writes out the dictionary values with RLE encoding in a byte buffer, and return the
+  /// result.
+  ///
+  /// If no values have been buffered, the result only contains the bit width byte,
+  /// which is 0 for an empty dictionary, and decodes to zero values.
+  #[inline]
+  pub fn write_indices(&mut self) -> Result<ByteBufferPtr> {
+    if self.num_entries() == 0 {
+      // Indices can not be buffered without dictionary entries, write only bit width 0
+      // instead of running RLE encoder with bit width 0
+      assert_eq!(self.buffered_indices.size(), 0);
+      return Ok(ByteBufferPtr::new(vec![0]));
+    }
+    let bit_width = self.bit_width();
+    // TODO: the caller should allocate the buffer
+    // Encoder grows the buffer when less than two runs fit into the remaining space,
+    // reserve it on top of the encoded indices, so that the buffer does not have to grow
+    let buffer_len = 1 + 2 * RleEncoder::min_buffer_size(bit_width) +
+      RleEncoder::max_buffer_size(bit_width, self.buffered_indices.size());
+    let mut buffer: Vec<u8> = vec![0; buffer_len as usize];
+    // Write bit width in the first byte
+    buffer[0] = bit_width as u8;
+    self.mem_tracker.alloc(buffer.capacity() as i64);
+
+    let mut encoder = RleEncoder::new_from_buf(bit_width, buffer, 1)
+      .with_min_run_length(self.min_rle_run_length);
+    for index in self.buffered_indices.data() {
+      encoder.put_growing(*index as u64)?;
+    }
+    // Track memory of the grown buffer, if any
+    let grown_len = 1 + encoder.capacity() - buffer_len;
+    if grown_len > 0 {
+      self.mem_tracker.alloc(grown_len as i64);
+    }
+    self.num_values_written += self.buffered_indices.size() as u64;
+    self.buffered_indices.clear();
+    Ok(ByteBufferPtr::new(encoder.consume()?))
+  }
+
+  #[inline]
+  fn put_one(&mut self, value: &T::T) -> Result<()> {
+    if let Some(ref dict) = self.shared_dict {
+      match dict.index_of(value) {
+        Some(index) => self.buffered_indices.push(index),
+        None => return Err(general_err!(
+          "Column {}: value {:?} is not in the shared dictionary",
+          self.desc.path(),
+          value
+        ))
+      }
+      return Ok(());
+    }
+
+    // Values must also be equal bitwise, so that e.g. -0.0 after 0.0 is looked up in the
+    // hash table, which hashes bytes of the value, same as without the fast path
+    if self.last_index != HASH_SLOT_EMPTY &&
+        self.uniques[self.last_index as usize] == *value &&
+        self.uniques[self.last_index as usize].as_bytes() == value.as_bytes() {
+      self.buffered_indices.push(self.last_index);
+      return Ok(());
+    }
+
+    #[cfg(test)]
+    self.num_hash_lookups += 1;
     let mut j = (hash_util::hash(value, 0) & self.mod_bitmask) as usize;
     let mut index = self.hash_slots[j];
 
@@ -303,11 +1367,13 @@ impl<T: DataType> DictEncoder<T> {
       self.hash_slots[j] = index;
       self.add_dict_key(value.clone());
 
-      if self.uniques.size() > (self.hash_table_size as f32 * MAX_HASH_LOAD) as usize {
+      let max_num_entries = (self.hash_table_size as f32 * self.max_hash_load) as usize;
+      if self.uniques.size() > max_num_entries {
         self.double_table_size();
       }
     }
 
+    self.last_index = index;
     self.buffered_indices.push(index);
     Ok(())
   }
@@ -320,10 +1386,11 @@ impl<T: DataType> DictEncoder<T> {
 
   #[inline]
   fn bit_width(&self) -> u8 {
-    let num_entries = self.uniques.size();
+    let num_entries = self.num_entries();
+    // Single entry dictionary still uses 1 bit per index
     if num_entries == 0 { 0 }
     else if num_entries == 1 { 1 }
-    else { log2(num_entries as u64) as u8 }
+    else { bit_width_for_max(num_entries as u64 - 1) }
   }
 
   #[inline]
@@ -356,24 +1423,249 @@ impl<T: DataType> DictEncoder<T> {
   }
 }
 
+impl DictEncoder<ByteArrayType> {
+  /// Encodes byte array values from borrowed slices.
+  /// Only values that are not in the dictionary yet are copied into `ByteArray`.
+  pub fn put_slices(&mut self, values: &[&[u8]]) -> Result<()> {
+    for v in values {
+      self.put_slice(v)?
+    }
+    Ok(())
+  }
+
+  #[inline]
+  fn put_slice(&mut self, value: &[u8]) -> Result<()> {
+    if let Some(ref dict) = self.shared_dict {
+      match dict.index_of_slice(value) {
+        Some(index) => self.buffered_indices.push(index),
+        None => return Err(general_err!(
+          "Column {}: value {:?} is not in the shared dictionary",
+          self.desc.path(),
+          value
+        ))
+      }
+      return Ok(());
+    }
+
+    if self.last_index != HASH_SLOT_EMPTY &&
+        self.uniques[self.last_index as usize].data() == value {
+      self.buffered_indices.push(self.last_index);
+      return Ok(());
+    }
+
+    #[cfg(test)]
+    self.num_hash_lookups += 1;
+    let mut j = (hash_util::hash(&value, 0) & self.mod_bitmask) as usize;
+    let mut index = self.hash_slots[j];
+
+    while index != HASH_SLOT_EMPTY && self.uniques[index as usize].data() != value {
+      j += 1;
+      if j == self.hash_table_size {
+        j = 0;
+      }
+      index = self.hash_slots[j];
+    }
+
+    if index == HASH_SLOT_EMPTY {
+      index = self.uniques.size() as i32;
+      self.hash_slots[j] = index;
+      self.add_dict_key(ByteArray::from(value.to_vec()));
+
+      let max_num_entries = (self.hash_table_size as f32 * self.max_hash_load) as usize;
+      if self.uniques.size() > max_num_entries {
+        self.double_table_size();
+      }
+    }
+
+    self.last_index = index;
+    self.buffered_indices.push(index);
+    Ok(())
+  }
+}
+
+impl<T: DataType> fmt::Debug for DictEncoder<T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("DictEncoder")
+      .field("column", &self.desc.path().string())
+      .field("encoding", &self.encoding())
+      .field("num_values", &self.num_buffered_values())
+      .field("num_entries", &self.num_entries())
+      .field("shared_dict", &self.shared_dict.is_some())
+      .field("hash_table_size", &self.hash_table_size)
+      .field("fallback_info", &self.fallback_info)
+      .finish()
+  }
+}
+
 impl<T: DataType> Encoder<T> for DictEncoder<T> {
   #[inline]
   fn put(&mut self, values: &[T::T]) -> Result<()> {
+    // Check all values first, so that no values are buffered on error
+    self.validate_batch(values)?;
     for i in values {
       self.put_one(&i)?
     }
     Ok(())
   }
 
+  fn validate_batch(&self, values: &[T::T]) -> Result<()> {
+    validate_column_values::<T>(values, &self.desc)?;
+    if let Some(ref dict) = self.shared_dict {
+      for value in values {
+        if dict.index_of(value).is_none() {
+          return Err(general_err!(
+            "Column {}: value {:?} is not in the shared dictionary",
+            self.desc.path(),
+            value
+          ));
+        }
+      }
+    }
+    Ok(())
+  }
+
+  fn put_repeated(&mut self, value: T::T, count: usize) -> Result<()> {
+    if count == 0 {
+      return Ok(());
+    }
+    // Look up the value once, all copies have the same index
+    self.put_one(&value)?;
+    let index = self.buffered_indices.data()[self.buffered_indices.size() - 1];
+    for _ in 1..count {
+      self.buffered_indices.push(index);
+    }
+    Ok(())
+  }
+
   #[inline]
   fn encoding(&self) -> Encoding {
-    Encoding::PLAIN_DICTIONARY
+    self.data_page_encoding
+  }
+
+  #[inline]
+  fn num_buffered_values(&self) -> usize {
+    self.buffered_indices.size()
+  }
+
+  #[inline]
+  fn estimated_data_encoded_size(&self) -> usize {
+    // 1 byte for the bit width, followed by RLE encoded indices
+    let bit_width = self.bit_width();
+    1 + RleEncoder::max_buffer_size(bit_width, self.buffered_indices.size())
+  }
+
+  #[inline]
+  fn max_encoded_size(&self) -> usize {
+    // Estimate is already an upper bound
+    self.estimated_data_encoded_size()
   }
 
   #[inline]
   fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
     self.write_indices()
   }
+
+  fn encode_summary(&self) -> EncodeSummary {
+    EncodeSummary {
+      encoding: self.encoding(),
+      num_values: self.num_buffered_values(),
+      estimated_size: self.estimated_data_encoded_size(),
+      num_dict_entries: Some(self.num_entries())
+    }
+  }
+}
+
+/// Location of a page written by `DictionaryPageWriter`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PageLocation {
+  /// Type of the page, either DICTIONARY_PAGE or DATA_PAGE.
+  pub page_type: PageType,
+  /// Offset of the first byte of the page, relative to the position of the sink when
+  /// the writer was created.
+  pub offset: u64,
+  /// Length of the page in bytes.
+  pub len: usize,
+  /// Number of values in the page, i.e. dictionary entries for a dictionary page.
+  pub num_values: usize
+}
+
+/// Writes dictionary encoded column chunk into a sink: the dictionary page (see
+/// `DictEncoder::write_dict()`) followed by data pages with RLE encoded indices (see
+/// `DictEncoder::write_indices()`).
+///
+/// Since dictionary is only complete after all values are encoded, data pages are
+/// buffered in memory and written out after the dictionary page on `close()`.
+/// Pass `&mut W` as a sink to keep using it after the writer is closed.
+pub struct DictionaryPageWriter<T: DataType, W: Write> {
+  encoder: DictEncoder<T>,
+  sink: W,
+  // Encoded data pages that have not been written to the sink yet, together with
+  // number of values in each page.
+  data_pages: Vec<(ByteBufferPtr, usize)>
+}
+
+impl<T: DataType, W: Write> DictionaryPageWriter<T, W> {
+  /// Creates new writer that encodes values with `encoder` and writes pages to `sink`.
+  pub fn new(encoder: DictEncoder<T>, sink: W) -> Self {
+    Self {
+      encoder: encoder,
+      sink: sink,
+      data_pages: vec![]
+    }
+  }
+
+  /// Encodes `values` into the current data page.
+  pub fn put(&mut self, values: &[T::T]) -> Result<()> {
+    self.encoder.put(values)
+  }
+
+  /// Ends the current data page, so that next values are encoded into a new page.
+  /// Does nothing if no values have been added to the current page.
+  pub fn flush_data_page(&mut self) -> Result<()> {
+    let num_values = self.encoder.num_buffered_values();
+    if num_values > 0 {
+      let page = self.encoder.write_indices()?;
+      self.data_pages.push((page, num_values));
+    }
+    Ok(())
+  }
+
+  /// Ends the current data page and writes the dictionary page followed by all data
+  /// pages to the sink. Returns locations of the written pages in the order they were
+  /// written.
+  ///
+  /// Dictionary page is not written if dictionary is empty, i.e. no values were added.
+  pub fn close(mut self) -> Result<Vec<PageLocation>> {
+    self.flush_data_page()?;
+    let mut locations = vec![];
+    let mut offset = 0;
+
+    let num_entries = self.encoder.num_entries();
+    if num_entries > 0 {
+      let dict_page = self.encoder.write_dict()?;
+      self.sink.write_all(dict_page.data())?;
+      locations.push(PageLocation {
+        page_type: PageType::DICTIONARY_PAGE,
+        offset: offset,
+        len: dict_page.len(),
+        num_values: num_entries
+      });
+      offset += dict_page.len() as u64;
+    }
+
+    for &(ref page, num_values) in &self.data_pages {
+      self.sink.write_all(page.data())?;
+      locations.push(PageLocation {
+        page_type: PageType::DATA_PAGE,
+        offset: offset,
+        len: page.len(),
+        num_values: num_values
+      });
+      offset += page.len() as u64;
+    }
+    self.sink.flush()?;
+    Ok(locations)
+  }
 }
 
 // ----------------------------------------------------------------------
@@ -382,57 +1674,109 @@ impl<T: DataType> Encoder<T> for DictEncoder<T> {
 const DEFAULT_RLE_BUFFER_LEN: usize = 1024;
 
 /// RLE/Bit-Packing hybrid encoding for values.
-/// Currently is used only for data pages v2 and supports boolean types.
+/// Currently is used only for data pages v2 and supports boolean and INT32 types.
+///
+/// Booleans are encoded with bit width 1 and INT32 values with bit width 32, unless set
+/// with `with_bit_width`, e.g. to encode dictionary indices or other small non-negative
+/// values. Bit width is not stored in the encoded data, so the decoder must use the
+/// same bit width.
+///
+/// Values are encoded into a buffer of 1024 bytes, unless set with `with_buffer_len`.
+/// When the buffer is close to full, its size is doubled, so any number of values can
+/// be encoded. Buffer is reused after flush and keeps its size.
 pub struct RleValueEncoder<T: DataType> {
   // Buffer with raw values that we collect,
   // when flushing buffer they are encoded using RLE encoder
   encoder: Option<RleEncoder>,
+  // Initial size of the encoder buffer in bytes
+  buffer_len: usize,
+  bit_width: u8,
+  num_values: usize,
   _phantom: PhantomData<T>
 }
 
 impl<T: DataType> RleValueEncoder<T> {
   /// Creates new rle value encoder.
   pub fn new() -> Self {
+    Self::with_buffer_len(DEFAULT_RLE_BUFFER_LEN)
+  }
+
+  /// Creates new rle value encoder with initial buffer size of `buffer_len` bytes.
+  /// Buffer is at least as large as two runs of the RLE encoder.
+  pub fn with_buffer_len(buffer_len: usize) -> Self {
+    let bit_width = match T::get_physical_type() {
+      Type::BOOLEAN => 1,
+      _ => 32
+    };
     Self {
       encoder: None,
+      buffer_len: buffer_len,
+      bit_width: bit_width,
+      num_values: 0,
       _phantom: PhantomData
     }
   }
-}
-
-impl<T: DataType> Encoder<T> for RleValueEncoder<T> {
-  #[inline]
-  default fn put(&mut self, _values: &[T::T]) -> Result<()> {
-    panic!("RleValueEncoder only supports BoolType");
-  }
 
-  fn encoding(&self) -> Encoding {
-    Encoding::RLE
-  }
-
-  #[inline]
-  default fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
-    panic!("RleValueEncoder only supports BoolType");
+  /// Sets bit width of encoded values and returns itself. Values that do not fit into
+  /// `bit_width` bits are rejected with an error when put.
+  ///
+  /// Panics if `bit_width` is 0 or greater than 32, or if values have been put already.
+  pub fn with_bit_width(mut self, bit_width: u8) -> Self {
+    assert!(
+      bit_width > 0 && bit_width <= 32,
+      "Invalid bit width {} for RLE encoding",
+      bit_width
+    );
+    assert!(self.encoder.is_none(), "Cannot change bit width after values are put");
+    self.bit_width = bit_width;
+    self
   }
-}
 
-impl Encoder<BoolType> for RleValueEncoder<BoolType> {
   #[inline]
-  default fn put(&mut self, values: &[bool]) -> Result<()> {
+  fn put_value(&mut self, value: u64) -> Result<()> {
     if self.encoder.is_none() {
-      self.encoder = Some(RleEncoder::new(1, DEFAULT_RLE_BUFFER_LEN));
+      let buffer_len =
+        cmp::max(self.buffer_len, 2 * RleEncoder::min_buffer_size(self.bit_width));
+      self.encoder = Some(RleEncoder::new(self.bit_width, buffer_len));
     }
-    let rle_encoder = self.encoder.as_mut().unwrap();
-    for value in values {
-      if !rle_encoder.put(*value as u64)? {
-        return Err(general_err!("RLE buffer is full"));
+    self.encoder.as_mut().unwrap().put_growing(value)?;
+    self.num_values += 1;
+    Ok(())
+  }
+
+  fn put_value_repeated(&mut self, value: u64, count: usize) -> Result<()> {
+    let mut remaining = count;
+    while remaining > 0 {
+      // Once the value starts an RLE run, the rest of copies are added to the run
+      let extended = match self.encoder {
+        Some(ref mut rle_encoder) => rle_encoder.extend_run(value, remaining),
+        None => false
+      };
+      if extended {
+        self.num_values += remaining;
+        break;
       }
+      self.put_value(value)?;
+      remaining -= 1;
     }
     Ok(())
   }
 
+  // Checks that INT32 `value` fits into the bit width and returns it as unsigned value
   #[inline]
-  fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
+  fn check_int32(&self, value: i32) -> Result<u64> {
+    let v = value as u32 as u64;
+    if v >> self.bit_width != 0 {
+      return Err(general_err!(
+        "Value {} does not fit into {} bits of RLE encoding",
+        value,
+        self.bit_width
+      ));
+    }
+    Ok(v)
+  }
+
+  fn flush_internal(&mut self) -> Result<ByteBufferPtr> {
     assert!(self.encoder.is_some(), "RLE value encoder is not initialized");
     let rle_encoder = self.encoder.as_mut().unwrap();
 
@@ -450,80 +1794,410 @@ impl Encoder<BoolType> for RleValueEncoder<BoolType> {
     };
     // Reset rle encoder for the next batch
     rle_encoder.clear();
+    self.num_values = 0;
 
     Ok(ByteBufferPtr::new(encoded_data))
   }
 }
 
-// ----------------------------------------------------------------------
-// DELTA_BINARY_PACKED encoding
+impl<T: DataType> fmt::Debug for RleValueEncoder<T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("RleValueEncoder")
+      .field("encoding", &self.encoding())
+      .field("num_values", &self.num_buffered_values())
+      .field("buffer_len", &self.buffer_len)
+      .field("bit_width", &self.bit_width)
+      .finish()
+  }
+}
 
-const MAX_PAGE_HEADER_WRITER_SIZE: usize = 32;
-const MAX_BIT_WRITER_SIZE: usize = 10 * 1024 * 1024;
-const DEFAULT_BLOCK_SIZE: usize = 128;
-const DEFAULT_NUM_MINI_BLOCKS: usize = 4;
+impl<T: DataType> Encoder<T> for RleValueEncoder<T> {
+  #[inline]
+  default fn put(&mut self, _values: &[T::T]) -> Result<()> {
+    panic!("RleValueEncoder only supports BoolType and Int32Type");
+  }
 
-/// Delta bit packed encoder.
-/// Consists of a header followed by blocks of delta encoded values binary packed.
-///
-/// Delta-binary-packing:
-/// ```shell
-///   [page-header] [block 1], [block 2], ... [block N]
-/// ```
-///
-/// Each page header consists of:
-/// ```shell
-///   [block size] [number of miniblocks in a block] [total value count] [first value]
-/// ```
-///
-/// Each block consists of:
-/// ```shell
-///   [min delta] [list of bitwidths of miniblocks] [miniblocks]
-/// ```
-///
-/// Current implementation writes values in `put` method, multiple calls to `put` to
-/// existing block or start new block if block size is exceeded. Calling `flush_buffer`
-/// writes out all data and resets internal state, including page header.
-///
-/// Supports only INT32 and INT64.
-pub struct DeltaBitPackEncoder<T: DataType> {
-  page_header_writer: BitWriter,
-  bit_writer: BitWriter,
-  total_values: usize,
-  first_value: i64,
-  current_value: i64,
-  block_size: usize,
-  mini_block_size: usize,
-  num_mini_blocks: usize,
-  values_in_block: usize,
-  deltas: Vec<i64>,
-  _phantom: PhantomData<T>
-}
+  #[inline]
+  default fn put_repeated(&mut self, _value: T::T, _count: usize) -> Result<()> {
+    panic!("RleValueEncoder only supports BoolType and Int32Type");
+  }
 
-impl<T: DataType> DeltaBitPackEncoder<T> {
-  /// Creates new delta bit packed encoder.
-  pub fn new() -> Self {
-    let block_size = DEFAULT_BLOCK_SIZE;
-    let num_mini_blocks = DEFAULT_NUM_MINI_BLOCKS;
-    let mini_block_size = block_size / num_mini_blocks;
-    assert!(mini_block_size % 8 == 0);
-    Self::assert_supported_type();
+  fn encoding(&self) -> Encoding {
+    Encoding::RLE
+  }
 
-    DeltaBitPackEncoder {
-      page_header_writer: BitWriter::new(MAX_PAGE_HEADER_WRITER_SIZE),
-      bit_writer: BitWriter::new(MAX_BIT_WRITER_SIZE),
-      total_values: 0,
-      first_value: 0,
-      current_value: 0, // current value to keep adding deltas
-      block_size: block_size, // can write fewer values than block size for last block
+  fn num_buffered_values(&self) -> usize {
+    self.num_values
+  }
+
+  fn estimated_data_encoded_size(&self) -> usize {
+    match self.encoder {
+      // 4 bytes for the length of encoded data, followed by RLE encoded values
+      Some(ref rle_encoder) => mem::size_of::<i32>() + rle_encoder.len(),
+      None => 0
+    }
+  }
+
+  fn max_encoded_size(&self) -> usize {
+    match self.encoder {
+      Some(ref rle_encoder) => mem::size_of::<i32>() + rle_encoder.max_flushed_len(),
+      None => mem::size_of::<i32>()
+    }
+  }
+
+  #[inline]
+  default fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
+    panic!("RleValueEncoder only supports BoolType and Int32Type");
+  }
+}
+
+impl Encoder<BoolType> for RleValueEncoder<BoolType> {
+  #[inline]
+  fn put(&mut self, values: &[bool]) -> Result<()> {
+    for value in values {
+      self.put_value(*value as u64)?;
+    }
+    Ok(())
+  }
+
+  fn put_repeated(&mut self, value: bool, count: usize) -> Result<()> {
+    self.put_value_repeated(value as u64, count)
+  }
+
+  #[inline]
+  fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
+    self.flush_internal()
+  }
+}
+
+impl Encoder<Int32Type> for RleValueEncoder<Int32Type> {
+  #[inline]
+  fn put(&mut self, values: &[i32]) -> Result<()> {
+    for value in values {
+      let v = self.check_int32(*value)?;
+      self.put_value(v)?;
+    }
+    Ok(())
+  }
+
+  fn put_repeated(&mut self, value: i32, count: usize) -> Result<()> {
+    let v = self.check_int32(value)?;
+    self.put_value_repeated(v, count)
+  }
+
+  #[inline]
+  fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
+    self.flush_internal()
+  }
+}
+
+// ----------------------------------------------------------------------
+// BIT_PACKED encoding
+
+const DEFAULT_BIT_PACKED_BUFFER_LEN: usize = 1024;
+
+/// Bit-packed encoding for values, deprecated in favour of the RLE/Bit-Packing hybrid
+/// encoding, but still used by older writers. Supports boolean and INT32 types.
+///
+/// Each value is packed with `bit_width` bits from the most significant bit to the
+/// least significant bit, as defined by the Parquet specification, e.g. values 0 to 7
+/// with bit width 3 are encoded as `0x05 0x39 0x77`. Note that this is the opposite of
+/// the bit order of the RLE/Bit-Packing hybrid encoding. There is no header, so values
+/// must be decoded with the same bit width.
+/// `get_encoder()` uses 1 bit for booleans. Bit width of INT32 values depends on their
+/// range and is not known from the column, so INT32 encoder is created with `new()`.
+pub struct BitPackedEncoder<T: DataType> {
+  bit_width: u8,
+  buffer: Vec<u8>,
+  // Bits of the last byte that is not complete yet, in the lowest `num_pending_bits`
+  pending: u64,
+  num_pending_bits: usize,
+  num_values: usize,
+  _phantom: PhantomData<T>
+}
+
+impl<T: DataType> BitPackedEncoder<T> {
+  /// Creates new bit packed encoder that packs values with `bit_width` bits.
+  ///
+  /// Panics if `bit_width` is greater than 32.
+  pub fn new(bit_width: u8) -> Self {
+    assert!(bit_width <= 32, "Invalid bit width {} for BIT_PACKED encoding", bit_width);
+    Self {
+      bit_width: bit_width,
+      buffer: Vec::with_capacity(DEFAULT_BIT_PACKED_BUFFER_LEN),
+      pending: 0,
+      num_pending_bits: 0,
+      num_values: 0,
+      _phantom: PhantomData
+    }
+  }
+
+  #[inline]
+  fn put_value(&mut self, value: u64) {
+    self.pending = (self.pending << self.bit_width) | value;
+    self.num_pending_bits += self.bit_width as usize;
+    while self.num_pending_bits >= 8 {
+      self.num_pending_bits -= 8;
+      let byte = (self.pending >> self.num_pending_bits) as u8;
+      self.buffer.push(byte);
+    }
+    self.pending &= (1 << self.num_pending_bits) - 1;
+    self.num_values += 1;
+  }
+}
+
+impl<T: DataType> fmt::Debug for BitPackedEncoder<T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("BitPackedEncoder")
+      .field("encoding", &self.encoding())
+      .field("num_values", &self.num_buffered_values())
+      .field("bit_width", &self.bit_width)
+      .finish()
+  }
+}
+
+impl<T: DataType> Encoder<T> for BitPackedEncoder<T> {
+  #[inline]
+  default fn put(&mut self, _values: &[T::T]) -> Result<()> {
+    panic!("BitPackedEncoder only supports BoolType and Int32Type");
+  }
+
+  fn encoding(&self) -> Encoding {
+    Encoding::BIT_PACKED
+  }
+
+  fn num_buffered_values(&self) -> usize {
+    self.num_values
+  }
+
+  fn estimated_data_encoded_size(&self) -> usize {
+    self.buffer.len() + (self.num_pending_bits > 0) as usize
+  }
+
+  fn max_encoded_size(&self) -> usize {
+    // Size of bit-packed values is exact
+    self.estimated_data_encoded_size()
+  }
+
+  fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
+    if self.num_pending_bits > 0 {
+      // Pad the last byte with zeros
+      let byte = (self.pending << (8 - self.num_pending_bits)) as u8;
+      self.buffer.push(byte);
+    }
+    let encoded_data =
+      mem::replace(&mut self.buffer, Vec::with_capacity(DEFAULT_BIT_PACKED_BUFFER_LEN));
+    self.pending = 0;
+    self.num_pending_bits = 0;
+    self.num_values = 0;
+    Ok(ByteBufferPtr::new(encoded_data))
+  }
+}
+
+impl Encoder<BoolType> for BitPackedEncoder<BoolType> {
+  #[inline]
+  fn put(&mut self, values: &[bool]) -> Result<()> {
+    if self.bit_width == 0 {
+      return Err(general_err!("Cannot encode boolean values with bit width 0"));
+    }
+    for value in values {
+      self.put_value(*value as u64);
+    }
+    Ok(())
+  }
+}
+
+impl Encoder<Int32Type> for BitPackedEncoder<Int32Type> {
+  #[inline]
+  fn put(&mut self, values: &[i32]) -> Result<()> {
+    for value in values {
+      let v = *value as u32 as u64;
+      if v >> self.bit_width != 0 {
+        return Err(general_err!(
+          "Value {} does not fit into {} bits of BIT_PACKED encoding",
+          value,
+          self.bit_width
+        ));
+      }
+      self.put_value(v);
+    }
+    Ok(())
+  }
+}
+
+// ----------------------------------------------------------------------
+// DELTA_BINARY_PACKED encoding
+
+const MAX_PAGE_HEADER_WRITER_SIZE: usize = 32;
+const MAX_BIT_WRITER_SIZE: usize = 10 * 1024 * 1024;
+const DEFAULT_BLOCK_SIZE: usize = 128;
+const DEFAULT_NUM_MINI_BLOCKS: usize = 4;
+
+/// Pool of bit writers for delta bit packed encoders, see
+/// `DeltaBitPackEncoder::with_pool()`. Each bit writer takes 10MB, so a column writer
+/// that creates an encoder per page can reuse the same buffers through the pool
+/// instead of allocating new ones.
+///
+/// Memory of bit writers allocated by the pool is tracked with the provided memory
+/// tracker until the pool is dropped.
+pub struct BitWriterPool {
+  // Bit writers that are not used by any encoder.
+  writers: RefCell<Vec<BitWriter>>,
+  // Number of bit writers allocated by this pool.
+  num_allocated: Cell<usize>,
+  mem_tracker: MemTrackerPtr
+}
+
+/// Reference counted pointer for [`BitWriterPool`].
+pub type BitWriterPoolPtr = Rc<BitWriterPool>;
+
+impl BitWriterPool {
+  /// Creates new empty pool.
+  pub fn new(mem_tracker: MemTrackerPtr) -> Self {
+    Self {
+      writers: RefCell::new(vec![]),
+      num_allocated: Cell::new(0),
+      mem_tracker: mem_tracker
+    }
+  }
+
+  /// Returns a bit writer from the pool, or a newly allocated one if the pool is empty.
+  pub fn take(&self) -> BitWriter {
+    match self.writers.borrow_mut().pop() {
+      Some(writer) => writer,
+      None => {
+        self.num_allocated.set(self.num_allocated.get() + 1);
+        self.mem_tracker.alloc(MAX_BIT_WRITER_SIZE as i64);
+        BitWriter::new(MAX_BIT_WRITER_SIZE)
+      }
+    }
+  }
+
+  /// Clears `writer` and returns it into the pool, so it can be reused.
+  pub fn put(&self, mut writer: BitWriter) {
+    writer.clear();
+    self.writers.borrow_mut().push(writer);
+  }
+
+  /// Returns number of bit writers allocated by this pool so far.
+  pub fn num_allocated(&self) -> usize {
+    self.num_allocated.get()
+  }
+
+  /// Returns number of bit writers that are in the pool and can be reused.
+  pub fn num_available(&self) -> usize {
+    self.writers.borrow().len()
+  }
+}
+
+impl Drop for BitWriterPool {
+  fn drop(&mut self) {
+    self.mem_tracker.alloc(-((self.num_allocated.get() * MAX_BIT_WRITER_SIZE) as i64));
+  }
+}
+
+/// Delta bit packed encoder.
+/// Consists of a header followed by blocks of delta encoded values binary packed.
+///
+/// Delta-binary-packing:
+/// ```shell
+///   [page-header] [block 1], [block 2], ... [block N]
+/// ```
+///
+/// Each page header consists of:
+/// ```shell
+///   [block size] [number of miniblocks in a block] [total value count] [first value]
+/// ```
+///
+/// Each block consists of:
+/// ```shell
+///   [min delta] [list of bitwidths of miniblocks] [miniblocks]
+/// ```
+///
+/// Current implementation writes values in `put` method, multiple calls to `put` to
+/// existing block or start new block if block size is exceeded. Calling `flush_buffer`
+/// writes out all data and resets internal state, including page header.
+///
+/// Supports only INT32 and INT64.
+pub struct DeltaBitPackEncoder<T: DataType> {
+  page_header_writer: BitWriter,
+  bit_writer: BitWriter,
+  total_values: usize,
+  first_value: i64,
+  current_value: i64,
+  block_size: usize,
+  mini_block_size: usize,
+  num_mini_blocks: usize,
+  values_in_block: usize,
+  deltas: Vec<i64>,
+  // Scratch buffer for the bit-packed deltas of a mini block, reused across blocks
+  packed_values: Vec<u64>,
+  // If true, values are expected to be non-decreasing, see `assume_monotonic`
+  monotonic: bool,
+  // Min delta of the current block, only maintained in monotonic mode
+  block_min_delta: i64,
+  // Pool to take `bit_writer` from and return it to on flush, see `with_pool`
+  pool: Option<BitWriterPoolPtr>,
+  _phantom: PhantomData<T>
+}
+
+impl<T: DataType> DeltaBitPackEncoder<T> {
+  /// Creates new delta bit packed encoder.
+  pub fn new() -> Self {
+    let mut encoder = Self::new_without_bit_writer();
+    encoder.bit_writer = BitWriter::new(MAX_BIT_WRITER_SIZE);
+    encoder
+  }
+
+  // Creates new encoder with an empty bit writer that can not hold any values.
+  fn new_without_bit_writer() -> Self {
+    let block_size = DEFAULT_BLOCK_SIZE;
+    let num_mini_blocks = DEFAULT_NUM_MINI_BLOCKS;
+    let mini_block_size = block_size / num_mini_blocks;
+    assert!(mini_block_size % 8 == 0);
+    Self::assert_supported_type();
+
+    DeltaBitPackEncoder {
+      page_header_writer: BitWriter::new(MAX_PAGE_HEADER_WRITER_SIZE),
+      bit_writer: BitWriter::new(0),
+      total_values: 0,
+      first_value: 0,
+      current_value: 0, // current value to keep adding deltas
+      block_size: block_size, // can write fewer values than block size for last block
       mini_block_size: mini_block_size,
       num_mini_blocks: num_mini_blocks,
       values_in_block: 0, // will be at most block_size
       deltas: vec![0; block_size],
+      packed_values: Vec::with_capacity(mini_block_size),
+      monotonic: false,
+      block_min_delta: i64::max_value(),
+      pool: None,
       _phantom: PhantomData
     }
   }
 
+  /// Creates new delta bit packed encoder that takes its bit writer from `pool` when
+  /// values are put, and returns it into the pool on `flush_buffer` or when the encoder
+  /// is dropped, instead of allocating its own bit writer. Output is identical to the
+  /// encoder created with `new()`.
+  pub fn with_pool(pool: BitWriterPoolPtr) -> Self {
+    let mut encoder = Self::new_without_bit_writer();
+    encoder.pool = Some(pool);
+    encoder
+  }
+
+  /// Sets whether values are known to be non-decreasing, e.g. row ids or timestamps.
+  ///
+  /// This is an optimization only and does not change the format: output is identical
+  /// to the default mode. When enabled, min delta of a block is maintained while putting
+  /// values instead of scanning all deltas on flush, and `put` returns an error if a
+  /// value is less than the previous one. Values that have been put before the failed
+  /// value remain in the encoder.
+  pub fn assume_monotonic(mut self, monotonic: bool) -> Self {
+    self.monotonic = monotonic;
+    self
+  }
+
   /// Writes page header for blocks, this method is invoked when we are done encoding
   /// values. It is also okay to encode when no values have been provided
   fn write_page_header(&mut self) {
@@ -547,12 +2221,19 @@ impl<T: DataType> DeltaBitPackEncoder<T> {
     }
 
     let mut min_delta = i64::max_value();
-    for i in 0..self.values_in_block {
-      min_delta = cmp::min(min_delta, self.deltas[i]);
+    if self.monotonic {
+      min_delta = self.block_min_delta;
+      self.block_min_delta = i64::max_value();
+    } else {
+      for i in 0..self.values_in_block {
+        min_delta = cmp::min(min_delta, self.deltas[i]);
+      }
     }
 
     // Write min delta
-    self.bit_writer.put_zigzag_vlq_int(min_delta);
+    if !self.bit_writer.put_zigzag_vlq_int(min_delta) {
+      return Err(general_err!("Not enough space to write min delta"));
+    }
 
     // Slice to store bit width for each mini block
     // apply unsafe allocation to avoid double mutable borrow
@@ -560,6 +2241,11 @@ impl<T: DataType> DeltaBitPackEncoder<T> {
       let tmp_slice = self.bit_writer.get_next_byte_ptr(self.num_mini_blocks)?;
       slice::from_raw_parts_mut(tmp_slice.as_ptr() as *mut u8, self.num_mini_blocks)
     };
+    // Bit writer can be reused and contain data of the previous page, bit widths of
+    // mini blocks that are not needed in the last block are always zeros
+    for width in mini_block_widths.iter_mut() {
+      *width = 0;
+    }
 
     for i in 0..self.num_mini_blocks {
       // Find how many values we need to encode - either block size or whatever values
@@ -577,18 +2263,30 @@ impl<T: DataType> DeltaBitPackEncoder<T> {
 
       // Compute bit width to store (max_delta - min_delta)
       let bit_width = num_required_bits(self.subtract_u64(max_delta, min_delta));
+      // Deltas are computed with wrapping arithmetic of `T`, so bit width can not exceed
+      // the type width, i.e. 32 bits for INT32 and 64 bits for INT64
+      debug_assert!(
+        bit_width <= mem::size_of::<T::T>() * 8,
+        "Bit width {} exceeds {} bits of the type",
+        bit_width,
+        mem::size_of::<T::T>() * 8
+      );
       mini_block_widths[i] = bit_width as u8;
 
       // Encode values in current mini block using min_delta and bit_width
+      self.packed_values.clear();
       for j in 0..n {
-        let packed_value = self.subtract_u64(
-          self.deltas[i * self.mini_block_size + j], min_delta);
-        self.bit_writer.put_value(packed_value, bit_width);
+        let delta = self.deltas[i * self.mini_block_size + j];
+        let value = self.subtract_u64(delta, min_delta);
+        self.packed_values.push(value);
+      }
+      if !bit_pack(&self.packed_values, bit_width as u8, &mut self.bit_writer) {
+        return Err(general_err!("Not enough space to write mini block values"));
       }
 
       // Pad the last block (n < mini_block_size)
-      for _ in n..self.mini_block_size {
-        self.bit_writer.put_value(0, bit_width);
+      if !self.bit_writer.put_zeros(self.mini_block_size - n, bit_width) {
+        return Err(general_err!("Not enough space to write mini block padding"));
       }
 
       self.values_in_block -= n;
@@ -603,13 +2301,33 @@ impl<T: DataType> DeltaBitPackEncoder<T> {
   }
 }
 
+impl<T: DataType> fmt::Debug for DeltaBitPackEncoder<T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("DeltaBitPackEncoder")
+      .field("encoding", &self.encoding())
+      .field("num_values", &self.num_buffered_values())
+      .field("block_size", &self.block_size)
+      .field("num_mini_blocks", &self.num_mini_blocks)
+      .field("mini_block_size", &self.mini_block_size)
+      .field("monotonic", &self.monotonic)
+      .finish()
+  }
+}
+
 // Implementation is shared between Int32Type and Int64Type,
 // see `DeltaBitPackEncoderConversion` below for specifics.
 impl<T: DataType> Encoder<T> for DeltaBitPackEncoder<T> {
   fn put(&mut self, values: &[T::T]) -> Result<()> {
+    // Empty input must not touch the state, otherwise the next non-empty `put` would
+    // not be treated as the start of the page and `first_value` would not be set
     if values.is_empty() {
       return Ok(());
     }
+    if let Some(ref pool) = self.pool {
+      if self.bit_writer.capacity() == 0 {
+        self.bit_writer = pool.take();
+      }
+    }
 
     let mut idx;
     // Define values to encode, initialize state
@@ -626,6 +2344,17 @@ impl<T: DataType> Encoder<T> for DeltaBitPackEncoder<T> {
     // Write block
     while idx < values.len() {
       let value = self.as_i64(values, idx);
+      if self.monotonic {
+        if value < self.current_value {
+          return Err(general_err!(
+            "Value {} is less than previous value {}, expected monotonic values",
+            value,
+            self.current_value
+          ));
+        }
+        let delta = self.subtract(value, self.current_value);
+        self.block_min_delta = cmp::min(self.block_min_delta, delta);
+      }
       self.deltas[self.values_in_block] = self.subtract(value, self.current_value);
       self.current_value = value;
       idx += 1;
@@ -641,6 +2370,30 @@ impl<T: DataType> Encoder<T> for DeltaBitPackEncoder<T> {
     Encoding::DELTA_BINARY_PACKED
   }
 
+  fn num_buffered_values(&self) -> usize {
+    self.total_values
+  }
+
+  fn estimated_data_encoded_size(&self) -> usize {
+    // Page header is only written on flush, so we use its upper bound. Values of the
+    // current block are not bit packed yet, therefore we count them as raw values.
+    MAX_PAGE_HEADER_WRITER_SIZE + self.bit_writer.bytes_written() +
+      self.values_in_block * mem::size_of::<T::T>()
+  }
+
+  fn max_encoded_size(&self) -> usize {
+    let mut block_len = 0;
+    if self.values_in_block > 0 {
+      // Min delta, bit widths of all mini blocks and mini blocks padded to their full
+      // size, bit width of deltas is at most the type width
+      let num_mini_blocks =
+        ceil(self.values_in_block as i64, self.mini_block_size as i64);
+      block_len = MAX_VLQ_BYTE_LEN + self.num_mini_blocks +
+        num_mini_blocks as usize * self.mini_block_size * mem::size_of::<T::T>();
+    }
+    MAX_PAGE_HEADER_WRITER_SIZE + self.bit_writer.bytes_written() + block_len
+  }
+
   fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
     // Write remaining values
     self.flush_block_values()?;
@@ -655,15 +2408,33 @@ impl<T: DataType> Encoder<T> for DeltaBitPackEncoder<T> {
     // Reset state
     self.page_header_writer.clear();
     self.bit_writer.clear();
+    if let Some(ref pool) = self.pool {
+      // Bit writer is only taken from the pool once values are put
+      if self.bit_writer.capacity() > 0 {
+        pool.put(mem::replace(&mut self.bit_writer, BitWriter::new(0)));
+      }
+    }
     self.total_values = 0;
     self.first_value = 0;
     self.current_value = 0;
     self.values_in_block = 0;
+    self.block_min_delta = i64::max_value();
 
     Ok(buffer.consume())
   }
 }
 
+impl<T: DataType> Drop for DeltaBitPackEncoder<T> {
+  fn drop(&mut self) {
+    // Encoder can be dropped with values that were never flushed, e.g. on error
+    if let Some(ref pool) = self.pool {
+      if self.bit_writer.capacity() > 0 {
+        pool.put(mem::replace(&mut self.bit_writer, BitWriter::new(0)));
+      }
+    }
+  }
+}
+
 /// Helper trait to define specific conversions and subtractions when computing deltas
 trait DeltaBitPackEncoderConversion<T: DataType> {
   // Method should panic if type is not supported, otherwise no-op
@@ -713,239 +2484,2496 @@ impl DeltaBitPackEncoderConversion<Int32Type> for DeltaBitPackEncoder<Int32Type>
     (left as i32).wrapping_sub(right as i32) as i64
   }
 
-  #[inline]
-  fn subtract_u64(&self, left: i64, right: i64) -> u64 {
-    // Conversion of i32 -> u32 -> u64 is to avoid non-zero left most bytes in int
-    // representation
-    (left as i32).wrapping_sub(right as i32) as u32 as u64
+  #[inline]
+  fn subtract_u64(&self, left: i64, right: i64) -> u64 {
+    // Conversion of i32 -> u32 -> u64 is to avoid non-zero left most bytes in int
+    // representation
+    (left as i32).wrapping_sub(right as i32) as u32 as u64
+  }
+}
+
+impl DeltaBitPackEncoderConversion<Int64Type> for DeltaBitPackEncoder<Int64Type> {
+  #[inline]
+  fn assert_supported_type() {
+    // no-op: supported type
+  }
+
+  #[inline]
+  fn as_i64(&self, values: &[i64], index: usize) -> i64 {
+    values[index]
+  }
+
+  #[inline]
+  fn subtract(&self, left: i64, right: i64) -> i64 {
+    // It is okay for values to overflow, wrapping_sub wrapping around at the boundary
+    left.wrapping_sub(right)
+  }
+
+  #[inline]
+  fn subtract_u64(&self, left: i64, right: i64) -> u64 {
+    left.wrapping_sub(right) as u64
+  }
+}
+
+// ----------------------------------------------------------------------
+// DELTA_LENGTH_BYTE_ARRAY encoding
+
+/// Encoding for byte arrays to separate the length values and the data.
+/// The lengths are encoded using DELTA_BINARY_PACKED encoding, data is
+/// stored as raw bytes.
+pub struct DeltaLengthByteArrayEncoder<T: DataType> {
+  // length encoder
+  len_encoder: DeltaBitPackEncoder<Int32Type>,
+  // byte array data
+  data: Vec<ByteArray>,
+  // number of byte arrays put since the last flush
+  num_values: usize,
+  _phantom: PhantomData<T>
+}
+
+impl<T: DataType> DeltaLengthByteArrayEncoder<T> {
+  /// Creates new delta length byte array encoder.
+  pub fn new() -> Self {
+    Self {
+      len_encoder: DeltaBitPackEncoder::new(),
+      data: vec![],
+      num_values: 0,
+      _phantom: PhantomData
+    }
+  }
+}
+
+impl<T: DataType> fmt::Debug for DeltaLengthByteArrayEncoder<T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("DeltaLengthByteArrayEncoder")
+      .field("encoding", &self.encoding())
+      .field("num_values", &self.num_buffered_values())
+      .field("len_encoder", &self.len_encoder)
+      .finish()
+  }
+}
+
+impl<T: DataType> Encoder<T> for DeltaLengthByteArrayEncoder<T> {
+  default fn put(&mut self, _values: &[T::T]) -> Result<()> {
+    panic!("DeltaLengthByteArrayEncoder only supports ByteArrayType");
+  }
+
+  fn encoding(&self) -> Encoding {
+    Encoding::DELTA_LENGTH_BYTE_ARRAY
+  }
+
+  fn num_buffered_values(&self) -> usize {
+    self.num_values
+  }
+
+  fn estimated_data_encoded_size(&self) -> usize {
+    let data_len: usize = self.data.iter().map(|v| v.len()).sum();
+    self.len_encoder.estimated_data_encoded_size() + data_len
+  }
+
+  fn max_encoded_size(&self) -> usize {
+    let data_len: usize = self.data.iter().map(|v| v.len()).sum();
+    self.len_encoder.max_encoded_size() + data_len
+  }
+
+  default fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
+    panic!("DeltaLengthByteArrayEncoder only supports ByteArrayType");
+  }
+}
+
+impl Encoder<ByteArrayType> for DeltaLengthByteArrayEncoder<ByteArrayType> {
+  fn put(&mut self, values: &[ByteArray]) -> Result<()> {
+    let lengths: Vec<i32> =
+      values.iter().map(|byte_array| byte_array.len() as i32).collect();
+    self.len_encoder.put(&lengths)?;
+    for byte_array in values {
+      self.data.push(byte_array.clone());
+    }
+    self.num_values += values.len();
+    Ok(())
+  }
+
+  fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
+    self.flush_with_prefix(&[])
+  }
+}
+
+impl DeltaLengthByteArrayEncoder<ByteArrayType> {
+  /// Flushes encoded lengths followed by byte array data into a single buffer that
+  /// starts with `prefix`. Buffer is allocated once with the exact size, so byte array
+  /// data is copied only once.
+  fn flush_with_prefix(&mut self, prefix: &[u8]) -> Result<ByteBufferPtr> {
+    let lengths = self.len_encoder.flush_buffer()?;
+    let data_len: usize = self.data.iter().map(|v| v.len()).sum();
+    let mut total_bytes = Vec::with_capacity(prefix.len() + lengths.len() + data_len);
+    total_bytes.extend_from_slice(prefix);
+    total_bytes.extend_from_slice(lengths.data());
+    self.data.iter().for_each(|byte_array| {
+      total_bytes.extend_from_slice(byte_array.data());
+    });
+    self.data.clear();
+    self.num_values = 0;
+    Ok(ByteBufferPtr::new(total_bytes))
+  }
+}
+
+// ----------------------------------------------------------------------
+// DELTA_BYTE_ARRAY encoding
+
+/// Encoding for byte arrays, prefix lengths are encoded using DELTA_BINARY_PACKED
+/// encoding, followed by suffixes with DELTA_LENGTH_BYTE_ARRAY encoding.
+pub struct DeltaByteArrayEncoder<T: DataType> {
+  prefix_len_encoder: DeltaBitPackEncoder<Int32Type>,
+  suffix_writer: DeltaLengthByteArrayEncoder<T>,
+  previous: Vec<u8>,
+  num_values: usize,
+  // Sum of prefix lengths shared with the previous value, see `avg_prefix_len()`
+  total_prefix_len: u64,
+  _phantom: PhantomData<T>
+}
+
+impl<T: DataType> DeltaByteArrayEncoder<T> {
+  /// Creates new delta byte array encoder.
+  pub fn new() -> Self {
+    Self {
+      prefix_len_encoder: DeltaBitPackEncoder::<Int32Type>::new(),
+      suffix_writer: DeltaLengthByteArrayEncoder::<T>::new(),
+      previous: vec![],
+      num_values: 0,
+      total_prefix_len: 0,
+      _phantom: PhantomData
+    }
+  }
+
+  /// Returns average length of the prefix shared with the previous value, over values
+  /// put since the last `flush_buffer()`, or 0.0 if no values have been put. Only
+  /// suffixes are stored, so the higher the average, the more the encoding saves, e.g.
+  /// for sorted values with long common prefixes.
+  pub fn avg_prefix_len(&self) -> f32 {
+    if self.num_values == 0 {
+      return 0.0;
+    }
+    self.total_prefix_len as f32 / self.num_values as f32
+  }
+}
+
+impl<T: DataType> fmt::Debug for DeltaByteArrayEncoder<T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("DeltaByteArrayEncoder")
+      .field("encoding", &self.encoding())
+      .field("num_values", &self.num_buffered_values())
+      .field("prefix_len_encoder", &self.prefix_len_encoder)
+      .field("suffix_writer", &self.suffix_writer)
+      .finish()
+  }
+}
+
+impl<T: DataType> Encoder<T> for DeltaByteArrayEncoder<T> {
+  default fn put(&mut self, _values: &[T::T]) -> Result<()> {
+    panic!("DeltaByteArrayEncoder only supports ByteArrayType");
+  }
+
+  fn encoding(&self) -> Encoding {
+    Encoding::DELTA_BYTE_ARRAY
+  }
+
+  fn num_buffered_values(&self) -> usize {
+    self.num_values
+  }
+
+  fn estimated_data_encoded_size(&self) -> usize {
+    self.prefix_len_encoder.estimated_data_encoded_size() +
+      self.suffix_writer.estimated_data_encoded_size()
+  }
+
+  fn max_encoded_size(&self) -> usize {
+    self.prefix_len_encoder.max_encoded_size() + self.suffix_writer.max_encoded_size()
+  }
+
+  default fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
+    panic!("DeltaByteArrayEncoder only supports ByteArrayType");
+  }
+}
+
+impl Encoder<ByteArrayType> for DeltaByteArrayEncoder<ByteArrayType> {
+  fn put(&mut self, values: &[ByteArray]) -> Result<()> {
+    let mut prefix_lengths: Vec<i32> = vec![];
+    let mut suffixes: Vec<ByteArray> = vec![];
+
+    for byte_array in values {
+      let current = byte_array.data();
+      // Maximum prefix length that is shared between previous value and current value
+      let prefix_len = cmp::min(self.previous.len(), current.len());
+      let mut match_len = 0;
+      while match_len < prefix_len && self.previous[match_len] == current[match_len] {
+        match_len += 1;
+      }
+      prefix_lengths.push(match_len as i32);
+      self.total_prefix_len += match_len as u64;
+      suffixes.push(byte_array.slice(match_len, byte_array.len() - match_len));
+      // Update previous for the next prefix
+      self.previous.clear();
+      self.previous.extend_from_slice(current);
+    }
+    self.prefix_len_encoder.put(&prefix_lengths)?;
+    self.suffix_writer.put(&suffixes)?;
+    self.num_values += values.len();
+    Ok(())
+  }
+
+  fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
+    // Prefix lengths are followed by suffixes, which are written directly after the
+    // lengths instead of being flushed into a separate buffer first
+    let lengths = self.prefix_len_encoder.flush_buffer()?;
+    let total_bytes = self.suffix_writer.flush_with_prefix(lengths.data())?;
+
+    // Each flushed buffer is decoded independently, so the next prefix is computed
+    // from scratch
+    self.previous.clear();
+    self.num_values = 0;
+    self.total_prefix_len = 0;
+
+    Ok(total_bytes)
+  }
+}
+
+// ----------------------------------------------------------------------
+// Encoding comparison
+
+/// Encoder that forwards values to two encoders, so that the same values can be
+/// encoded with two encodings and the smaller result is picked, without buffering
+/// input values separately.
+///
+/// When used as `Encoder`, the first encoder is the primary one: `encoding()`,
+/// `num_buffered_values()`, `estimated_data_encoded_size()` and `flush_buffer()` report
+/// and return its state and output. `flush_buffer()` also flushes the second encoder to
+/// keep both in sync, and discards its output; use `flush_smaller()` to pick the
+/// smaller output instead.
+pub struct TeeEncoder<T: DataType> {
+  first: Box<Encoder<T>>,
+  second: Box<Encoder<T>>
+}
+
+impl<T: DataType> TeeEncoder<T> {
+  /// Creates new tee encoder for `first` and `second` encoders.
+  pub fn new(first: Box<Encoder<T>>, second: Box<Encoder<T>>) -> Self {
+    Self { first: first, second: second }
+  }
+
+  /// Returns estimated encoded sizes of the first and the second encoders.
+  pub fn estimated_data_encoded_sizes(&self) -> (usize, usize) {
+    (
+      self.first.estimated_data_encoded_size(),
+      self.second.estimated_data_encoded_size()
+    )
+  }
+
+  /// Flushes the encoder with the smaller estimated size, first encoder is picked when
+  /// sizes are equal. The other encoder is flushed as well to reset its state and its
+  /// output is discarded.
+  ///
+  /// Returns encoding and encoded values of the picked encoder.
+  pub fn flush_smaller(&mut self) -> Result<(Encoding, ByteBufferPtr)> {
+    let (first_size, second_size) = self.estimated_data_encoded_sizes();
+    let (winner, other) = if first_size <= second_size {
+      (&mut self.first, &mut self.second)
+    } else {
+      (&mut self.second, &mut self.first)
+    };
+    let buffer = winner.flush_buffer()?;
+    other.flush_buffer()?;
+    Ok((winner.encoding(), buffer))
+  }
+
+  /// Returns the first and the second encoders, e.g. to flush them separately.
+  pub fn into_inner(self) -> (Box<Encoder<T>>, Box<Encoder<T>>) {
+    (self.first, self.second)
+  }
+}
+
+impl<T: DataType> Encoder<T> for TeeEncoder<T> {
+  /// Encodes `values` with both encoders. Values are validated by both encoders first,
+  /// so that a batch rejected by either encoder is not put into the other one.
+  fn put(&mut self, values: &[T::T]) -> Result<()> {
+    self.validate_batch(values)?;
+    self.first.put(values)?;
+    self.second.put(values)
+  }
+
+  fn put_repeated(&mut self, value: T::T, count: usize) -> Result<()> {
+    if count > 0 {
+      self.validate_batch(&[value.clone()])?;
+    }
+    self.first.put_repeated(value.clone(), count)?;
+    self.second.put_repeated(value, count)
+  }
+
+  fn put_spaced(&mut self, values: &[T::T], valid_bits: &[u8]) -> Result<usize> {
+    // Bitmap that is too short is reported by the first encoder before any value is put
+    let num_bits = valid_bits.len() * 8;
+    let non_null: Vec<T::T> = values.iter()
+      .enumerate()
+      .filter(|&(i, _)| i < num_bits && get_array_bit(valid_bits, i))
+      .map(|(_, value)| value.clone())
+      .collect();
+    self.validate_batch(&non_null[..])?;
+    let num_values = self.first.put_spaced(values, valid_bits)?;
+    self.second.put_spaced(values, valid_bits)?;
+    Ok(num_values)
+  }
+
+  fn validate_batch(&self, values: &[T::T]) -> Result<()> {
+    self.first.validate_batch(values)?;
+    self.second.validate_batch(values)
+  }
+
+  fn encoding(&self) -> Encoding {
+    self.first.encoding()
+  }
+
+  fn num_buffered_values(&self) -> usize {
+    self.first.num_buffered_values()
+  }
+
+  fn estimated_data_encoded_size(&self) -> usize {
+    self.first.estimated_data_encoded_size()
+  }
+
+  fn max_encoded_size(&self) -> usize {
+    self.first.max_encoded_size()
+  }
+
+  fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
+    let buffer = self.first.flush_buffer()?;
+    self.second.flush_buffer()?;
+    Ok(buffer)
+  }
+
+  fn flush_into(&mut self, out: &mut [u8]) -> Result<usize> {
+    let num_bytes = self.first.flush_into(out)?;
+    self.second.flush_buffer()?;
+    Ok(num_bytes)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::super::decoding::*;
+  use super::*;
+  use schema::types::{ColumnDescriptor, ColumnPath, Type as SchemaType};
+  use std::{f32, f64, i64};
+  use std::rc::Rc;
+  use util::bit_util::BitReader;
+  use util::memory::MemTracker;
+  use util::test_common::{
+    make_col_desc, random_bools, random_bytes, random_numbers, random_numbers_range,
+    RandGen
+  };
+
+  const TEST_SET_SIZE: usize = 1024;
+
+  #[test]
+  fn test_bool() {
+    BoolType::test(Encoding::PLAIN, TEST_SET_SIZE, -1);
+    BoolType::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, -1);
+    BoolType::test(Encoding::RLE, TEST_SET_SIZE, -1);
+    BoolType::test(Encoding::BIT_PACKED, TEST_SET_SIZE, -1);
+  }
+
+  #[test]
+  fn test_rle_value_encoder_grows_buffer() {
+    // Random values are bit packed, which takes far more than the initial buffer
+    let values = random_bools(100000);
+    for buffer_len in &[0, 16, 1024] {
+      let mut encoder = RleValueEncoder::<BoolType>::with_buffer_len(*buffer_len);
+      for _ in 0..2 {
+        for chunk in values.chunks(999) {
+          encoder.put(chunk).unwrap();
+        }
+        assert_eq!(encoder.num_buffered_values(), values.len());
+        let data = encoder.flush_buffer().unwrap();
+
+        let mut decoder = RleValueDecoder::<BoolType>::new();
+        decoder.set_data(data, values.len()).unwrap();
+        let mut result = vec![false; values.len()];
+        assert_eq!(decoder.get(&mut result[..]).unwrap(), values.len());
+        assert_eq!(result, values);
+      }
+    }
+  }
+
+  #[test]
+  fn test_i32() {
+    Int32Type::test(Encoding::PLAIN, TEST_SET_SIZE, -1);
+    Int32Type::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, -1);
+    Int32Type::test(Encoding::DELTA_BINARY_PACKED, TEST_SET_SIZE, -1);
+    Int32Type::test(Encoding::RLE, TEST_SET_SIZE, -1);
+  }
+
+  #[test]
+  fn test_bit_packed_small_range() {
+    let values = vec![1, 2, 3, 4, 5, 6, 7, 0];
+    let mut encoder = BitPackedEncoder::<Int32Type>::new(3);
+    // Buffer grows beyond its initial size
+    for _ in 0..1000 {
+      encoder.put(&values[..]).unwrap();
+    }
+    assert_eq!(encoder.num_buffered_values(), 8000);
+    assert_eq!(encoder.estimated_data_encoded_size(), 3000);
+    let data = encoder.flush_buffer().unwrap();
+    assert_eq!(data.len(), 3000);
+    assert_eq!(&data.data()[..3], &[0x29, 0xCB, 0xB8]);
+
+    let mut decoder = BitPackedDecoder::<Int32Type>::new(3);
+    decoder.set_data(data, 8000).unwrap();
+    let result = decoder.decode_all().unwrap();
+    assert_eq!(&result[..8], &values[..]);
+    assert_eq!(&result[7992..], &values[..]);
+
+    // Values out of range of bit width are rejected
+    assert_eq!(
+      encoder.put(&[8]).unwrap_err(),
+      general_err!("Value 8 does not fit into 3 bits of BIT_PACKED encoding")
+    );
+    assert_eq!(
+      encoder.put(&[-1]).unwrap_err(),
+      general_err!("Value -1 does not fit into 3 bits of BIT_PACKED encoding")
+    );
+
+    // Page is too short for the number of values
+    let mut decoder = BitPackedDecoder::<Int32Type>::new(3);
+    assert_eq!(
+      decoder.set_data(ByteBufferPtr::new(vec![0x29, 0xCB]), 8).unwrap_err(),
+      eof_err!("8 values of 3 bits require 3 bytes, but page has 2 bytes")
+    );
+  }
+
+  #[test]
+  fn test_bit_packed_msb_first() {
+    // Example from the Parquet specification
+    let values: Vec<i32> = (0..8).collect();
+    let mut encoder = BitPackedEncoder::<Int32Type>::new(3);
+    encoder.put(&values[..]).unwrap();
+    let data = encoder.flush_buffer().unwrap();
+    assert_eq!(data.data(), &[0x05, 0x39, 0x77]);
+    let mut decoder = BitPackedDecoder::<Int32Type>::new(3);
+    decoder.set_data(data, values.len()).unwrap();
+    assert_eq!(decoder.decode_all().unwrap(), values);
+
+    // Last byte is padded with zeros, first value takes the highest bit
+    let values = vec![true, false, true, true, false, false, false, true, true];
+    let mut encoder = BitPackedEncoder::<BoolType>::new(1);
+    encoder.put(&values[..]).unwrap();
+    assert_eq!(encoder.estimated_data_encoded_size(), 2);
+    let data = encoder.flush_buffer().unwrap();
+    assert_eq!(data.data(), &[0b10110001, 0b10000000]);
+    let mut decoder = BitPackedDecoder::<BoolType>::new(1);
+    decoder.set_data(data, values.len()).unwrap();
+    assert_eq!(decoder.decode_all().unwrap(), values);
+  }
+
+  #[test]
+  fn test_bit_packed_round_trip() {
+    for &bit_width in &[1, 7, 17, 31, 32] {
+      let high = if bit_width == 32 { i32::max_value() } else { 1 << bit_width };
+      let mut values = vec![];
+      random_numbers_range(TEST_SET_SIZE, 0, high, &mut values);
+      let mut encoder = BitPackedEncoder::<Int32Type>::new(bit_width);
+      encoder.put(&values[..]).unwrap();
+      let data = encoder.flush_buffer().unwrap();
+      assert_eq!(data.len(), (TEST_SET_SIZE * bit_width as usize + 7) / 8);
+
+      let mut decoder = BitPackedDecoder::<Int32Type>::new(bit_width);
+      decoder.set_data(data, values.len()).unwrap();
+      assert_eq!(decoder.decode_all().unwrap(), values);
+    }
+  }
+
+  #[test]
+  fn test_get_encoder_dictionary() {
+    let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+    let values = vec![1, 2, 1, 1, 3];
+    let mut indices = vec![];
+    for &encoding in &[Encoding::PLAIN_DICTIONARY, Encoding::RLE_DICTIONARY] {
+      let mem_tracker = Rc::new(MemTracker::new());
+      let mut encoder = get_encoder::<Int32Type>(desc.clone(), encoding, mem_tracker)
+        .unwrap();
+      // Encoder reports the requested encoding
+      assert_eq!(encoder.encoding(), encoding);
+      encoder.put(&values[..]).unwrap();
+      indices.push(encoder.flush_buffer().unwrap());
+    }
+    // Indices are the same for both encodings
+    assert_eq!(indices[0].data(), indices[1].data());
+  }
+
+  #[test]
+  fn test_get_encoder_bit_packed() {
+    let mem_tracker = Rc::new(MemTracker::new());
+    let desc = make_col_desc("col", Type::INT64, LogicalType::NONE, -1, 0, 0);
+    assert_eq!(
+      get_encoder::<Int64Type>(desc, Encoding::BIT_PACKED, mem_tracker.clone())
+        .err()
+        .unwrap(),
+      nyi_err!("Encoding BIT_PACKED is not supported for type INT64")
+    );
+    let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+    assert_eq!(
+      get_encoder::<Int32Type>(desc, Encoding::BIT_PACKED, mem_tracker).err().unwrap(),
+      general_err!(
+        "Column \"col\": BIT_PACKED encoding of INT32 values requires bit width, \
+         use BitPackedEncoder::new()"
+      )
+    );
+  }
+
+  #[test]
+  fn test_i64() {
+    Int64Type::test(Encoding::PLAIN, TEST_SET_SIZE, -1);
+    Int64Type::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, -1);
+    Int64Type::test(Encoding::DELTA_BINARY_PACKED, TEST_SET_SIZE, -1);
+  }
+
+  #[test]
+  fn test_i96() {
+    Int96Type::test(Encoding::PLAIN, TEST_SET_SIZE, -1);
+    Int96Type::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, -1);
+  }
+
+  #[test]
+  fn test_float() {
+    FloatType::test(Encoding::PLAIN, TEST_SET_SIZE, -1);
+    FloatType::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, -1);
+  }
+
+  #[test]
+  fn test_double() {
+    DoubleType::test(Encoding::PLAIN, TEST_SET_SIZE, -1);
+    DoubleType::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, -1);
+  }
+
+  #[test]
+  fn test_byte_array() {
+    ByteArrayType::test(Encoding::PLAIN, TEST_SET_SIZE, -1);
+    ByteArrayType::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, -1);
+    ByteArrayType::test(Encoding::DELTA_LENGTH_BYTE_ARRAY, TEST_SET_SIZE, -1);
+    ByteArrayType::test(Encoding::DELTA_BYTE_ARRAY, TEST_SET_SIZE, -1);
+  }
+
+  #[test]
+  fn test_fixed_lenbyte_array() {
+    FixedLenByteArrayType::test(Encoding::PLAIN, TEST_SET_SIZE, 100);
+    FixedLenByteArrayType::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, 100);
+  }
+
+  #[test]
+  fn test_plain_first_last() {
+    // Values are not tracked by default
+    let mut encoder = create_test_plain_encoder::<Int32Type>();
+    encoder.put(&[1, 2]).unwrap();
+    assert_eq!(encoder.first_last(), None);
+
+    let mut encoder = create_test_plain_encoder::<Int32Type>().track_first_last(true);
+    assert_eq!(encoder.first_last(), None);
+    encoder.put(&[]).unwrap();
+    assert_eq!(encoder.first_last(), None);
+    encoder.put(&[5]).unwrap();
+    assert_eq!(encoder.first_last(), Some((5, 5)));
+    encoder.put(&[3, 9, 1]).unwrap();
+    encoder.put(&[]).unwrap();
+    encoder.put(&[4, 2]).unwrap();
+    assert_eq!(encoder.first_last(), Some((5, 2)));
+    encoder.flush_buffer().unwrap();
+    assert_eq!(encoder.first_last(), None);
+    encoder.put(&[7, 8]).unwrap();
+    assert_eq!(encoder.first_last(), Some((7, 8)));
+
+    let mut encoder = create_test_plain_encoder::<BoolType>().track_first_last(true);
+    encoder.put(&[false, true]).unwrap();
+    encoder.put(&[true, true]).unwrap();
+    assert_eq!(encoder.first_last(), Some((false, true)));
+
+    let mut encoder =
+      create_test_plain_encoder::<ByteArrayType>().track_first_last(true);
+    encoder.put(&[ByteArray::from("b"), ByteArray::from("a")]).unwrap();
+    let c: &[u8] = b"c";
+    let d: &[u8] = b"d";
+    encoder.put_slices(&[c, d]).unwrap();
+    assert_eq!(encoder.first_last(), Some((ByteArray::from("b"), ByteArray::from("d"))));
+    encoder.flush_buffer().unwrap();
+    encoder.put_slices(&[d]).unwrap();
+    encoder.put_slices(&[]).unwrap();
+    encoder.put_slices(&[c]).unwrap();
+    assert_eq!(encoder.first_last(), Some((ByteArray::from("d"), ByteArray::from("c"))));
+  }
+
+  #[test]
+  fn test_dict_first_last() {
+    let mut encoder = create_test_dict_encoder::<Int32Type>(-1);
+    assert_eq!(encoder.first_last(), None);
+    encoder.put(&[5, 3]).unwrap();
+    encoder.put(&[]).unwrap();
+    encoder.put(&[3, 9, 5]).unwrap();
+    assert_eq!(encoder.first_last(), Some((5, 5)));
+    encoder.put_indices(&[2]).unwrap();
+    assert_eq!(encoder.first_last(), Some((5, 9)));
+    encoder.write_indices().unwrap();
+    assert_eq!(encoder.first_last(), None);
+    encoder.put(&[1, 3]).unwrap();
+    assert_eq!(encoder.first_last(), Some((1, 3)));
+  }
+
+  #[test]
+  fn test_plain_put_slices() {
+    let values: Vec<&[u8]> = vec![
+      &b"parquet"[..], &b""[..], &b"rs"[..], &b"parquet"[..], &b"encoding"[..]
+    ];
+    let byte_arrays: Vec<ByteArray> =
+      values.iter().map(|v| ByteArray::from(v.to_vec())).collect();
+
+    let mut encoder = create_test_plain_encoder::<ByteArrayType>();
+    encoder.put(&byte_arrays[..]).unwrap();
+    let expected = encoder.flush_buffer().unwrap();
+
+    encoder.put_slices(&values[..]).unwrap();
+    let actual = encoder.flush_buffer().unwrap();
+
+    assert_eq!(actual.data(), expected.data());
+  }
+
+  #[test]
+  fn test_delta_byte_array_flush_bytes() {
+    let values: Vec<ByteArray> = (0..1000)
+      .map(|i| ByteArray::from(format!("value-{:06}", i * 7).as_str()))
+      .collect();
+
+    // Expected output: prefix lengths, suffix lengths and suffixes are encoded
+    // separately and concatenated
+    let mut prefix_lengths = vec![];
+    let mut suffix_lengths = vec![];
+    let mut suffixes = vec![];
+    let mut previous: &[u8] = &[];
+    for value in &values {
+      let current = value.data();
+      let prefix_len =
+        previous.iter().zip(current.iter()).take_while(|&(a, b)| a == b).count();
+      prefix_lengths.push(prefix_len as i32);
+      suffix_lengths.push((current.len() - prefix_len) as i32);
+      suffixes.extend_from_slice(&current[prefix_len..]);
+      previous = current;
+    }
+    let mut expected = vec![];
+    for lengths in &[prefix_lengths, suffix_lengths] {
+      let mut len_encoder = DeltaBitPackEncoder::<Int32Type>::new();
+      len_encoder.put(&lengths[..]).unwrap();
+      expected.extend_from_slice(len_encoder.flush_buffer().unwrap().data());
+    }
+    expected.extend_from_slice(&suffixes[..]);
+
+    let mut encoder = DeltaByteArrayEncoder::<ByteArrayType>::new();
+    encoder.put(&values[..500]).unwrap();
+    encoder.put(&values[500..]).unwrap();
+    assert_eq!(encoder.flush_buffer().unwrap().data(), &expected[..]);
+    // Encoder state is reset after flush
+    encoder.put(&values[..]).unwrap();
+    assert_eq!(encoder.flush_buffer().unwrap().data(), &expected[..]);
+
+    let mut encoder = DeltaLengthByteArrayEncoder::<ByteArrayType>::new();
+    encoder.put(&values[..]).unwrap();
+    let lengths: Vec<i32> = values.iter().map(|v| v.len() as i32).collect();
+    let mut len_encoder = DeltaBitPackEncoder::<Int32Type>::new();
+    len_encoder.put(&lengths[..]).unwrap();
+    let mut expected = len_encoder.flush_buffer().unwrap().data().to_vec();
+    values.iter().for_each(|v| expected.extend_from_slice(v.data()));
+    assert_eq!(encoder.flush_buffer().unwrap().data(), &expected[..]);
+  }
+
+  #[test]
+  fn test_delta_byte_array_avg_prefix_len() {
+    let mut encoder = DeltaByteArrayEncoder::<ByteArrayType>::new();
+    assert_eq!(encoder.avg_prefix_len(), 0.0);
+
+    // Sorted values share all but the last 3 characters with the previous value
+    let sorted: Vec<ByteArray> = (0..1000)
+      .map(|i| ByteArray::from(format!("http://example.com/path/{:03}", i).as_str()))
+      .collect();
+    encoder.put(&sorted[..]).unwrap();
+    assert!(encoder.avg_prefix_len() > 20.0, "{}", encoder.avg_prefix_len());
+    encoder.flush_buffer().unwrap();
+    assert_eq!(encoder.avg_prefix_len(), 0.0);
+
+    let random: Vec<ByteArray> =
+      (0..1000).map(|_| ByteArray::from(random_bytes(24))).collect();
+    encoder.put(&random[..]).unwrap();
+    assert!(encoder.avg_prefix_len() < 0.1, "{}", encoder.avg_prefix_len());
+  }
+
+  #[test]
+  fn test_delta_byte_array_repeated_first_value() {
+    // First value has no previous value, so it is written as a suffix, and identical
+    // second value is a full prefix match with empty suffix
+    let values = vec![ByteArray::from("parquet"), ByteArray::from("parquet")];
+    let mut encoder = DeltaByteArrayEncoder::<ByteArrayType>::new();
+    encoder.put(&values[..]).unwrap();
+    let data = encoder.flush_buffer().unwrap();
+
+    let mut prefix_len_decoder = DeltaBitPackDecoder::<Int32Type>::new();
+    prefix_len_decoder.set_data(data.all(), values.len()).unwrap();
+    let mut prefix_lengths = vec![0; values.len()];
+    prefix_len_decoder.get(&mut prefix_lengths[..]).unwrap();
+    assert_eq!(prefix_lengths, vec![0, 7]);
+
+    let mut suffix_decoder = DeltaLengthByteArrayDecoder::<ByteArrayType>::new();
+    suffix_decoder
+      .set_data(data.start_from(prefix_len_decoder.get_offset()), values.len())
+      .unwrap();
+    let mut suffixes = vec![ByteArray::new(); values.len()];
+    assert_eq!(suffix_decoder.get(&mut suffixes[..]).unwrap(), values.len());
+    assert_eq!(suffixes[0].data(), b"parquet");
+    assert!(suffixes[1].data().is_empty());
+
+    let mut decoder = DeltaByteArrayDecoder::<ByteArrayType>::new();
+    decoder.set_data(data, values.len()).unwrap();
+    let mut result = vec![ByteArray::new(); values.len()];
+    assert_eq!(decoder.get(&mut result[..]).unwrap(), values.len());
+    assert_eq!(result, values);
+  }
+
+  #[test]
+  fn test_delta_byte_array_flush_resets_previous_value() {
+    // First value of a page is not prefix encoded against the last value of the
+    // previous page, so each page can be decoded independently
+    let page1 = vec![ByteArray::from("hello"), ByteArray::from("help")];
+    let page2 = vec![ByteArray::from("help me"), ByteArray::from("helpful")];
+    let mut encoder = DeltaByteArrayEncoder::<ByteArrayType>::new();
+    encoder.put(&page1[..]).unwrap();
+    let data1 = encoder.flush_buffer().unwrap();
+    encoder.put(&page2[..]).unwrap();
+    let data2 = encoder.flush_buffer().unwrap();
+
+    let mut fresh_encoder = DeltaByteArrayEncoder::<ByteArrayType>::new();
+    fresh_encoder.put(&page2[..]).unwrap();
+    assert_eq!(data2.data(), fresh_encoder.flush_buffer().unwrap().data());
+
+    for (data, expected) in vec![(data2, page2), (data1, page1)] {
+      let mut decoder = DeltaByteArrayDecoder::<ByteArrayType>::new();
+      decoder.set_data(data, expected.len()).unwrap();
+      assert_eq!(decoder.decode_all().unwrap(), expected);
+    }
+  }
+
+  #[test]
+  fn test_plain_byte_array_reserve() {
+    let values: Vec<ByteArray> = (0..1000)
+      .map(|i| ByteArray::from(vec![(i % 256) as u8; i % 17]))
+      .collect();
+    let mut expected = vec![];
+    for v in &values {
+      expected.extend_from_slice((v.len() as u32).as_bytes());
+      expected.extend_from_slice(v.data());
+    }
+
+    let mem_tracker = Rc::new(MemTracker::new());
+    let desc = make_col_desc("col", Type::BYTE_ARRAY, LogicalType::NONE, -1, 0, 0);
+    let mut encoder =
+      PlainEncoder::<ByteArrayType>::new(desc, mem_tracker.clone(), vec![]);
+    encoder.put(&values[..]).unwrap();
+    // Buffer is allocated once with the exact size of encoded values
+    assert_eq!(encoder.buffer.capacity(), expected.len());
+    assert_eq!(mem_tracker.memory_usage(), expected.len() as i64);
+    assert_eq!(encoder.flush_buffer().unwrap().data(), &expected[..]);
+
+    let slices: Vec<&[u8]> = values.iter().map(|v| v.data()).collect();
+    encoder.put_slices(&slices[..]).unwrap();
+    assert_eq!(encoder.buffer.capacity(), expected.len());
+    assert_eq!(encoder.flush_buffer().unwrap().data(), &expected[..]);
+  }
+
+  #[test]
+  fn test_dict_put_slices() {
+    let values: Vec<&[u8]> = vec![
+      &b"a"[..], &b"bc"[..], &b"a"[..], &b""[..], &b"def"[..], &b"bc"[..], &b"a"[..]
+    ];
+    let byte_arrays: Vec<ByteArray> =
+      values.iter().map(|v| ByteArray::from(v.to_vec())).collect();
+
+    let mut expected_encoder = create_test_dict_encoder::<ByteArrayType>(-1);
+    expected_encoder.put(&byte_arrays[..]).unwrap();
+
+    let mut encoder = create_test_dict_encoder::<ByteArrayType>(-1);
+    encoder.put_slices(&values[..]).unwrap();
+
+    assert_eq!(encoder.num_entries(), 4);
+    assert_eq!(encoder.num_entries(), expected_encoder.num_entries());
+    assert_eq!(
+      encoder.write_dict().unwrap().data(),
+      expected_encoder.write_dict().unwrap().data()
+    );
+    assert_eq!(
+      encoder.flush_buffer().unwrap().data(),
+      expected_encoder.flush_buffer().unwrap().data()
+    );
+  }
+
+  #[test]
+  fn test_min_encoded_size() {
+    assert_eq!(min_encoded_size(Encoding::PLAIN, 10, Type::INT32, -1), 40);
+    assert_eq!(min_encoded_size(Encoding::PLAIN, 0, Type::INT32, -1), 0);
+    assert_eq!(min_encoded_size(Encoding::PLAIN, 10, Type::BOOLEAN, -1), 2);
+    assert_eq!(
+      min_encoded_size(Encoding::PLAIN, 3, Type::FIXED_LEN_BYTE_ARRAY, 5),
+      15
+    );
+    assert_eq!(min_encoded_size(Encoding::PLAIN, 3, Type::BYTE_ARRAY, -1), 12);
+    assert_eq!(min_encoded_size(Encoding::PLAIN_DICTIONARY, 100, Type::INT32, -1), 1);
+
+    // Header: block size (2 bytes), number of mini blocks, number of values, first value
+    assert_eq!(min_encoded_size(Encoding::DELTA_BINARY_PACKED, 0, Type::INT32, -1), 5);
+    assert_eq!(min_encoded_size(Encoding::DELTA_BINARY_PACKED, 1, Type::INT32, -1), 5);
+    // Number of values takes 2 bytes, one block of deltas
+    assert_eq!(min_encoded_size(Encoding::DELTA_BINARY_PACKED, 129, Type::INT64, -1), 11);
+
+    // Same values are encoded with the minimum size
+    for num_values in vec![0, 1, 2, 129, 1000] {
+      let mut encoder = DeltaBitPackEncoder::<Int32Type>::new();
+      encoder.put(&vec![0; num_values][..]).unwrap();
+      assert_eq!(
+        encoder.flush_buffer().unwrap().len(),
+        min_encoded_size(Encoding::DELTA_BINARY_PACKED, num_values, Type::INT32, -1)
+      );
+
+      let values = vec![ByteArray::from(""); num_values];
+      let encodings = vec![Encoding::DELTA_LENGTH_BYTE_ARRAY, Encoding::DELTA_BYTE_ARRAY];
+      for encoding in encodings {
+        let mut encoder = create_test_encoder::<ByteArrayType>(-1, encoding);
+        encoder.put(&values[..]).unwrap();
+        assert_eq!(
+          encoder.flush_buffer().unwrap().len(),
+          min_encoded_size(encoding, num_values, Type::BYTE_ARRAY, -1)
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn test_compare_stat_values() {
+    use std::cmp::Ordering::{Greater, Less};
+
+    let ty = SchemaType::primitive_type_builder("d", Type::FIXED_LEN_BYTE_ARRAY)
+      .with_logical_type(LogicalType::DECIMAL)
+      .with_length(2)
+      .with_precision(4)
+      .with_scale(1)
+      .build()
+      .unwrap();
+    let desc = ColumnDescriptor::new(Rc::new(ty), None, 0, 0, ColumnPath::new(vec![]));
+    let minus_one = ByteArray::from(vec![0xFF, 0xFF]);
+    let minus_two = ByteArray::from(vec![0xFF, 0xFE]);
+    let one = ByteArray::from(vec![0x00, 0x01]);
+    // Negative and positive decimal
+    assert_eq!(
+      compare_stat_values::<FixedLenByteArrayType>(&minus_one, &one, &desc),
+      Less
+    );
+    // Two negative decimals
+    assert_eq!(
+      compare_stat_values::<FixedLenByteArrayType>(&minus_one, &minus_two, &desc),
+      Greater
+    );
+
+    // Without DECIMAL bytes are compared lexicographically
+    let desc =
+      make_col_desc("col", Type::FIXED_LEN_BYTE_ARRAY, LogicalType::NONE, 2, 0, 0);
+    assert_eq!(
+      compare_stat_values::<FixedLenByteArrayType>(&minus_one, &one, &desc),
+      Greater
+    );
+    let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+    assert_eq!(compare_stat_values::<Int32Type>(&-1, &1, &desc), Less);
+  }
+
+  #[test]
+  fn test_boundary_order() {
+    let order = boundary_order::<Int32Type>;
+    assert_eq!(order(&[], &[]), BoundaryOrder::ASCENDING);
+    assert_eq!(order(&[5], &[1]), BoundaryOrder::ASCENDING);
+    assert_eq!(order(&[1, 1, 4], &[3, 3, 9]), BoundaryOrder::ASCENDING);
+    assert_eq!(order(&[1, 1], &[3, 3]), BoundaryOrder::ASCENDING);
+    assert_eq!(order(&[7, 4, -2], &[9, 5, 3]), BoundaryOrder::DESCENDING);
+    // Mins are ascending, but maxes are not
+    assert_eq!(order(&[1, 2, 3], &[10, 5, 6]), BoundaryOrder::UNORDERED);
+    assert_eq!(order(&[1, 5, 3], &[2, 6, 4]), BoundaryOrder::UNORDERED);
+
+    let mins = vec![ByteArray::from("a"), ByteArray::from("ab")];
+    let maxes = vec![ByteArray::from("b"), ByteArray::from("c")];
+    assert_eq!(
+      boundary_order::<ByteArrayType>(&mins[..], &maxes[..]),
+      BoundaryOrder::ASCENDING
+    );
+    let mins: Vec<ByteArray> = mins.into_iter().rev().collect();
+    let maxes: Vec<ByteArray> = maxes.into_iter().rev().collect();
+    assert_eq!(
+      boundary_order::<ByteArrayType>(&mins[..], &maxes[..]),
+      BoundaryOrder::DESCENDING
+    );
+    // NaN is greater than any other value
+    assert_eq!(
+      boundary_order::<DoubleType>(&[f64::NAN, 1.0], &[f64::NAN, 2.0]),
+      BoundaryOrder::DESCENDING
+    );
+  }
+
+  #[test]
+  fn test_encode_stat_value() {
+    let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+    assert_eq!(
+      encode_stat_value::<Int32Type>(&42, desc.clone()).unwrap(),
+      vec![42, 0, 0, 0]
+    );
+    assert_eq!(
+      encode_stat_value::<Int32Type>(&-2, desc).unwrap(),
+      vec![0xFE, 0xFF, 0xFF, 0xFF]
+    );
+
+    // No length prefix, unlike PLAIN encoded BYTE_ARRAY values
+    let desc = make_col_desc("col", Type::BYTE_ARRAY, LogicalType::NONE, -1, 0, 0);
+    let value = ByteArray::from("parquet");
+    assert_eq!(
+      encode_stat_value::<ByteArrayType>(&value, desc.clone()).unwrap(),
+      b"parquet".to_vec()
+    );
+    assert!(encode_stat_value::<ByteArrayType>(&ByteArray::from(""), desc)
+      .unwrap()
+      .is_empty());
+
+    let desc = make_col_desc("col", Type::BOOLEAN, LogicalType::NONE, -1, 0, 0);
+    assert_eq!(encode_stat_value::<BoolType>(&true, desc.clone()).unwrap(), vec![1]);
+    assert_eq!(encode_stat_value::<BoolType>(&false, desc).unwrap(), vec![0]);
+
+    let desc =
+      make_col_desc("col", Type::FIXED_LEN_BYTE_ARRAY, LogicalType::NONE, 3, 0, 0);
+    let value = ByteArray::from(vec![1, 2, 3]);
+    assert_eq!(
+      encode_stat_value::<FixedLenByteArrayType>(&value, desc.clone()).unwrap(),
+      vec![1, 2, 3]
+    );
+    let value = ByteArray::from(vec![1, 2]);
+    assert_eq!(
+      encode_stat_value::<FixedLenByteArrayType>(&value, desc).unwrap_err(),
+      general_err!("Column \"col\": expected value of length 3, found 2")
+    );
+  }
+
+  #[test]
+  fn test_flush_into() {
+    let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+    let values: Vec<i32> = (0..1000).map(|i| i * 7 % 101).collect();
+    let encodings =
+      vec![Encoding::PLAIN, Encoding::PLAIN_DICTIONARY, Encoding::DELTA_BINARY_PACKED];
+    for encoding in encodings {
+      let mut encoder =
+        get_encoder::<Int32Type>(desc.clone(), encoding, Rc::new(MemTracker::new()))
+          .unwrap();
+      encoder.put(&values[..]).unwrap();
+      let max_size = encoder.max_encoded_size();
+      let expected = encoder.flush_buffer().unwrap();
+      assert!(
+        max_size >= expected.len(),
+        "{}: {} < {}",
+        encoding,
+        max_size,
+        expected.len()
+      );
+
+      encoder.put(&values[..]).unwrap();
+      let mut out = vec![0xFF; max_size + 10];
+      assert_eq!(encoder.flush_into(&mut out[..]).unwrap(), expected.len());
+      assert_eq!(&out[..expected.len()], expected.data());
+      assert_eq!(encoder.num_buffered_values(), 0);
+
+      // Max size fits, smaller buffer does not
+      encoder.put(&values[..]).unwrap();
+      let mut out = vec![0; max_size];
+      assert_eq!(encoder.flush_into(&mut out[..]).unwrap(), expected.len());
+      assert_eq!(&out[..expected.len()], expected.data());
+
+      encoder.put(&values[..]).unwrap();
+      let mut out = vec![0; max_size - 1];
+      assert_eq!(
+        encoder.flush_into(&mut out[..]).unwrap_err(),
+        general_err!(
+          "Output buffer of {} bytes is too small for up to {} encoded bytes",
+          max_size - 1,
+          max_size
+        )
+      );
+      // Values are not flushed on error
+      assert_eq!(encoder.num_buffered_values(), values.len());
+      assert_eq!(encoder.flush_buffer().unwrap().data(), expected.data());
+    }
+  }
+
+  #[test]
+  fn test_max_encoded_size() {
+    // Lengths that end with a partial group, block or run
+    let lengths = [1, 7, 9, 100, 513, 1000];
+    let values = <BoolType as RandGen<BoolType>>::gen_vec(-1, 1000);
+    check_max_encoded_size::<BoolType>(Encoding::PLAIN, &values[..], &lengths);
+    check_max_encoded_size::<BoolType>(Encoding::RLE, &values[..], &lengths);
+    let values = vec![true; 1000];
+    check_max_encoded_size::<BoolType>(Encoding::RLE, &values[..], &lengths);
+
+    // Deltas of random values take the full type width
+    let values = <Int32Type as RandGen<Int32Type>>::gen_vec(-1, 1000);
+    for &enc in &[
+      Encoding::PLAIN,
+      Encoding::PLAIN_DICTIONARY,
+      Encoding::DELTA_BINARY_PACKED
+    ] {
+      check_max_encoded_size::<Int32Type>(enc, &values[..], &lengths);
+    }
+    let values = <Int64Type as RandGen<Int64Type>>::gen_vec(-1, 1000);
+    check_max_encoded_size::<Int64Type>(
+      Encoding::DELTA_BINARY_PACKED, &values[..], &lengths);
+
+    let values = <ByteArrayType as RandGen<ByteArrayType>>::gen_vec(-1, 1000);
+    for &enc in &[
+      Encoding::PLAIN,
+      Encoding::PLAIN_DICTIONARY,
+      Encoding::DELTA_LENGTH_BYTE_ARRAY,
+      Encoding::DELTA_BYTE_ARRAY
+    ] {
+      check_max_encoded_size::<ByteArrayType>(enc, &values[..], &lengths);
+    }
+  }
+
+  fn check_max_encoded_size<T: DataType>(
+    enc: Encoding,
+    values: &[T::T],
+    lengths: &[usize]
+  ) where T: 'static {
+    let mut encoder = create_test_encoder::<T>(-1, enc);
+    for &len in lengths {
+      encoder.put(&values[..len]).unwrap();
+      let max_size = encoder.max_encoded_size();
+      let actual = encoder.flush_buffer().unwrap().len();
+      assert!(max_size >= actual, "{}, {} values: {} < {}", enc, len, max_size, actual);
+    }
+  }
+
+  #[test]
+  fn test_put_spaced() {
+    let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+    let values: Vec<i32> = (0..20).map(|i| i * 7 % 11).collect();
+    // Slots 0, 2, 4, 5, 7, 8 and 19 are valid
+    let valid_bits = vec![0b10110101, 0b00000001, 0b00001000];
+    let dense = vec![values[0], values[2], values[4], values[5], values[7], values[8],
+      values[19]];
+    let encodings =
+      vec![Encoding::PLAIN, Encoding::PLAIN_DICTIONARY, Encoding::DELTA_BINARY_PACKED];
+    for encoding in encodings {
+      let mut encoder =
+        get_encoder::<Int32Type>(desc.clone(), encoding, Rc::new(MemTracker::new()))
+          .unwrap();
+      encoder.put(&dense[..]).unwrap();
+      let expected = encoder.flush_buffer().unwrap();
+
+      assert_eq!(encoder.put_spaced(&values[..], &valid_bits[..]).unwrap(), dense.len());
+      assert_eq!(encoder.num_buffered_values(), dense.len());
+      assert_eq!(encoder.flush_buffer().unwrap().data(), expected.data());
+
+      // All slots are null
+      assert_eq!(encoder.put_spaced(&values[..], &[0, 0, 0]).unwrap(), 0);
+      assert_eq!(encoder.num_buffered_values(), 0);
+
+      assert_eq!(
+        encoder.put_spaced(&values[..], &valid_bits[..2]).unwrap_err(),
+        general_err!("Validity bitmap of 2 bytes is too short for 20 values")
+      );
+    }
+  }
+
+  #[test]
+  fn test_put_unsigned() {
+    let u32_values = vec![0, 1, i32::max_value() as u32, 1 << 31, u32::max_value(), 7];
+    let u64_values = vec![0, 1, i64::max_value() as u64, 1 << 63, u64::max_value(), 7];
+    for &encoding in &[Encoding::PLAIN, Encoding::DELTA_BINARY_PACKED] {
+      let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+      let mut encoder =
+        get_encoder::<Int32Type>(desc.clone(), encoding, Rc::new(MemTracker::new()))
+          .unwrap();
+      encoder.put_u32(&u32_values[..]).unwrap();
+      let data = encoder.flush_buffer().unwrap();
+      let mut decoder = get_decoder::<Int32Type>(desc, encoding).unwrap();
+      decoder.set_data(data, u32_values.len()).unwrap();
+      let mut result = vec![0; u32_values.len()];
+      assert_eq!(decoder.get(&mut result[..]).unwrap(), u32_values.len());
+      let result: Vec<u32> = result.into_iter().map(|v| v as u32).collect();
+      assert_eq!(result, u32_values);
+
+      let desc = make_col_desc("col", Type::INT64, LogicalType::NONE, -1, 0, 0);
+      let mut encoder =
+        get_encoder::<Int64Type>(desc.clone(), encoding, Rc::new(MemTracker::new()))
+          .unwrap();
+      encoder.put_u64(&u64_values[..]).unwrap();
+      let data = encoder.flush_buffer().unwrap();
+      let mut decoder = get_decoder::<Int64Type>(desc, encoding).unwrap();
+      decoder.set_data(data, u64_values.len()).unwrap();
+      let mut result = vec![0; u64_values.len()];
+      assert_eq!(decoder.get(&mut result[..]).unwrap(), u64_values.len());
+      let result: Vec<u64> = result.into_iter().map(|v| v as u64).collect();
+      assert_eq!(result, u64_values);
+    }
+
+    // Concrete encoders encode unsigned values, also in more than one batch
+    let u32_values: Vec<u32> = (0..1000).map(|i| u32::max_value() - i).collect();
+    let mut encoder = DeltaBitPackEncoder::<Int32Type>::new();
+    encoder.put_u32(&u32_values[..]).unwrap();
+    assert_eq!(encoder.num_buffered_values(), u32_values.len());
+    let mut decoder = DeltaBitPackDecoder::<Int32Type>::new();
+    decoder.set_data(encoder.flush_buffer().unwrap(), u32_values.len()).unwrap();
+    let result: Vec<u32> =
+      decoder.decode_all().unwrap().into_iter().map(|v| v as u32).collect();
+    assert_eq!(result, u32_values);
+
+    let u64_values: Vec<u64> = (0..1000).map(|i| (1 << 63) + i).collect();
+    let desc = make_col_desc("col", Type::INT64, LogicalType::NONE, -1, 0, 0);
+    let mem_tracker = Rc::new(MemTracker::new());
+    let mut encoder = PlainEncoder::<Int64Type>::new(desc, mem_tracker, vec![]);
+    encoder.put_u64(&u64_values[..]).unwrap();
+    let mut decoder = PlainDecoder::<Int64Type>::new(-1);
+    decoder.set_data(encoder.flush_buffer().unwrap(), u64_values.len()).unwrap();
+    let result: Vec<u64> =
+      decoder.decode_all().unwrap().into_iter().map(|v| v as u64).collect();
+    assert_eq!(result, u64_values);
+  }
+
+  #[test]
+  fn test_get_encoder_negative_fixed_len() {
+    // Schema builder rejects negative length, build the type directly as if it was
+    // read from a corrupt file
+    let ty = SchemaType::primitive_type_builder("b", Type::FIXED_LEN_BYTE_ARRAY)
+      .with_length(4)
+      .build()
+      .unwrap();
+    let basic_info = match ty {
+      SchemaType::PrimitiveType { basic_info, .. } => basic_info,
+      _ => panic!("Expected primitive type")
+    };
+    let ty = SchemaType::PrimitiveType {
+      basic_info: basic_info,
+      physical_type: Type::FIXED_LEN_BYTE_ARRAY,
+      type_length: -1,
+      scale: 0,
+      precision: 0
+    };
+    let desc = Rc::new(ColumnDescriptor::new(
+      Rc::new(ty), None, 0, 0, ColumnPath::from("b")));
+
+    for encoding in vec![Encoding::PLAIN, Encoding::PLAIN_DICTIONARY] {
+      let res = get_encoder::<FixedLenByteArrayType>(
+        desc.clone(), encoding, Rc::new(MemTracker::new()));
+      assert_eq!(
+        res.err().unwrap(),
+        general_err!("Column \"b\": invalid FIXED_LEN_BYTE_ARRAY length -1")
+      );
+    }
+
+    let desc =
+      make_col_desc("col", Type::FIXED_LEN_BYTE_ARRAY, LogicalType::NONE, 4, 0, 0);
+    assert!(get_encoder::<FixedLenByteArrayType>(
+      desc, Encoding::PLAIN, Rc::new(MemTracker::new())).is_ok());
+  }
+
+  #[test]
+  fn test_validate_batch() {
+    // Fixed length byte arrays with one value of wrong length
+    let desc =
+      make_col_desc("col", Type::FIXED_LEN_BYTE_ARRAY, LogicalType::NONE, 2, 0, 0);
+    let values = vec![ByteArray::from(vec![1, 2]), ByteArray::from(vec![3])];
+    let mut encoder = PlainEncoder::<FixedLenByteArrayType>::new(
+      desc.clone(), Rc::new(MemTracker::new()), vec![]);
+    assert!(encoder.validate_batch(&values[..1]).is_ok());
+    assert_eq!(
+      encoder.validate_batch(&values[..]).unwrap_err(),
+      general_err!("Column \"col\": expected value of length 2, found 1")
+    );
+    assert!(encoder.put(&values[..]).is_err());
+    assert_eq!(encoder.num_buffered_values(), 0);
+    assert_eq!(encoder.estimated_data_encoded_size(), 0);
+    let dict_encoder =
+      DictEncoder::<FixedLenByteArrayType>::new(desc, Rc::new(MemTracker::new()));
+    assert!(dict_encoder.validate_batch(&values[..]).is_err());
+    assert_eq!(dict_encoder.num_buffered_values(), 0);
+
+    // UTF8 byte arrays with one invalid UTF-8 value
+    let desc = make_col_desc("s", Type::BYTE_ARRAY, LogicalType::UTF8, -1, 0, 0);
+    let values = vec![ByteArray::from("parquet"), ByteArray::from(vec![0xC3, 0x28])];
+    let encoder = PlainEncoder::<ByteArrayType>::new(
+      desc.clone(), Rc::new(MemTracker::new()), vec![]);
+    assert!(encoder.validate_batch(&values[..1]).is_ok());
+    assert!(encoder.validate_batch(&values[..]).is_err());
+    assert_eq!(encoder.num_buffered_values(), 0);
+    let mut dict_encoder =
+      DictEncoder::<ByteArrayType>::new(desc, Rc::new(MemTracker::new()));
+    assert!(dict_encoder.validate_batch(&values[..]).is_err());
+    assert_eq!(dict_encoder.num_entries(), 0);
+    dict_encoder.put(&values[..1]).unwrap();
+    assert!(dict_encoder.validate_batch(&values[1..]).is_err());
+    assert_eq!(dict_encoder.num_entries(), 1);
+
+    // Non-binary columns accept any values unless checks are enabled
+    let desc = make_col_desc("col", Type::DOUBLE, LogicalType::NONE, -1, 0, 0);
+    let encoder = PlainEncoder::<DoubleType>::new(
+      desc.clone(), Rc::new(MemTracker::new()), vec![]);
+    assert!(encoder.validate_batch(&[f64::NAN, 1.0]).is_ok());
+    let encoder = PlainEncoder::<DoubleType>::new(
+      desc, Rc::new(MemTracker::new()), vec![]).reject_non_finite(true);
+    assert!(encoder.validate_batch(&[1.0, f64::NAN]).is_err());
+    assert_eq!(encoder.num_buffered_values(), 0);
+  }
+
+  #[test]
+  fn test_error_contains_column_path() {
+    let ty = SchemaType::primitive_type_builder("b", Type::FIXED_LEN_BYTE_ARRAY)
+      .with_length(4)
+      .build()
+      .unwrap();
+    let path = ColumnPath::new(vec!["a".to_string(), "b".to_string()]);
+    let desc = ColumnDescriptor::new(Rc::new(ty), None, 0, 0, path);
+    let mut encoder = PlainEncoder::<FixedLenByteArrayType>::new(
+      Rc::new(desc), Rc::new(MemTracker::new()), vec![]);
+
+    let values = vec![ByteArray::from(vec![1, 2, 3, 4]), ByteArray::from(vec![1, 2])];
+    let err = encoder.put(&values[..]).unwrap_err();
+    assert_eq!(
+      err,
+      general_err!("Column \"a.b\": expected value of length 4, found 2")
+    );
+  }
+
+  #[test]
+  fn test_plain_fixed_len_byte_array_length() {
+    let desc =
+      make_col_desc("col", Type::FIXED_LEN_BYTE_ARRAY, LogicalType::NONE, 3, 0, 0);
+    let mut encoder = PlainEncoder::<FixedLenByteArrayType>::new(
+      desc, Rc::new(MemTracker::new()), vec![]);
+
+    // Values of wrong length are rejected and no values are written
+    let values: Vec<ByteArray> =
+      vec!["abc", "de", "fgh"].into_iter().map(ByteArray::from).collect();
+    assert!(encoder.put(&values[..]).is_err());
+    assert_eq!(encoder.num_buffered_values(), 0);
+
+    let values: Vec<ByteArray> =
+      vec!["abc", "def", "ghi"].into_iter().map(ByteArray::from).collect();
+    encoder.put(&values[..]).unwrap();
+    let data = encoder.flush_buffer().unwrap();
+    assert_eq!(data.data(), b"abcdefghi");
+
+    // Decoder reads values of the type length
+    let mut decoder = PlainDecoder::<FixedLenByteArrayType>::new(3);
+    decoder.set_data(data.all(), values.len()).unwrap();
+    let mut buffer = vec![ByteArray::new(); values.len()];
+    assert_eq!(decoder.get(&mut buffer[..]).unwrap(), values.len());
+    assert_eq!(buffer, values);
+
+    // Page that is shorter than values of the type length is rejected by the decoder
+    let mut decoder = PlainDecoder::<FixedLenByteArrayType>::new(3);
+    decoder.set_data(ByteBufferPtr::new(b"abcdefgh".to_vec()), values.len()).unwrap();
+    assert!(decoder.get(&mut buffer[..]).is_err());
+    let mut decoder = PlainDecoder::<FixedLenByteArrayType>::new(3).check_width(true);
+    assert!(decoder.set_data(ByteBufferPtr::new(b"abcdefgh".to_vec()), 3).is_err());
+  }
+
+  #[test]
+  fn test_plain_bool_large() {
+    // Internal bit writer holds 256 bytes, check values around that boundary
+    for &total in &[1, 7, 8, 9, 2047, 2048, 2049, 4096, 10000] {
+      BoolType::test(Encoding::PLAIN, total, -1);
+
+      let mut encoder = create_test_plain_encoder::<BoolType>();
+      let values = <BoolType as RandGen<BoolType>>::gen_vec(-1, total);
+      encoder.put(&values[..]).unwrap();
+      let data = encoder.flush_buffer().unwrap();
+      assert_eq!(data.len(), (total + 7) / 8);
+    }
+  }
+
+  #[test]
+  fn test_delta_bit_pack_padding() {
+    // Number of deltas is not a multiple of mini block size, so the last mini block
+    // is padded with zeros
+    for &total in &[2, 9, 33, 37, 130] {
+      Int32Type::test(Encoding::DELTA_BINARY_PACKED, total, -1);
+      Int64Type::test(Encoding::DELTA_BINARY_PACKED, total, -1);
+    }
+  }
+
+  #[test]
+  fn test_delta_bit_pack_max_bit_width() {
+    // Deltas span the full range of the type via wraparound
+    let mut values = vec![];
+    for _ in 0..100 {
+      values.extend_from_slice(&[0, i32::max_value(), 0, i32::min_value(), -1]);
+    }
+    let mut encoder = DeltaBitPackEncoder::<Int32Type>::new();
+    encoder.put(&values[..]).unwrap();
+    let data = encoder.flush_buffer().unwrap();
+    let widths = delta_bit_pack_mini_block_widths(data.data());
+    assert_eq!(*widths.iter().max().unwrap(), 32);
+
+    let mut decoder = DeltaBitPackDecoder::<Int32Type>::new();
+    decoder.set_data(data, values.len()).unwrap();
+    let mut result = vec![0; values.len()];
+    assert_eq!(decoder.get(&mut result[..]).unwrap(), values.len());
+    assert_eq!(result, values);
+
+    let mut values = vec![];
+    for _ in 0..100 {
+      values.extend_from_slice(&[0, i64::max_value(), 0, i64::min_value(), -1]);
+    }
+    let mut encoder = DeltaBitPackEncoder::<Int64Type>::new();
+    encoder.put(&values[..]).unwrap();
+    let data = encoder.flush_buffer().unwrap();
+    let widths = delta_bit_pack_mini_block_widths(data.data());
+    assert_eq!(*widths.iter().max().unwrap(), 64);
+
+    let mut decoder = DeltaBitPackDecoder::<Int64Type>::new();
+    decoder.set_data(data, values.len()).unwrap();
+    let mut result = vec![0; values.len()];
+    assert_eq!(decoder.get(&mut result[..]).unwrap(), values.len());
+    assert_eq!(result, values);
+  }
+
+  // Returns bit widths of all mini blocks in delta bit packed `data`
+  fn delta_bit_pack_mini_block_widths(data: &[u8]) -> Vec<u8> {
+    let mut reader = BitReader::from(data.to_vec());
+    let block_size = reader.get_vlq_int().unwrap() as usize;
+    let num_mini_blocks = reader.get_vlq_int().unwrap() as usize;
+    let total_values = reader.get_vlq_int().unwrap() as usize;
+    reader.get_zigzag_vlq_int().unwrap();
+
+    let mini_block_size = block_size / num_mini_blocks;
+    let num_blocks = (total_values.saturating_sub(1) + block_size - 1) / block_size;
+    let mut widths = vec![];
+    for _ in 0..num_blocks {
+      reader.get_zigzag_vlq_int().unwrap();
+      let block_widths: Vec<u8> = (0..num_mini_blocks)
+        .map(|_| reader.get_aligned::<u8>(1).unwrap())
+        .collect();
+      for width in &block_widths {
+        // Skip packed deltas of the mini block
+        for _ in 0..mini_block_size * (*width as usize) / 8 {
+          reader.get_aligned::<u8>(1).unwrap();
+        }
+      }
+      widths.extend(block_widths);
+    }
+    widths
+  }
+
+  #[test]
+  fn test_delta_bit_pack_bit_writer_full() {
+    // Mini block values do not fit into the bit writer, which is reported as error
+    // instead of dropping the values
+    let values: Vec<i32> = (0..200).map(|i| if i % 2 == 0 { i32::min_value() } else { i })
+      .collect();
+    let mut encoder = DeltaBitPackEncoder::<Int32Type>::new();
+    encoder.bit_writer = BitWriter::new(16);
+    assert_eq!(
+      encoder.put(&values[..]).unwrap_err(),
+      general_err!("Not enough space to write mini block values")
+    );
+  }
+
+  #[test]
+  fn test_delta_bit_pack_monotonic() {
+    let values: Vec<i64> = (0..1000).map(|i| i * 3 + (i % 7)).collect();
+
+    let mut encoder = DeltaBitPackEncoder::<Int64Type>::new();
+    encoder.put(&values[..]).unwrap();
+    let expected = encoder.flush_buffer().unwrap();
+
+    let mut encoder = DeltaBitPackEncoder::<Int64Type>::new().assume_monotonic(true);
+    encoder.put(&values[0..500]).unwrap();
+    encoder.put(&values[500..]).unwrap();
+    let actual = encoder.flush_buffer().unwrap();
+    assert_eq!(actual.data(), expected.data());
+
+    // Encoder can be reused after flush
+    encoder.put(&values[..]).unwrap();
+    assert_eq!(encoder.flush_buffer().unwrap().data(), expected.data());
+  }
+
+  #[test]
+  fn test_delta_bit_pack_monotonic_decrease() {
+    let mut encoder = DeltaBitPackEncoder::<Int32Type>::new().assume_monotonic(true);
+    encoder.put(&[1, 2, 2, 5]).unwrap();
+    assert_eq!(
+      encoder.put(&[4]).unwrap_err(),
+      general_err!("Value 4 is less than previous value 5, expected monotonic values")
+    );
+  }
+
+  #[test]
+  fn test_delta_bit_pack_pool() {
+    let mem_tracker = Rc::new(MemTracker::new());
+    let pool = Rc::new(BitWriterPool::new(mem_tracker.clone()));
+    // Last block of each page is partial
+    let pages: Vec<Vec<i32>> = (0..10).map(|i| random_numbers(100 * i + 37)).collect();
+    let expected: Vec<Vec<u8>> = pages.iter().map(|values| {
+      let mut encoder = DeltaBitPackEncoder::<Int32Type>::new();
+      encoder.put(&values[..]).unwrap();
+      encoder.flush_buffer().unwrap().data().to_vec()
+    }).collect();
+
+    // New encoder for each page
+    for (values, bytes) in pages.iter().zip(expected.iter()) {
+      let mut encoder = DeltaBitPackEncoder::<Int32Type>::with_pool(pool.clone());
+      encoder.put(&values[..]).unwrap();
+      assert_eq!(pool.num_available(), 0);
+      assert_eq!(encoder.flush_buffer().unwrap().data(), &bytes[..]);
+      assert_eq!(pool.num_available(), 1);
+    }
+
+    // Same encoder for all pages, each page is smaller than the previous one, so the
+    // bit writer contains data of the previous page
+    let mut encoder = DeltaBitPackEncoder::<Int32Type>::with_pool(pool.clone());
+    for (values, bytes) in pages.iter().zip(expected.iter()).rev() {
+      encoder.put(&values[..]).unwrap();
+      assert_eq!(encoder.flush_buffer().unwrap().data(), &bytes[..]);
+    }
+
+    // Empty page does not take a bit writer from the pool
+    let empty_page = DeltaBitPackEncoder::<Int32Type>::new().flush_buffer().unwrap();
+    assert_eq!(encoder.flush_buffer().unwrap().data(), empty_page.data());
+    assert_eq!(pool.num_available(), 1);
+
+    // Encoder dropped without flushing returns its bit writer into the pool
+    let mut unflushed = DeltaBitPackEncoder::<Int32Type>::with_pool(pool.clone());
+    unflushed.put(&pages[0][..]).unwrap();
+    assert_eq!(pool.num_available(), 0);
+    drop(unflushed);
+    assert_eq!(pool.num_available(), 1);
+
+    assert_eq!(pool.num_allocated(), 1);
+    assert_eq!(mem_tracker.memory_usage(), MAX_BIT_WRITER_SIZE as i64);
+    drop(encoder);
+    drop(pool);
+    assert_eq!(mem_tracker.memory_usage(), 0);
+  }
+
+  #[test]
+  fn test_num_buffered_values() {
+    let values = vec![
+      ByteArray::from("parquet"),
+      ByteArray::from("parquet-rs"),
+      ByteArray::from(""),
+      ByteArray::from("rust")
+    ];
+    for &enc in &[
+      Encoding::PLAIN,
+      Encoding::PLAIN_DICTIONARY,
+      Encoding::DELTA_LENGTH_BYTE_ARRAY,
+      Encoding::DELTA_BYTE_ARRAY
+    ] {
+      let mut encoder = create_test_encoder::<ByteArrayType>(-1, enc);
+      assert_eq!(encoder.num_buffered_values(), 0);
+      encoder.put(&values[..]).unwrap();
+      assert_eq!(encoder.num_buffered_values(), 4);
+      encoder.put(&values[1..3]).unwrap();
+      assert_eq!(encoder.num_buffered_values(), 6);
+      encoder.flush_buffer().unwrap();
+      assert_eq!(encoder.num_buffered_values(), 0);
+      encoder.put(&values[0..1]).unwrap();
+      assert_eq!(encoder.num_buffered_values(), 1);
+    }
+
+    let mut encoder = create_test_encoder::<BoolType>(-1, Encoding::RLE);
+    encoder.put(&[true, false, true]).unwrap();
+    assert_eq!(encoder.num_buffered_values(), 3);
+    encoder.flush_buffer().unwrap();
+    assert_eq!(encoder.num_buffered_values(), 0);
+
+    let mut encoder = create_test_encoder::<Int32Type>(-1, Encoding::DELTA_BINARY_PACKED);
+    encoder.put(&[1, 2, 3]).unwrap();
+    encoder.put(&[4]).unwrap();
+    assert_eq!(encoder.num_buffered_values(), 4);
+    encoder.flush_buffer().unwrap();
+    assert_eq!(encoder.num_buffered_values(), 0);
+  }
+
+  #[test]
+  fn test_plain_non_finite_default() {
+    let mut encoder = create_test_plain_encoder::<FloatType>();
+    let values = vec![1.0, f32::INFINITY, f32::NEG_INFINITY, f32::NAN];
+    encoder.put(&values[..]).unwrap();
+    assert_eq!(encoder.num_buffered_values(), 4);
+
+    let mut decoder = PlainDecoder::<FloatType>::new(-1);
+    decoder.set_data(encoder.flush_buffer().unwrap(), 4).unwrap();
+    let mut result = vec![0.0; 4];
+    assert_eq!(decoder.get(&mut result[..]).unwrap(), 4);
+    assert_eq!(&result[..3], &values[..3]);
+    assert!(result[3].is_nan());
+
+    let mut encoder = create_test_plain_encoder::<DoubleType>();
+    encoder.put(&[f64::INFINITY, f64::NAN]).unwrap();
+    assert_eq!(encoder.flush_buffer().unwrap().len(), 16);
+  }
+
+  #[test]
+  fn test_plain_non_finite_strict() {
+    let mut encoder = create_test_plain_encoder::<FloatType>().reject_non_finite(false);
+    encoder.put(&[1.0, f32::NAN]).unwrap();
+    let err = encoder.put(&[2.0, f32::NEG_INFINITY]).unwrap_err();
+    assert_eq!(err, general_err!("Column \"col\": value -inf is not finite"));
+    // Rejected batch is not encoded
+    assert_eq!(encoder.num_buffered_values(), 2);
+    assert_eq!(encoder.flush_buffer().unwrap().len(), 8);
+
+    let mut encoder = create_test_plain_encoder::<DoubleType>().reject_non_finite(true);
+    encoder.put(&[1.0, f64::MAX, f64::MIN_POSITIVE]).unwrap();
+    let err = encoder.put(&[f64::INFINITY]).unwrap_err();
+    assert_eq!(err, general_err!("Column \"col\": value inf is not finite"));
+    let err = encoder.put(&[f64::NAN]).unwrap_err();
+    assert_eq!(err, general_err!("Column \"col\": value NaN is not finite"));
+    assert_eq!(encoder.num_buffered_values(), 3);
+  }
+
+  #[test]
+  fn test_estimated_data_encoded_size() {
+    let values: Vec<ByteArray> = (0..100)
+      .map(|i| ByteArray::from(format!("value-{}", i % 10).as_str()))
+      .collect();
+    for &enc in &[
+      Encoding::PLAIN,
+      Encoding::PLAIN_DICTIONARY,
+      Encoding::DELTA_LENGTH_BYTE_ARRAY,
+      Encoding::DELTA_BYTE_ARRAY
+    ] {
+      let mut encoder = create_test_encoder::<ByteArrayType>(-1, enc);
+      encoder.put(&values[..]).unwrap();
+      let estimated = encoder.estimated_data_encoded_size();
+      let actual = encoder.flush_buffer().unwrap().len();
+      if enc == Encoding::PLAIN {
+        assert_eq!(estimated, actual);
+      } else {
+        assert!(estimated >= actual, "{}: {} < {}", enc, estimated, actual);
+      }
+    }
+
+    let mut encoder = create_test_encoder::<BoolType>(-1, Encoding::PLAIN);
+    encoder.put(&[true, false, true]).unwrap();
+    assert_eq!(encoder.estimated_data_encoded_size(), 1);
+
+    let mut encoder = create_test_encoder::<Int64Type>(-1, Encoding::DELTA_BINARY_PACKED);
+    let values: Vec<i64> = (0..1000).collect();
+    encoder.put(&values[..]).unwrap();
+    let estimated = encoder.estimated_data_encoded_size();
+    let actual = encoder.flush_buffer().unwrap().len();
+    assert!(estimated >= actual, "{} < {}", estimated, actual);
+  }
+
+  #[test]
+  fn test_dict_repeated_values() {
+    let mut values = vec![];
+    for i in 0..100 {
+      for _ in 0..50 {
+        values.push(i % 7);
+      }
+    }
+    values.extend(vec![1, 2, 1, 2, 3]);
+
+    let mut encoder = create_test_dict_encoder::<Int32Type>(-1);
+    encoder.put(&values[..]).unwrap();
+    // Only the first value of each run is looked up in the hash table
+    // Last run ends with 1, so the following 1 does not need a lookup either
+    assert_eq!(encoder.num_hash_lookups, 104);
+    assert_eq!(encoder.entries(), &[0, 1, 2, 3, 4, 5, 6]);
+    let indices = encoder.write_indices().unwrap();
+
+    // Output is the same as when every value is looked up
+    let mut encoder = create_test_dict_encoder::<Int32Type>(-1);
+    for v in &values {
+      encoder.last_index = HASH_SLOT_EMPTY;
+      encoder.put(&[*v]).unwrap();
+    }
+    assert_eq!(encoder.num_hash_lookups, values.len() as u64);
+    assert_eq!(encoder.write_indices().unwrap().data(), indices.data());
+
+    let mut decoder = create_test_dict_decoder::<Int32Type>();
+    let mut dict_decoder = PlainDecoder::<Int32Type>::new(-1);
+    dict_decoder.set_data(encoder.write_dict().unwrap(), encoder.num_entries()).unwrap();
+    decoder.set_dict(Box::new(dict_decoder)).unwrap();
+    decoder.set_data(indices, values.len()).unwrap();
+    let mut result = vec![0; values.len()];
+    assert_eq!(decoder.get(&mut result[..]).unwrap(), values.len());
+    assert_eq!(result, values);
+
+    let mut encoder = create_test_dict_encoder::<ByteArrayType>(-1);
+    let a: &[u8] = b"a";
+    let b: &[u8] = b"b";
+    encoder.put_slices(&[a, a, a, b, b, a]).unwrap();
+    encoder.put(&[ByteArray::from("a"), ByteArray::from("a")]).unwrap();
+    assert_eq!(encoder.num_hash_lookups, 3);
+    assert_eq!(encoder.num_buffered_values(), 8);
   }
-}
 
-impl DeltaBitPackEncoderConversion<Int64Type> for DeltaBitPackEncoder<Int64Type> {
-  #[inline]
-  fn assert_supported_type() {
-    // no-op: supported type
+  #[test]
+  fn test_dict_repeated_values_bitwise() {
+    // Zeros of different sign are equal, NaN values are not equal to each other
+    check_dict_repeated_values::<FloatType>(&[0.0, -0.0, -0.0, 0.0, f32::NAN, f32::NAN]);
+    check_dict_repeated_values::<DoubleType>(&[0.0, -0.0, -0.0, 0.0, f64::NAN, f64::NAN]);
   }
 
-  #[inline]
-  fn as_i64(&self, values: &[i64], index: usize) -> i64 {
-    values[index]
+  // Checks that dictionary encoder with the last value fast path writes the same
+  // dictionary and indices as when every value is looked up in the hash table.
+  fn check_dict_repeated_values<T: DataType>(values: &[T::T]) {
+    let mut encoder = create_test_dict_encoder::<T>(-1);
+    encoder.put(values).unwrap();
+    let dict = encoder.write_dict().unwrap();
+    let indices = encoder.write_indices().unwrap();
+
+    let mut expected = create_test_dict_encoder::<T>(-1);
+    for v in values {
+      expected.last_index = HASH_SLOT_EMPTY;
+      expected.put(&[v.clone()]).unwrap();
+    }
+    assert_eq!(expected.num_hash_lookups, values.len() as u64);
+    assert_eq!(dict.data(), expected.write_dict().unwrap().data());
+    assert_eq!(indices.data(), expected.write_indices().unwrap().data());
   }
 
-  #[inline]
-  fn subtract(&self, left: i64, right: i64) -> i64 {
-    // It is okay for values to overflow, wrapping_sub wrapping around at the boundary
-    left.wrapping_sub(right)
+  #[test]
+  fn test_dict_encoder_with_load_factor() {
+    let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+    let values: Vec<i32> = (0..1000).chain(0..1000).collect();
+    let mut expected = create_test_dict_encoder::<Int32Type>(-1);
+    expected.put(&values[..]).unwrap();
+    // Default load factor resizes 1024 slots once 716 values are added
+    assert_eq!(expected.hash_table_size, 2048);
+    let expected_dict = expected.write_dict().unwrap();
+    let expected_indices = expected.write_indices().unwrap();
+
+    // Very high load factor does not resize the table, very low one resizes it eagerly
+    let params = vec![(0.99, 1024), (0.01, 131072)];
+    for (load_factor, table_size) in params {
+      let mut encoder = DictEncoder::<Int32Type>::with_load_factor(
+        desc.clone(), Rc::new(MemTracker::new()), load_factor);
+      encoder.put(&values[..]).unwrap();
+      assert_eq!(encoder.hash_table_size, table_size);
+      assert_eq!(encoder.write_dict().unwrap().data(), expected_dict.data());
+      assert_eq!(encoder.write_indices().unwrap().data(), expected_indices.data());
+    }
+
+    // Small number of values is enough to resize with a low load factor
+    let mut encoder = DictEncoder::<Int32Type>::with_load_factor(
+      desc, Rc::new(MemTracker::new()), 0.01);
+    encoder.put(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]).unwrap();
+    assert_eq!(encoder.hash_table_size, 1024);
+    encoder.put(&[11]).unwrap();
+    assert_eq!(encoder.hash_table_size, 2048);
   }
 
-  #[inline]
-  fn subtract_u64(&self, left: i64, right: i64) -> u64 {
-    left.wrapping_sub(right) as u64
+  #[test]
+  fn test_dict_encoder_reset_dictionary() {
+    let mem_tracker = Rc::new(MemTracker::new());
+    let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+    let mut encoder = DictEncoder::<Int32Type>::new(desc, mem_tracker.clone());
+    let values: Vec<i32> = (0..1000).collect();
+    encoder.put(&values[..]).unwrap();
+    encoder.write_dict().unwrap();
+    encoder.write_indices().unwrap();
+    assert_eq!(encoder.hash_table_size, 2048);
+    let memory_usage = mem_tracker.memory_usage();
+
+    encoder.reset_dictionary();
+    assert_eq!(encoder.num_entries(), 0);
+    assert_eq!(encoder.dict_encoded_size(), 0);
+    assert!(!encoder.dict_page_written());
+    assert_eq!(encoder.hash_table_size, 2048);
+    assert_eq!(mem_tracker.memory_usage(), memory_usage);
+
+    // Next row group starts with index 0
+    let mut expected = create_test_dict_encoder::<Int32Type>(-1);
+    expected.put(&[500, 7, 500]).unwrap();
+    encoder.put(&[500, 7, 500]).unwrap();
+    assert_eq!(encoder.entries(), &[500, 7]);
+    assert_eq!(encoder.buffered_indices.data(), &[0, 1, 0]);
+    assert_eq!(
+      encoder.write_dict().unwrap().data(),
+      expected.write_dict().unwrap().data()
+    );
+    assert_eq!(
+      encoder.write_indices().unwrap().data(),
+      expected.write_indices().unwrap().data()
+    );
+    assert_eq!(encoder.hash_table_size, 2048);
   }
-}
 
-// ----------------------------------------------------------------------
-// DELTA_LENGTH_BYTE_ARRAY encoding
+  #[test]
+  fn test_dict_encoder_with_min_rle_run_length() {
+    let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+    // Low cardinality values with runs of 10 to 30 values
+    let mut values = vec![];
+    for i in 0..200 {
+      values.extend(vec![(i % 4) as i32; 10 + (i * 7) % 21]);
+    }
+    let encode = |min_run_length: usize| {
+      let mem_tracker = Rc::new(MemTracker::new());
+      let mut encoder = DictEncoder::<Int32Type>::new(desc.clone(), mem_tracker)
+        .with_min_rle_run_length(min_run_length);
+      encoder.put(&values[..]).unwrap();
+      let indices = encoder.write_indices().unwrap();
+      let mut decoder = create_test_dict_decoder::<Int32Type>();
+      let mut dict_decoder = PlainDecoder::<Int32Type>::new(-1);
+      let dict = encoder.write_dict().unwrap();
+      dict_decoder.set_data(dict, encoder.num_entries()).unwrap();
+      decoder.set_dict(Box::new(dict_decoder)).unwrap();
+      decoder.set_data(indices.all(), values.len()).unwrap();
+      let mut result = vec![0; values.len()];
+      assert_eq!(decoder.get(&mut result[..]).unwrap(), values.len());
+      assert_eq!(result, values);
+      indices.len()
+    };
 
-/// Encoding for byte arrays to separate the length values and the data.
-/// The lengths are encoded using DELTA_BINARY_PACKED encoding, data is
-/// stored as raw bytes.
-pub struct DeltaLengthByteArrayEncoder<T: DataType> {
-  // length encoder
-  len_encoder: DeltaBitPackEncoder<Int32Type>,
-  // byte array data
-  data: Vec<ByteArray>,
-  _phantom: PhantomData<T>
-}
+    let rle_len = encode(8);
+    let bit_packed_len = encode(64);
+    assert!(rle_len < bit_packed_len, "{} >= {}", rle_len, bit_packed_len);
+    assert!(encode(MAX_MIN_RLE_RUN_LENGTH) >= bit_packed_len);
 
-impl<T: DataType> DeltaLengthByteArrayEncoder<T> {
-  /// Creates new delta length byte array encoder.
-  pub fn new() -> Self {
-    Self {
-      len_encoder: DeltaBitPackEncoder::new(),
-      data: vec![],
-      _phantom: PhantomData
-    }
+    // Setting is combined with other constructors
+    let mem_tracker = Rc::new(MemTracker::new());
+    let encoder = DictEncoder::<Int32Type>::new_labeled(
+      desc.clone(), mem_tracker, Encoding::RLE_DICTIONARY).with_min_rle_run_length(64);
+    assert_eq!(encoder.encoding(), Encoding::RLE_DICTIONARY);
+    assert_eq!(encoder.min_rle_run_length, 64);
   }
-}
 
-impl<T: DataType> Encoder<T> for DeltaLengthByteArrayEncoder<T> {
-  default fn put(&mut self, _values: &[T::T]) -> Result<()> {
-    panic!("DeltaLengthByteArrayEncoder only supports ByteArrayType");
+  #[test]
+  #[should_panic(expected = "Minimum RLE run length 4 must be at least 8")]
+  fn test_dict_encoder_invalid_min_rle_run_length() {
+    let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+    let mem_tracker = Rc::new(MemTracker::new());
+    DictEncoder::<Int32Type>::new(desc, mem_tracker).with_min_rle_run_length(4);
   }
 
-  fn encoding(&self) -> Encoding {
-    Encoding::DELTA_LENGTH_BYTE_ARRAY
+  #[test]
+  #[should_panic(expected = "Minimum RLE run length 513 must be at most 512")]
+  fn test_dict_encoder_too_large_min_rle_run_length() {
+    let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+    let mem_tracker = Rc::new(MemTracker::new());
+    DictEncoder::<Int32Type>::new(desc, mem_tracker).with_min_rle_run_length(513);
   }
 
-  default fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
-    panic!("DeltaLengthByteArrayEncoder only supports ByteArrayType");
+  #[test]
+  #[should_panic(expected = "Invalid hash table load factor 1, must be in range")]
+  fn test_dict_encoder_with_invalid_load_factor() {
+    let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+    DictEncoder::<Int32Type>::with_load_factor(desc, Rc::new(MemTracker::new()), 1.0);
   }
-}
 
-impl Encoder<ByteArrayType> for DeltaLengthByteArrayEncoder<ByteArrayType> {
-  fn put(&mut self, values: &[ByteArray]) -> Result<()> {
-    let lengths: Vec<i32> =
-      values.iter().map(|byte_array| byte_array.len() as i32).collect();
-    self.len_encoder.put(&lengths)?;
-    for byte_array in values {
-      self.data.push(byte_array.clone());
+  #[test]
+  fn test_dict_entries() {
+    let mut encoder = create_test_dict_encoder::<Int32Type>(-1);
+    assert!(encoder.entries().is_empty());
+    encoder.put(&[3, 1, 3, 2, 1, 3]).unwrap();
+    assert_eq!(encoder.entries(), &[3, 1, 2]);
+
+    // Insert enough unique values to resize hash table several times
+    let values: Vec<i32> = (0..5000).map(|i| 4999 - i).collect();
+    encoder.put(&values[..]).unwrap();
+    encoder.put(&values[..]).unwrap();
+    let mut expected = vec![3, 1, 2];
+    expected.extend(values.iter().filter(|v| **v > 3 || **v == 0));
+    assert_eq!(encoder.num_entries(), 5000);
+    assert_eq!(encoder.entries(), &expected[..]);
+
+    let mut encoder = create_test_dict_encoder::<ByteArrayType>(-1);
+    encoder.put(&[ByteArray::from("b"), ByteArray::from("a"), ByteArray::from("b")])
+      .unwrap();
+    assert_eq!(encoder.entries(), &[ByteArray::from("b"), ByteArray::from("a")]);
+  }
+
+  #[test]
+  fn test_dict_fallback_info() {
+    // Each batch adds 4 new entries (16 bytes) to the dictionary
+    let max_dict_size = 100;
+    let mut encoder = create_test_dict_encoder::<Int32Type>(-1);
+    assert_eq!(encoder.fallback_info(), None);
+    let mut dict_page_values = 0;
+    let mut batch = 0;
+    while encoder.dict_encoded_size() <= max_dict_size {
+      let values: Vec<i32> = (0..8).map(|i| batch * 4 + i % 4).collect();
+      encoder.put(&values[..]).unwrap();
+      encoder.flush_buffer().unwrap();
+      dict_page_values += values.len() as u64;
+      batch += 1;
     }
-    Ok(())
+    assert_eq!(batch, 7);
+    assert_eq!(encoder.fallback_info(), None);
+
+    let info = encoder.fallback();
+    assert_eq!(info, FallbackInfo { num_dict_values: 56, dict_page_written: false });
+    assert_eq!(info.num_dict_values, dict_page_values);
+    assert_eq!(encoder.fallback_info(), Some(info));
+
+    // Information is recorded once
+    encoder.write_dict().unwrap();
+    encoder.put(&[1, 2, 3]).unwrap();
+    assert_eq!(encoder.fallback(), info);
+    assert_eq!(encoder.fallback_info(), Some(info));
+
+    // Buffered values are dictionary encoded
+    let mut encoder = create_test_dict_encoder::<Int32Type>(-1);
+    encoder.put(&[1, 2, 3, 4]).unwrap();
+    encoder.flush_buffer().unwrap();
+    encoder.write_dict().unwrap();
+    encoder.put(&[5, 6]).unwrap();
+    let info = encoder.fallback();
+    assert_eq!(info, FallbackInfo { num_dict_values: 6, dict_page_written: true });
   }
 
-  fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
-    let mut total_bytes = vec![];
-    let lengths = self.len_encoder.flush_buffer()?;
-    total_bytes.extend_from_slice(lengths.data());
-    self.data.iter().for_each(|byte_array| {
-      total_bytes.extend_from_slice(byte_array.data());
-    });
-    self.data.clear();
-    Ok(ByteBufferPtr::new(total_bytes))
+  #[test]
+  fn test_dict_take_dict_page() {
+    let mut encoder = create_test_dict_encoder::<Int32Type>(-1);
+    encoder.put(&[1, 2, 1, 3]).unwrap();
+    let indices = encoder.flush_buffer().unwrap();
+    assert!(!encoder.dict_page_written());
+
+    // Values after fallback are encoded with another encoding, dictionary page is
+    // still written for the dictionary encoded values
+    let info = encoder.fallback();
+    assert!(!info.dict_page_written);
+    let dict_page = encoder.take_dict_page().unwrap().expect("dictionary page");
+    assert!(encoder.dict_page_written());
+    assert!(encoder.take_dict_page().unwrap().is_none());
+    assert!(encoder.take_dict_page().unwrap().is_none());
+
+    let mut dict_decoder = PlainDecoder::<Int32Type>::new(-1);
+    dict_decoder.set_data(dict_page, encoder.num_entries()).unwrap();
+    let mut decoder = create_test_dict_decoder::<Int32Type>();
+    decoder.set_dict(Box::new(dict_decoder)).unwrap();
+    decoder.set_data(indices, 4).unwrap();
+    assert_eq!(decoder.decode_all().unwrap(), vec![1, 2, 1, 3]);
+
+    // Dictionary page written directly is not returned again
+    let mut encoder = create_test_dict_encoder::<Int32Type>(-1);
+    encoder.put(&[1]).unwrap();
+    encoder.write_dict().unwrap();
+    assert!(encoder.take_dict_page().unwrap().is_none());
   }
-}
 
-// ----------------------------------------------------------------------
-// DELTA_BYTE_ARRAY encoding
+  #[test]
+  fn test_dict_write_dict_error() {
+    // Shared dictionary entry does not match the FIXED_LEN_BYTE_ARRAY length, so the
+    // dictionary page fails to encode and is not marked as written
+    let dict = SharedDictionary::<FixedLenByteArrayType>::new(
+      vec![ByteArray::from(vec![1, 2, 3]), ByteArray::from(vec![4, 5])]).unwrap();
+    let desc =
+      make_col_desc("col", Type::FIXED_LEN_BYTE_ARRAY, LogicalType::NONE, 3, 0, 0);
+    let mem_tracker = Rc::new(MemTracker::new());
+    let mut encoder = DictEncoder::with_shared_dict(desc, dict, mem_tracker);
+    assert!(encoder.write_dict().is_err());
+    assert!(!encoder.dict_page_written());
+    assert!(encoder.take_dict_page().is_err());
+    assert!(!encoder.dict_page_written());
+  }
 
-/// Encoding for byte arrays, prefix lengths are encoded using DELTA_BINARY_PACKED
-/// encoding, followed by suffixes with DELTA_LENGTH_BYTE_ARRAY encoding.
-pub struct DeltaByteArrayEncoder<T: DataType> {
-  prefix_len_encoder: DeltaBitPackEncoder<Int32Type>,
-  suffix_writer: DeltaLengthByteArrayEncoder<T>,
-  previous: Vec<u8>,
-  _phantom: PhantomData<T>
-}
+  #[test]
+  fn test_dict_shared_dictionary() {
+    let codes = vec!["US", "FR", "DE", "JP"];
+    let dict = SharedDictionary::<ByteArrayType>::new(
+      codes.iter().map(|c| ByteArray::from(*c)).collect()).unwrap();
+    let desc = make_col_desc("col", Type::BYTE_ARRAY, LogicalType::NONE, -1, 0, 0);
+    let mem_tracker = Rc::new(MemTracker::new());
+    let mut encoder1 =
+      DictEncoder::with_shared_dict(desc.clone(), dict.clone(), mem_tracker.clone());
+    let mut encoder2 = DictEncoder::with_shared_dict(desc, dict, mem_tracker);
+
+    let values1: Vec<ByteArray> =
+      vec!["JP", "US", "US", "DE"].into_iter().map(ByteArray::from).collect();
+    encoder1.put(&values1[..]).unwrap();
+    encoder2.put_slices(&[&b"FR"[..], &b"JP"[..], &b"US"[..]]).unwrap();
+    assert_eq!(encoder1.num_entries(), 4);
+    assert_eq!(encoder2.entries(), encoder1.entries());
+    assert_eq!(
+      encoder2.write_dict().unwrap().data(),
+      encoder1.write_dict().unwrap().data()
+    );
 
-impl<T: DataType> DeltaByteArrayEncoder<T> {
-  /// Creates new delta byte array encoder.
-  pub fn new() -> Self {
-    Self {
-      prefix_len_encoder: DeltaBitPackEncoder::<Int32Type>::new(),
-      suffix_writer: DeltaLengthByteArrayEncoder::<T>::new(),
-      previous: vec![],
-      _phantom: PhantomData
-    }
+    // Both encoders produce indices into the same dictionary page
+    let mut dict_decoder = PlainDecoder::<ByteArrayType>::new(-1);
+    dict_decoder.set_data(encoder1.write_dict().unwrap(), codes.len()).unwrap();
+    let mut decoder = create_test_dict_decoder::<ByteArrayType>();
+    decoder.set_dict(Box::new(dict_decoder)).unwrap();
+
+    let mut result = vec![ByteArray::new(); 4];
+    decoder.set_data(encoder1.flush_buffer().unwrap(), 4).unwrap();
+    assert_eq!(decoder.get(&mut result[..]).unwrap(), 4);
+    assert_eq!(result, values1);
+    decoder.set_data(encoder2.flush_buffer().unwrap(), 3).unwrap();
+    assert_eq!(decoder.get(&mut result[..]).unwrap(), 3);
+    assert_eq!(&result[..3], &[ByteArray::from("FR"), ByteArray::from("JP"),
+      ByteArray::from("US")]);
+
+    // Values outside of the dictionary are rejected, batch is rejected as a whole
+    let values = vec![ByteArray::from("US"), ByteArray::from("GB")];
+    assert!(encoder1.validate_batch(&values[..]).is_err());
+    assert!(encoder1.validate_batch(&values[..1]).is_ok());
+    assert!(encoder1.put(&values[..]).is_err());
+    assert_eq!(encoder1.num_buffered_values(), 0);
+    assert!(encoder1.put(&[ByteArray::from("GB")]).is_err());
+    assert!(encoder2.put_slices(&[&b"GB"[..]]).is_err());
+
+    // Size of the shared dictionary is the size of its PLAIN encoded entries
+    let dict_len = encoder1.write_dict().unwrap().len();
+    assert_eq!(dict_len, 4 * (4 + 2));
+    assert_eq!(encoder1.dict_encoded_size(), dict_len as u64);
+    assert_eq!(encoder2.dict_encoded_size(), dict_len as u64);
+
+    let dict = SharedDictionary::<Int32Type>::new(vec![7, 3, 5]).unwrap();
+    assert_eq!(dict.index_of(&5), Some(2));
+    assert_eq!(dict.index_of(&4), None);
+    assert!(SharedDictionary::<Int32Type>::new(vec![1, 2, 1]).is_err());
+    assert_eq!(SharedDictionary::<Int32Type>::new(vec![]).unwrap().index_of(&1), None);
   }
-}
 
-impl<T: DataType> Encoder<T> for DeltaByteArrayEncoder<T> {
-  default fn put(&mut self, _values: &[T::T]) -> Result<()> {
-    panic!("DeltaByteArrayEncoder only supports ByteArrayType");
+  #[test]
+  fn test_dict_encoder_new_labeled() {
+    let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+    let mem_tracker = Rc::new(MemTracker::new());
+    let mut plain_encoder =
+      DictEncoder::<Int32Type>::new(desc.clone(), mem_tracker.clone());
+    let mut rle_encoder =
+      DictEncoder::<Int32Type>::new_labeled(desc, mem_tracker, Encoding::RLE_DICTIONARY);
+    assert_eq!(plain_encoder.encoding(), Encoding::PLAIN_DICTIONARY);
+    assert_eq!(rle_encoder.encoding(), Encoding::RLE_DICTIONARY);
+
+    let values = vec![3, 1, 3, 2];
+    plain_encoder.put(&values[..]).unwrap();
+    rle_encoder.put(&values[..]).unwrap();
+    assert_eq!(
+      rle_encoder.write_dict().unwrap().data(),
+      plain_encoder.write_dict().unwrap().data()
+    );
+    assert_eq!(
+      rle_encoder.flush_buffer().unwrap().data(),
+      plain_encoder.flush_buffer().unwrap().data()
+    );
   }
 
-  fn encoding(&self) -> Encoding {
-    Encoding::DELTA_BYTE_ARRAY
+  #[test]
+  #[should_panic(expected = "Invalid dictionary data page encoding PLAIN")]
+  fn test_dict_encoder_new_labeled_invalid_encoding() {
+    let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+    let mem_tracker = Rc::new(MemTracker::new());
+    DictEncoder::<Int32Type>::new_labeled(desc, mem_tracker, Encoding::PLAIN);
   }
 
-  default fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
-    panic!("DeltaByteArrayEncoder only supports ByteArrayType");
+  #[test]
+  fn test_dict_put_indices() {
+    let mut encoder = create_test_dict_encoder::<Int32Type>(-1);
+    encoder.put(&[10, 20, 30]).unwrap();
+    encoder.flush_buffer().unwrap();
+
+    encoder.put_indices(&[2, 0, 0, 1, 2]).unwrap();
+    encoder.put(&[20]).unwrap();
+    assert_eq!(encoder.num_buffered_values(), 6);
+
+    let mut dict_decoder = PlainDecoder::<Int32Type>::new(-1);
+    dict_decoder.set_data(encoder.write_dict().unwrap(), 3).unwrap();
+    let mut decoder = create_test_dict_decoder::<Int32Type>();
+    decoder.set_dict(Box::new(dict_decoder)).unwrap();
+    decoder.set_data(encoder.flush_buffer().unwrap(), 6).unwrap();
+    let mut result = vec![0; 6];
+    assert_eq!(decoder.get(&mut result[..]).unwrap(), 6);
+    assert_eq!(result, vec![30, 10, 10, 20, 30, 20]);
+
+    // Out of range indices are rejected and none of the indices are buffered
+    assert_eq!(
+      encoder.put_indices(&[0, 3]).unwrap_err(),
+      general_err!(
+        "Column \"col\": dictionary index 3 is out of range for dictionary with 3 \
+          entries")
+    );
+    assert!(encoder.put_indices(&[1, -1]).is_err());
+    assert_eq!(encoder.num_buffered_values(), 0);
   }
-}
 
-impl Encoder<ByteArrayType> for DeltaByteArrayEncoder<ByteArrayType> {
-  fn put(&mut self, values: &[ByteArray]) -> Result<()> {
-    let mut prefix_lengths: Vec<i32> = vec![];
-    let mut suffixes: Vec<ByteArray> = vec![];
+  #[test]
+  fn test_encode_summary() {
+    let mut encoder = create_test_encoder::<Int32Type>(-1, Encoding::PLAIN);
+    encoder.put(&[1, 2, 3]).unwrap();
+    let summary = encoder.encode_summary();
+    assert_eq!(summary, EncodeSummary {
+      encoding: Encoding::PLAIN,
+      num_values: 3,
+      estimated_size: 12,
+      num_dict_entries: None
+    });
+    assert_eq!(summary.to_string(), "encoding: PLAIN, values: 3, estimated bytes: 12");
+
+    let mut encoder = create_test_dict_encoder::<Int32Type>(-1);
+    encoder.put(&[1, 2, 1, 1, 3, 2]).unwrap();
+    let summary = encoder.encode_summary();
+    assert_eq!(summary.encoding, Encoding::PLAIN_DICTIONARY);
+    assert_eq!(summary.num_values, 6);
+    assert_eq!(summary.estimated_size, encoder.estimated_data_encoded_size());
+    assert_eq!(summary.num_dict_entries, Some(3));
+    assert_eq!(
+      summary.to_string(),
+      format!(
+        "encoding: PLAIN_DICTIONARY, values: 6, estimated bytes: {}, {}",
+        summary.estimated_size,
+        "dictionary entries: 3"
+      )
+    );
 
-    for byte_array in values {
-      let current = byte_array.data();
-      // Maximum prefix length that is shared between previous value and current value
-      let prefix_len = cmp::min(self.previous.len(), current.len());
-      let mut match_len = 0;
-      while match_len < prefix_len && self.previous[match_len] == current[match_len] {
-        match_len += 1;
-      }
-      prefix_lengths.push(match_len as i32);
-      suffixes.push(byte_array.slice(match_len, byte_array.len() - match_len));
-      // Update previous for the next prefix
-      self.previous.clear();
-      self.previous.extend_from_slice(current);
+    // Summary reflects the values buffered since the last flush
+    encoder.flush_buffer().unwrap();
+    assert_eq!(encoder.encode_summary().num_values, 0);
+    assert_eq!(encoder.encode_summary().num_dict_entries, Some(3));
+  }
+
+  #[test]
+  fn test_tee_encoder() {
+    let values: Vec<i32> = (0..1000).map(|i| i % 10).collect();
+    let encodings = [Encoding::PLAIN, Encoding::PLAIN_DICTIONARY];
+    let mut tee = TeeEncoder::new(
+      create_test_encoder::<Int32Type>(-1, encodings[0]),
+      create_test_encoder::<Int32Type>(-1, encodings[1])
+    );
+    tee.put(&values[..500]).unwrap();
+    tee.put(&values[500..]).unwrap();
+
+    // Each inner encoder matches the same encoder used on its own
+    let mut expected_sizes = vec![];
+    let mut expected_bytes = vec![];
+    for encoding in &encodings {
+      let mut encoder = create_test_encoder::<Int32Type>(-1, *encoding);
+      encoder.put(&values[..]).unwrap();
+      expected_sizes.push(encoder.estimated_data_encoded_size());
+      expected_bytes.push(encoder.flush_buffer().unwrap());
     }
-    self.prefix_len_encoder.put(&prefix_lengths)?;
-    self.suffix_writer.put(&suffixes)?;
-    Ok(())
+    let (plain_size, dict_size) = tee.estimated_data_encoded_sizes();
+    assert_eq!(vec![plain_size, dict_size], expected_sizes);
+    assert!(dict_size < plain_size);
+
+    let (encoding, bytes) = tee.flush_smaller().unwrap();
+    assert_eq!(encoding, Encoding::PLAIN_DICTIONARY);
+    assert_eq!(bytes.data(), expected_bytes[1].data());
+    assert_eq!(tee.estimated_data_encoded_sizes().0, 0);
+
+    // Encoded values are the same as encoded separately
+    tee.put(&values[..]).unwrap();
+    let (mut plain, mut dict) = tee.into_inner();
+    assert_eq!(plain.num_buffered_values(), values.len());
+    assert_eq!(dict.num_buffered_values(), values.len());
+    assert_eq!(plain.flush_buffer().unwrap().data(), expected_bytes[0].data());
+    assert_eq!(dict.flush_buffer().unwrap().data(), expected_bytes[1].data());
+
+    // Used as encoder, tee reports and returns the first encoder
+    let mut tee = TeeEncoder::new(
+      create_test_encoder::<Int32Type>(-1, encodings[0]),
+      create_test_encoder::<Int32Type>(-1, encodings[1])
+    );
+    {
+      let encoder: &mut Encoder<Int32Type> = &mut tee;
+      encoder.put(&values[..500]).unwrap();
+      encoder.put_repeated(values[500], 1).unwrap();
+      encoder.put(&values[501..]).unwrap();
+      assert_eq!(encoder.encoding(), Encoding::PLAIN);
+      assert_eq!(encoder.num_buffered_values(), values.len());
+      assert_eq!(encoder.estimated_data_encoded_size(), expected_sizes[0]);
+      assert_eq!(encoder.flush_buffer().unwrap().data(), expected_bytes[0].data());
+      assert_eq!(encoder.num_buffered_values(), 0);
+    }
+    // Second encoder is flushed as well
+    assert_eq!(tee.estimated_data_encoded_sizes().1, 1);
+
+    let mut encoder: Box<Encoder<Int32Type>> = Box::new(tee);
+    encoder.put(&values[..]).unwrap();
+    assert_eq!(encoder.flush_buffer().unwrap().data(), expected_bytes[0].data());
+
+    // Spaced values and flush into a slice are forwarded to both encoders
+    let valid_bits = vec![0xFF; values.len() / 8];
+    assert_eq!(encoder.put_spaced(&values[..], &valid_bits[..]).unwrap(), values.len());
+    let mut out = vec![0; expected_sizes[0]];
+    assert_eq!(encoder.flush_into(&mut out[..]).unwrap(), expected_sizes[0]);
+    assert_eq!(&out[..], expected_bytes[0].data());
+    assert!(encoder.put_spaced(&values[..], &valid_bits[..1]).is_err());
+    assert_eq!(encoder.num_buffered_values(), 0);
   }
 
-  fn flush_buffer(&mut self) -> Result<ByteBufferPtr> {
-    // TODO: investigate if we can merge lengths and suffixes
-    // without copying data into new vector.
-    let mut total_bytes = vec![];
-    // Insert lengths ...
-    let lengths = self.prefix_len_encoder.flush_buffer()?;
-    total_bytes.extend_from_slice(lengths.data());
-    // ... followed by suffixes
-    let suffixes = self.suffix_writer.flush_buffer()?;
-    total_bytes.extend_from_slice(suffixes.data());
+  #[test]
+  fn test_tee_encoder_rejected_batch() {
+    let dict = SharedDictionary::<ByteArrayType>::new(
+      vec![ByteArray::from("a"), ByteArray::from("b")]).unwrap();
+    let desc = make_col_desc("col", Type::BYTE_ARRAY, LogicalType::NONE, -1, 0, 0);
+    let mem_tracker = Rc::new(MemTracker::new());
+    let mut tee = TeeEncoder::new(
+      Box::new(PlainEncoder::new(desc.clone(), mem_tracker.clone(), vec![])),
+      Box::new(DictEncoder::with_shared_dict(desc, dict, mem_tracker))
+    );
 
-    Ok(ByteBufferPtr::new(total_bytes))
+    // Second encoder rejects the value, first encoder does not buffer the batch
+    let values = vec![ByteArray::from("a"), ByteArray::from("c")];
+    assert!(tee.validate_batch(&values[..]).is_err());
+    assert!(tee.put(&values[..]).is_err());
+    assert!(tee.put_repeated(ByteArray::from("c"), 3).is_err());
+    assert!(tee.put_spaced(&values[..], &[0b10]).is_err());
+    let (first, second) = tee.into_inner();
+    assert_eq!(first.num_buffered_values(), 0);
+    assert_eq!(second.num_buffered_values(), 0);
   }
-}
 
+  #[test]
+  fn test_chunk_by_encoded_size() {
+    let lengths = vec![10, 50, 5, 200, 30, 30, 30, 1, 0, 64, 64];
+    let values: Vec<ByteArray> =
+      lengths.iter().map(|len| ByteArray::from(vec![b'x'; *len])).collect();
+    let desc = make_col_desc("col", Type::BYTE_ARRAY, LogicalType::NONE, -1, 0, 0);
+    let max_page_bytes = 100;
+
+    let chunks = chunk_by_encoded_size::<ByteArrayType>(
+      &values[..], Encoding::PLAIN, desc, max_page_bytes).unwrap();
+    // Each PLAIN encoded value takes 4 more bytes than its length
+    assert_eq!(chunks, vec![0..3, 3..4, 4..6, 6..9, 9..10, 10..11]);
+    let mut next = 0;
+    for chunk in &chunks {
+      assert_eq!(chunk.start, next);
+      next = chunk.end;
+      let mut encoder = create_test_encoder::<ByteArrayType>(-1, Encoding::PLAIN);
+      encoder.put(&values[chunk.clone()]).unwrap();
+      let encoded_len = encoder.flush_buffer().unwrap().len();
+      assert!(encoded_len <= max_page_bytes || chunk.len() == 1);
+    }
+    assert_eq!(next, values.len());
+
+    // Dictionary indices, estimate is an upper bound of the encoded size
+    let values: Vec<i32> = (0..10000).map(|i| (i * 7) % 100).collect();
+    let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+    let chunks = chunk_by_encoded_size::<Int32Type>(
+      &values[..], Encoding::PLAIN_DICTIONARY, desc.clone(), 256).unwrap();
+    assert!(chunks.len() > 1);
+    for chunk in &chunks {
+      let mut encoder = create_test_encoder::<Int32Type>(-1, Encoding::PLAIN_DICTIONARY);
+      encoder.put(&values[chunk.clone()]).unwrap();
+      assert!(encoder.flush_buffer().unwrap().len() <= 256);
+    }
+    assert_eq!(chunks.last().unwrap().end, values.len());
 
-#[cfg(test)]
-mod tests {
-  use super::super::decoding::*;
-  use super::*;
-  use schema::types::{ColumnDescriptor, ColumnPath, Type as SchemaType};
-  use std::rc::Rc;
-  use util::memory::MemTracker;
-  use util::test_common::RandGen;
+    let chunks = chunk_by_encoded_size::<Int32Type>(&[], Encoding::PLAIN, desc, 10);
+    assert!(chunks.unwrap().is_empty());
+  }
 
-  const TEST_SET_SIZE: usize = 1024;
+  #[test]
+  fn test_recommend_encoding() {
+    let values = vec![true; 1000];
+    assert_eq!(recommend_encoding::<BoolType>(&values[..]), Encoding::RLE);
+    let mut encoder = create_test_encoder::<BoolType>(-1, Encoding::RLE);
+    encoder.put(&values[..]).unwrap();
+    let data = encoder.flush_buffer().unwrap();
+    assert!(data.len() < 1000 / 8);
+    let mut decoder = create_test_decoder::<BoolType>(-1, Encoding::RLE);
+    decoder.set_data(data, values.len()).unwrap();
+    let mut result = vec![false; values.len()];
+    assert_eq!(decoder.get(&mut result[..]).unwrap(), values.len());
+    assert_eq!(result, values);
+
+    // Few long runs
+    let mut values = vec![false; 500];
+    values.extend(vec![true; 300]);
+    values.push(false);
+    assert_eq!(recommend_encoding::<BoolType>(&values[..]), Encoding::RLE);
+
+    let values: Vec<bool> = (0..1000).map(|i| i % 3 == 0).collect();
+    assert_eq!(recommend_encoding::<BoolType>(&values[..]), Encoding::PLAIN);
+    let values = random_bools(1000);
+    assert_eq!(recommend_encoding::<BoolType>(&values[..]), Encoding::PLAIN);
+    assert_eq!(recommend_encoding::<BoolType>(&[]), Encoding::PLAIN);
+
+    // Sorted integers are delta encoded, unsorted integers and other types are not
+    let delta = Encoding::DELTA_BINARY_PACKED;
+    assert_eq!(recommend_encoding::<Int32Type>(&[1; 1000]), delta);
+    let values: Vec<i32> = (0..1000).map(|i| i * 3 - 100).collect();
+    assert_eq!(recommend_encoding::<Int32Type>(&values[..]), delta);
+    let values: Vec<i64> = (0..20000).map(|i| i64::max_value() - 20000 + i).collect();
+    assert_eq!(recommend_encoding::<Int64Type>(&values[..]), delta);
+
+    assert_eq!(recommend_encoding::<Int32Type>(&[3, 1, 2]), Encoding::PLAIN);
+    let values: Vec<i64> = (0..1000).map(|i| (i * 7919) % 1000).collect();
+    assert_eq!(recommend_encoding::<Int64Type>(&values[..]), Encoding::PLAIN);
+    assert_eq!(recommend_encoding::<Int32Type>(&[5]), Encoding::PLAIN);
+    assert_eq!(recommend_encoding::<Int32Type>(&[]), Encoding::PLAIN);
+    assert_eq!(recommend_encoding::<DoubleType>(&[1.0, 2.0]), Encoding::PLAIN);
+  }
 
   #[test]
-  fn test_bool() {
-    BoolType::test(Encoding::PLAIN, TEST_SET_SIZE, -1);
-    BoolType::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, -1);
-    BoolType::test(Encoding::RLE, TEST_SET_SIZE, -1);
+  fn test_frame_of_reference() {
+    let values = vec![15, 10, 12, 10, 1000];
+    let (min, offsets) = frame_of_reference::<Int32Type>(&values[..]);
+    assert_eq!(min, 10);
+    assert_eq!(offsets, vec![5, 0, 2, 0, 990]);
+
+    let values = random_numbers::<i32>(1000);
+    let (min, offsets) = frame_of_reference::<Int32Type>(&values[..]);
+    let result: Vec<i32> = offsets.iter().map(|v| (min + v) as i32).collect();
+    assert_eq!(result, values);
+
+    let mut values = random_numbers::<i64>(1000);
+    values.push(i64::MIN);
+    values.push(i64::MAX);
+    let (min, offsets) = frame_of_reference::<Int64Type>(&values[..]);
+    assert_eq!(min, i64::MIN);
+    let result: Vec<i64> = offsets.iter().map(|v| min.wrapping_add(*v)).collect();
+    assert_eq!(result, values);
+
+    assert_eq!(frame_of_reference::<Int64Type>(&[]), (0, vec![]));
   }
 
   #[test]
-  fn test_i32() {
-    Int32Type::test(Encoding::PLAIN, TEST_SET_SIZE, -1);
-    Int32Type::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, -1);
-    Int32Type::test(Encoding::DELTA_BINARY_PACKED, TEST_SET_SIZE, -1);
+  #[cfg(feature = "bench")]
+  fn test_bench_encode() {
+    let values: Vec<i64> = (0..1000).collect();
+    let desc = make_col_desc("col", Type::INT64, LogicalType::NONE, -1, 0, 0);
+    bench_encode::<Int64Type>(Encoding::PLAIN, &values[..], desc.clone(), 1);
+    // Each iteration starts with an empty dictionary
+    bench_encode::<Int64Type>(Encoding::PLAIN_DICTIONARY, &values[..], desc, 3);
   }
 
   #[test]
-  fn test_i64() {
-    Int64Type::test(Encoding::PLAIN, TEST_SET_SIZE, -1);
-    Int64Type::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, -1);
-    Int64Type::test(Encoding::DELTA_BINARY_PACKED, TEST_SET_SIZE, -1);
+  fn test_is_sorted() {
+    assert!(is_sorted::<Int32Type>(&[]));
+    assert!(is_sorted::<Int32Type>(&[5]));
+    assert!(is_sorted::<Int32Type>(&[1, 2, 2, 3, 10]));
+    assert!(!is_sorted::<Int32Type>(&[10, 3, 2, 2, 1]));
+    assert!(is_sorted::<Int64Type>(&[i64::min_value(), -1, 0, i64::max_value()]));
+    assert!(!is_sorted::<DoubleType>(&[1.0, f64::NAN, 2.0]));
+
+    let mut values = random_numbers::<i32>(100);
+    values.sort();
+    assert!(is_sorted::<Int32Type>(&values[..]));
+    values.reverse();
+    assert_eq!(is_sorted::<Int32Type>(&values[..]), values[0] == values[99]);
   }
 
   #[test]
-  fn test_i96() {
-    Int96Type::test(Encoding::PLAIN, TEST_SET_SIZE, -1);
-    Int96Type::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, -1);
+  fn test_dict_estimated_total_size() {
+    // Indices do not repeat and are bit packed
+    let values: Vec<i32> = (0..1000).map(|i| i % 10).collect();
+    let mut encoder = create_test_dict_encoder::<Int32Type>(-1);
+    encoder.put(&values[..]).unwrap();
+    check_dict_estimated_total_size(encoder, 40);
+
+    let values: Vec<ByteArray> =
+      (0..1000).map(|i| ByteArray::from(format!("v{}", i % 10).as_str())).collect();
+    let mut encoder = create_test_dict_encoder::<ByteArrayType>(-1);
+    encoder.put(&values[..]).unwrap();
+    check_dict_estimated_total_size(encoder, 60);
+
+    let mut encoder = create_test_dict_encoder::<BoolType>(-1);
+    encoder.put(&[true, false, true]).unwrap();
+    check_dict_estimated_total_size(encoder, 1);
+  }
+
+  fn check_dict_estimated_total_size<T: DataType>(
+    mut encoder: DictEncoder<T>,
+    dict_page_size: usize
+  ) {
+    let estimated = encoder.estimated_total_size();
+    assert_eq!(encoder.estimated_dict_page_size(), dict_page_size);
+    let dict_len = encoder.write_dict().unwrap().len();
+    assert_eq!(dict_len, dict_page_size);
+    let actual = dict_len + encoder.write_indices().unwrap().len();
+    assert!(estimated >= actual, "estimated {} < actual {}", estimated, actual);
+    assert!(
+      estimated - actual <= actual / 4,
+      "estimated {}, actual {}",
+      estimated,
+      actual
+    );
   }
 
   #[test]
-  fn test_float() {
-    FloatType::test(Encoding::PLAIN, TEST_SET_SIZE, -1);
-    FloatType::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, -1);
+  fn test_put_repeated() {
+    let prefix = vec![1, 2, 3, 3];
+    for encoding in vec![
+      Encoding::PLAIN, Encoding::PLAIN_DICTIONARY, Encoding::DELTA_BINARY_PACKED
+    ] {
+      for count in vec![0, 1, 7, 8, 9, 1000] {
+        check_put_repeated::<Int32Type>(encoding, -1, prefix.clone(), 3, count);
+        check_put_repeated::<Int32Type>(encoding, -1, prefix.clone(), 5, count);
+      }
+    }
+
+    for count in vec![0, 1, 7, 8, 9, 1000, 5000] {
+      check_put_repeated::<BoolType>(Encoding::RLE, -1, vec![true, false], false, count);
+      check_put_repeated::<BoolType>(Encoding::RLE, -1, vec![], true, count);
+      check_put_repeated::<BoolType>(
+        Encoding::PLAIN, -1, vec![true, false], false, count);
+    }
+
+    let prefix = vec![ByteArray::from("ab"), ByteArray::from("abc")];
+    for encoding in vec![
+      Encoding::PLAIN, Encoding::PLAIN_DICTIONARY,
+      Encoding::DELTA_LENGTH_BYTE_ARRAY, Encoding::DELTA_BYTE_ARRAY
+    ] {
+      for count in vec![0, 1, 100] {
+        check_put_repeated::<ByteArrayType>(
+          encoding, -1, prefix.clone(), ByteArray::from("abcd"), count);
+      }
+    }
   }
 
   #[test]
-  fn test_double() {
-    DoubleType::test(Encoding::PLAIN, TEST_SET_SIZE, -1);
-    DoubleType::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, -1);
+  fn test_encoder_debug() {
+    let mut encoder = create_test_plain_encoder::<Int32Type>();
+    encoder.put(&[1, 2, 3]).unwrap();
+    let debug = format!("{:?}", encoder);
+    assert!(debug.starts_with("PlainEncoder {"), "{}", debug);
+    assert!(debug.contains("encoding: PLAIN"), "{}", debug);
+    assert!(debug.contains("num_values: 3"), "{}", debug);
+    assert!(debug.contains("buffered_bytes: 12"), "{}", debug);
+
+    let mut encoder = create_test_dict_encoder::<Int32Type>(-1);
+    encoder.put(&[1, 2, 1, 1]).unwrap();
+    let debug = format!("{:?}", encoder);
+    assert!(debug.contains("encoding: PLAIN_DICTIONARY"), "{}", debug);
+    assert!(debug.contains("num_values: 4"), "{}", debug);
+    assert!(debug.contains("num_entries: 2"), "{}", debug);
+
+    let mut encoder = DeltaBitPackEncoder::<Int64Type>::new();
+    encoder.put(&[1, 2]).unwrap();
+    let debug = format!("{:?}", encoder);
+    assert!(debug.contains("encoding: DELTA_BINARY_PACKED"), "{}", debug);
+    assert!(debug.contains("num_values: 2"), "{}", debug);
+    assert!(debug.contains("block_size: 128"), "{}", debug);
+
+    let mut encoder = DeltaByteArrayEncoder::<ByteArrayType>::new();
+    encoder.put(&[ByteArray::from("ab"), ByteArray::from("ac")]).unwrap();
+    let debug = format!("{:?}", encoder);
+    assert!(debug.starts_with("DeltaByteArrayEncoder { encoding: DELTA_BYTE_ARRAY"));
+    assert!(debug.contains("DeltaLengthByteArrayEncoder { encoding: DELTA_LENGTH"));
+    assert!(debug.contains("num_values: 2"), "{}", debug);
   }
 
   #[test]
-  fn test_byte_array() {
-    ByteArrayType::test(Encoding::PLAIN, TEST_SET_SIZE, -1);
-    ByteArrayType::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, -1);
-    ByteArrayType::test(Encoding::DELTA_LENGTH_BYTE_ARRAY, TEST_SET_SIZE, -1);
-    ByteArrayType::test(Encoding::DELTA_BYTE_ARRAY, TEST_SET_SIZE, -1);
+  fn test_dict_empty() {
+    test_dict_empty_internal::<BoolType>(-1);
+    test_dict_empty_internal::<Int32Type>(-1);
+    test_dict_empty_internal::<Int96Type>(-1);
+    test_dict_empty_internal::<DoubleType>(-1);
+    test_dict_empty_internal::<ByteArrayType>(-1);
+    test_dict_empty_internal::<FixedLenByteArrayType>(16);
+  }
+
+  fn test_dict_empty_internal<T: DataType>(type_length: i32) {
+    let mut encoder = create_test_dict_encoder::<T>(type_length);
+    assert_eq!(encoder.num_entries(), 0);
+
+    let dict = encoder.write_dict().unwrap();
+    assert_eq!(dict.len(), 0);
+
+    let indices = encoder.flush_buffer().unwrap();
+    assert_eq!(indices.data(), &[0]);
+
+    let mut dict_decoder = PlainDecoder::<T>::new(type_length);
+    dict_decoder.set_data(dict, encoder.num_entries()).unwrap();
+    let mut decoder = create_test_dict_decoder::<T>();
+    decoder.set_dict(Box::new(dict_decoder)).unwrap();
+    decoder.set_data(indices, 0).unwrap();
+    assert_eq!(decoder.values_left(), 0);
+
+    let mut result_data = vec![T::T::default(); 8];
+    assert_eq!(decoder.get(&mut result_data).unwrap(), 0);
   }
 
   #[test]
-  fn test_fixed_lenbyte_array() {
-    FixedLenByteArrayType::test(Encoding::PLAIN, TEST_SET_SIZE, 100);
-    FixedLenByteArrayType::test(Encoding::PLAIN_DICTIONARY, TEST_SET_SIZE, 100);
+  fn test_dict_single_entry() {
+    let mut encoder = create_test_dict_encoder::<Int32Type>(-1);
+    encoder.put(&[7, 7, 7]).unwrap();
+    assert_eq!(encoder.num_entries(), 1);
+
+    let indices = encoder.flush_buffer().unwrap();
+    // Single entry is encoded with bit width 1
+    assert_eq!(indices.data()[0], 1);
+
+    let mut dict_decoder = PlainDecoder::<Int32Type>::new(-1);
+    dict_decoder.set_data(encoder.write_dict().unwrap(), 1).unwrap();
+    let mut decoder = create_test_dict_decoder::<Int32Type>();
+    decoder.set_dict(Box::new(dict_decoder)).unwrap();
+    decoder.set_data(indices, 3).unwrap();
+    let mut result = vec![0; 4];
+    assert_eq!(decoder.get(&mut result[..]).unwrap(), 3);
+    assert_eq!(&result[..3], &[7, 7, 7]);
+    assert_eq!(decoder.values_left(), 0);
+
+    // No values buffered after flush, only bit width is written
+    assert_eq!(encoder.flush_buffer().unwrap().data(), &[1]);
+  }
+
+  #[test]
+  fn test_dictionary_page_writer() {
+    let desc = make_col_desc("col", Type::INT32, LogicalType::NONE, -1, 0, 0);
+    let encoder = DictEncoder::<Int32Type>::new(desc, Rc::new(MemTracker::new()));
+    let mut sink: Vec<u8> = vec![];
+    let pages = vec![vec![1, 2, 3, 1, 2], vec![3, 3, 3], vec![4, 1, 5, 2]];
+    let locations = {
+      let mut writer = DictionaryPageWriter::new(encoder, &mut sink);
+      for values in &pages {
+        writer.put(&values[..]).unwrap();
+        writer.flush_data_page().unwrap();
+        // Flushing again does not create an empty page
+        writer.flush_data_page().unwrap();
+      }
+      writer.close().unwrap()
+    };
+    assert_eq!(locations.len(), 4);
+    assert_eq!(locations[0].page_type, PageType::DICTIONARY_PAGE);
+    assert_eq!(locations[0].offset, 0);
+    assert_eq!(locations[0].num_values, 5);
+    // Pages follow each other and cover the whole sink
+    for i in 1..locations.len() {
+      assert_eq!(locations[i].page_type, PageType::DATA_PAGE);
+      let prev_end = locations[i - 1].offset + locations[i - 1].len as u64;
+      assert_eq!(locations[i].offset, prev_end);
+      assert_eq!(locations[i].num_values, pages[i - 1].len());
+    }
+    let last = locations[locations.len() - 1];
+    assert_eq!(last.offset as usize + last.len, sink.len());
+
+    // Read pages back by offset
+    let read_page = |location: &PageLocation| {
+      let start = location.offset as usize;
+      ByteBufferPtr::new(sink[start..start + location.len].to_vec())
+    };
+    let mut dict_decoder = PlainDecoder::<Int32Type>::new(-1);
+    dict_decoder.set_data(read_page(&locations[0]), locations[0].num_values).unwrap();
+    let mut decoder = create_test_dict_decoder::<Int32Type>();
+    decoder.set_dict(Box::new(dict_decoder)).unwrap();
+    for (location, values) in locations[1..].iter().zip(pages.iter()) {
+      decoder.set_data(read_page(location), location.num_values).unwrap();
+      assert_eq!(&decoder.decode_all().unwrap(), values);
+    }
+  }
+
+  #[test]
+  fn test_dictionary_page_writer_empty() {
+    let desc = make_col_desc("col", Type::BYTE_ARRAY, LogicalType::NONE, -1, 0, 0);
+    let encoder = DictEncoder::<ByteArrayType>::new(desc, Rc::new(MemTracker::new()));
+    let mut sink: Vec<u8> = vec![];
+    let locations = {
+      let mut writer = DictionaryPageWriter::new(encoder, &mut sink);
+      writer.put(&[]).unwrap();
+      writer.flush_data_page().unwrap();
+      writer.close().unwrap()
+    };
+    assert!(locations.is_empty());
+    assert!(sink.is_empty());
   }
 
   trait EncodingTester<T: DataType> {
@@ -1033,25 +5061,44 @@ mod tests {
     }
   }
 
-  fn create_test_col_desc(type_len: i32, t: Type) -> ColumnDescriptor {
-    let ty = SchemaType::primitive_type_builder("t", t)
-      .with_length(type_len)
-      .build()
-      .unwrap();
-    ColumnDescriptor::new(Rc::new(ty), None, 0, 0, ColumnPath::new(vec![]))
+  // Checks that `put_repeated` encodes the same bytes as `put` of `count` copies, when
+  // the copies are put between `prefix` values.
+  fn check_put_repeated<T: DataType>(
+    encoding: Encoding,
+    type_len: i32,
+    prefix: Vec<T::T>,
+    value: T::T,
+    count: usize
+  ) where T: 'static {
+    let mut encoder = create_test_encoder::<T>(type_len, encoding);
+    encoder.put(&prefix[..]).unwrap();
+    encoder.put_repeated(value.clone(), count).unwrap();
+    encoder.put(&prefix[..]).unwrap();
+
+    let mut expected_encoder = create_test_encoder::<T>(type_len, encoding);
+    expected_encoder.put(&prefix[..]).unwrap();
+    expected_encoder.put(&vec![value; count][..]).unwrap();
+    expected_encoder.put(&prefix[..]).unwrap();
+
+    assert_eq!(encoder.num_buffered_values(), expected_encoder.num_buffered_values());
+    assert_eq!(
+      encoder.flush_buffer().unwrap().data(),
+      expected_encoder.flush_buffer().unwrap().data()
+    );
   }
 
   fn create_test_encoder<T: DataType>(
     type_len: i32, enc: Encoding
   ) -> Box<Encoder<T>> where T: 'static {
-    let desc = create_test_col_desc(type_len, T::get_physical_type());
+    let desc =
+      make_col_desc("col", T::get_physical_type(), LogicalType::NONE, type_len, 0, 0);
     let mem_tracker = Rc::new(MemTracker::new());
     let encoder: Box<Encoder<T>> = match enc {
       Encoding::PLAIN => {
-        Box::new(PlainEncoder::<T>::new(Rc::new(desc), mem_tracker, vec![]))
+        Box::new(PlainEncoder::<T>::new(desc, mem_tracker, vec![]))
       },
       Encoding::PLAIN_DICTIONARY => {
-        Box::new(DictEncoder::<T>::new(Rc::new(desc), mem_tracker))
+        Box::new(DictEncoder::<T>::new(desc, mem_tracker))
       },
       Encoding::RLE => {
         Box::new(RleValueEncoder::<T>::new())
@@ -1075,7 +5122,8 @@ mod tests {
   fn create_test_decoder<T: DataType>(
     type_len: i32, enc: Encoding
   ) -> Box<Decoder<T>> where T: 'static {
-    let desc = create_test_col_desc(type_len, T::get_physical_type());
+    let desc =
+      make_col_desc("col", T::get_physical_type(), LogicalType::NONE, type_len, 0, 0);
     let decoder: Box<Decoder<T>> = match enc {
       Encoding::PLAIN => {
         Box::new(PlainDecoder::<T>::new(desc.type_length()))
@@ -1102,10 +5150,17 @@ mod tests {
     decoder
   }
 
+  fn create_test_plain_encoder<T: DataType>() -> PlainEncoder<T> {
+    let desc = make_col_desc("col", T::get_physical_type(), LogicalType::NONE, -1, 0, 0);
+    let mem_tracker = Rc::new(MemTracker::new());
+    PlainEncoder::<T>::new(desc, mem_tracker, vec![])
+  }
+
   fn create_test_dict_encoder<T: DataType>(type_len: i32) -> DictEncoder<T> {
-    let desc = create_test_col_desc(type_len, T::get_physical_type());
+    let desc =
+      make_col_desc("col", T::get_physical_type(), LogicalType::NONE, type_len, 0, 0);
     let mem_tracker = Rc::new(MemTracker::new());
-    DictEncoder::<T>::new(Rc::new(desc), mem_tracker)
+    DictEncoder::<T>::new(desc, mem_tracker)
   }
 
   fn create_test_dict_decoder<T: DataType>() -> DictDecoder<T> {
@@ -18,21 +18,27 @@
 use std::cmp;
 use std::mem;
 
+use super::encoding::Encoder;
 use super::rle::{RleDecoder, RleEncoder};
 use basic::Encoding;
-use data_type::AsBytes;
+use column::page::Page;
+use data_type::{AsBytes, DataType};
 use errors::{ParquetError, Result};
-use util::bit_util::{ceil, log2, BitReader, BitWriter};
+use util::bit_util::{bit_width_for_max, ceil, BitReader, BitWriter};
 use util::memory::ByteBufferPtr;
 
 enum InternalEncoder {
   RLE(RleEncoder),
-  BIT_PACKED(BitWriter)
+  BIT_PACKED(BitWriter),
+  // Max level is 0, levels are not written
+  EMPTY
 }
 
 enum InternalDecoder {
   RLE(RleDecoder),
-  BIT_PACKED(BitReader)
+  BIT_PACKED(BitReader),
+  // Max level is 0, all levels are 0 and no bytes are read
+  EMPTY
 }
 
 /// A encoder for definition/repetition levels.
@@ -47,9 +53,15 @@ impl LevelEncoder {
   /// For bit packed encoding it is assumed that buffer is already allocated with
   /// 'LevelEncoder::max_buffer_size' method.
   ///
+  /// When `max_level` is 0, e.g. repetition levels of a flat schema, levels are omitted
+  /// from the page: encoder does not write any bytes and `byte_buffer` is not used.
+  ///
   /// Panics, if encoding is not supported
   pub fn new(encoding: Encoding, max_level: i16, byte_buffer: Vec<u8>) -> Self {
-    let bit_width = log2(max_level as u64 + 1) as u8;
+    let bit_width = bit_width_for_max(max_level as u64);
+    if max_level == 0 {
+      return LevelEncoder { bit_width: bit_width, encoder: InternalEncoder::EMPTY };
+    }
     match encoding {
       Encoding::RLE => {
         LevelEncoder {
@@ -99,20 +111,31 @@ impl LevelEncoder {
         }
         bit_packed_encoder.flush();
       },
+      InternalEncoder::EMPTY => {
+        for value in buffer {
+          if *value != 0 {
+            return Err(general_err!("Level {} exceeds max level 0", value));
+          }
+          num_encoded += 1;
+        }
+      }
     }
     Ok(num_encoded)
   }
 
   /// Computes max buffer size for level encoder/decoder based on encoding, max
   /// repetition/definition level and number of total buffered values (includes null
-  /// values).
+  /// values). Returns 0 when `max_level` is 0, since such levels are not written.
   #[inline]
   pub fn max_buffer_size(
     encoding: Encoding,
     max_level: i16,
     num_buffered_values: usize
   ) -> usize {
-    let bit_width = log2(max_level as u64 + 1) as u8;
+    if max_level == 0 {
+      return 0;
+    }
+    let bit_width = bit_width_for_max(max_level as u64);
     match encoding {
       Encoding::RLE => {
         RleEncoder::max_buffer_size(bit_width, num_buffered_values) +
@@ -125,6 +148,24 @@ impl LevelEncoder {
     }
   }
 
+  /// Returns number of bytes written so far. For BIT_PACKED encoding this is the length
+  /// of the buffer that `consume()` would return. For RLE encoding this is an upper
+  /// bound of that length, because the last values and the current run are not written
+  /// until the encoder is flushed, see `RleEncoder::max_flushed_len()`, and includes 4
+  /// bytes of the encoded length prefix.
+  #[inline]
+  pub fn bytes_written(&self) -> usize {
+    match self.encoder {
+      InternalEncoder::RLE(ref rle_encoder) => {
+        mem::size_of::<i32>() + rle_encoder.max_flushed_len()
+      },
+      InternalEncoder::BIT_PACKED(ref bit_packed_encoder) => {
+        bit_packed_encoder.bytes_written()
+      },
+      InternalEncoder::EMPTY => 0
+    }
+  }
+
   /// Finalizes level encoder, flush all intermediate buffers and return resulting
   /// encoded buffer. Returned buffer is already truncated to encoded bytes only.
   #[inline]
@@ -142,8 +183,232 @@ impl LevelEncoder {
       InternalEncoder::BIT_PACKED(bit_packed_encoder) => {
         Ok(bit_packed_encoder.consume())
       },
+      InternalEncoder::EMPTY => Ok(Vec::new())
+    }
+  }
+}
+
+/// Converts optional values of a column into definition levels and non-null values.
+///
+/// Present values get definition level `max_def_level` and null values get
+/// `max_def_level - 1`, i.e. only the leaf value is null, which for a flat optional
+/// column results in levels 1 and 0 respectively. Non-null values are returned in their
+/// original order.
+///
+/// Panics if `max_def_level` is not positive, because such column cannot contain nulls.
+pub fn options_to_levels_and_values<T: DataType>(
+  values: &[Option<T::T>],
+  max_def_level: i16
+) -> (Vec<i16>, Vec<T::T>) {
+  assert!(max_def_level > 0, "Max definition level must be positive");
+  let mut def_levels = Vec::with_capacity(values.len());
+  let mut non_null_values = Vec::with_capacity(values.len());
+  for value in values {
+    match *value {
+      Some(ref v) => {
+        def_levels.push(max_def_level);
+        non_null_values.push(v.clone());
+      },
+      None => def_levels.push(max_def_level - 1)
+    }
+  }
+  (def_levels, non_null_values)
+}
+
+/// Returns the number of null values implied by definition levels `def_levels`, i.e.
+/// the number of levels below `max_def_level`, e.g. to populate `null_count` statistics.
+///
+/// For nested columns this also counts levels of null or empty parents, since the leaf
+/// value is missing for them as well. Column with `max_def_level` 0 cannot contain nulls.
+pub fn count_nulls(def_levels: &[i16], max_def_level: i16) -> u64 {
+  if max_def_level == 0 {
+    return 0;
+  }
+  def_levels.iter().filter(|level| **level < max_def_level).count() as u64
+}
+
+/// Encodes definition levels of a data page with `num_values` values that are all
+/// null, without a value encoder, since such page has no values to encode. Returns RLE
+/// encoded levels with the 4-byte length prefix of data page V1, and the null count.
+///
+/// Null values get definition level `max_def_level - 1`, same as in
+/// `options_to_levels_and_values()`. Returns an error if `max_def_level` is not
+/// positive, because such column cannot contain nulls.
+pub fn encode_all_null_page(
+  num_values: usize,
+  max_def_level: i16
+) -> Result<(Vec<u8>, u64)> {
+  if max_def_level <= 0 {
+    return Err(general_err!(
+      "Column with max definition level {} cannot contain nulls", max_def_level));
+  }
+  let size = LevelEncoder::max_buffer_size(Encoding::RLE, max_def_level, num_values);
+  let mut encoder = LevelEncoder::new(Encoding::RLE, max_def_level, vec![0; size]);
+  encoder.put(&vec![max_def_level - 1; num_values])?;
+  Ok((encoder.consume()?, num_values as u64))
+}
+
+/// Builds body of a data page V1 in a single buffer: repetition levels, followed by
+/// definition levels, followed by encoded values.
+///
+/// Levels are expected to be the output of `LevelEncoder::consume()`, which for RLE
+/// encoding already contains the 4-byte length prefix required in data page V1. Level
+/// streams that are not written, e.g. when max level is 0, are passed as `None`.
+pub fn build_v1_page_body(
+  rep: Option<ByteBufferPtr>,
+  def: Option<ByteBufferPtr>,
+  values: ByteBufferPtr
+) -> ByteBufferPtr {
+  let levels_len =
+    |levels: &Option<ByteBufferPtr>| levels.as_ref().map_or(0, |buf| buf.len());
+  let mut body = Vec::with_capacity(levels_len(&rep) + levels_len(&def) + values.len());
+  for levels in [rep, def].iter() {
+    if let Some(ref buf) = *levels {
+      body.extend_from_slice(buf.as_ref());
+    }
+  }
+  body.extend_from_slice(values.as_ref());
+  ByteBufferPtr::new(body)
+}
+
+/// Tracks estimated size of a data page that consists of repetition levels, definition
+/// levels and encoded values.
+///
+/// In data page V1 levels are stored inline with values, and RLE encoded levels are
+/// prefixed with 4 bytes of their length. In data page V2 levels are stored separately
+/// and their lengths are part of the page header, so the length prefix is not counted.
+/// Levels that are not set (e.g. max level is 0) are not counted.
+pub struct PageSizeTracker {
+  data_page_v2: bool,
+  rep_levels_byte_len: usize,
+  def_levels_byte_len: usize,
+  values_byte_len: usize
+}
+
+impl PageSizeTracker {
+  /// Creates new page size tracker for data page V1 or data page V2.
+  pub fn new(data_page_v2: bool) -> Self {
+    Self {
+      data_page_v2: data_page_v2,
+      rep_levels_byte_len: 0,
+      def_levels_byte_len: 0,
+      values_byte_len: 0
+    }
+  }
+
+  /// Updates size of repetition levels from the current state of `encoder`.
+  pub fn update_rep_levels(&mut self, encoder: &LevelEncoder) {
+    self.rep_levels_byte_len = self.levels_byte_len_for(encoder);
+  }
+
+  /// Updates size of definition levels from the current state of `encoder`.
+  pub fn update_def_levels(&mut self, encoder: &LevelEncoder) {
+    self.def_levels_byte_len = self.levels_byte_len_for(encoder);
+  }
+
+  /// Updates size of values from the current state of `encoder`.
+  pub fn update_values<T: DataType>(&mut self, encoder: &Encoder<T>) {
+    self.values_byte_len = encoder.estimated_data_encoded_size();
+  }
+
+  /// Returns estimated size in bytes of repetition and definition levels.
+  pub fn levels_byte_len(&self) -> usize {
+    self.rep_levels_byte_len + self.def_levels_byte_len
+  }
+
+  /// Returns estimated size in bytes of encoded values.
+  pub fn values_byte_len(&self) -> usize {
+    self.values_byte_len
+  }
+
+  /// Returns estimated total size in bytes of the page (excluding page header).
+  pub fn total_estimated_bytes(&self) -> usize {
+    self.levels_byte_len() + self.values_byte_len
+  }
+
+  /// Resets tracker, should be called when a new page is started.
+  pub fn reset(&mut self) {
+    self.rep_levels_byte_len = 0;
+    self.def_levels_byte_len = 0;
+    self.values_byte_len = 0;
+  }
+
+  #[inline]
+  fn levels_byte_len_for(&self, encoder: &LevelEncoder) -> usize {
+    match encoder.encoder {
+      InternalEncoder::RLE(ref rle_encoder) if self.data_page_v2 => {
+        rle_encoder.max_flushed_len()
+      },
+      _ => encoder.bytes_written()
+    }
+  }
+}
+
+/// Typed representation of data page V2 header fields that describe the page layout.
+///
+/// Data page V2 stores RLE encoded repetition levels, followed by RLE encoded definition
+/// levels, followed by encoded values. Levels are never compressed and their lengths are
+/// stored in the header, `is_compressed` only applies to the values region.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataPageV2Header {
+  pub num_values: u32,
+  pub num_nulls: u32,
+  pub num_rows: u32,
+  pub rep_levels_byte_len: u32,
+  pub def_levels_byte_len: u32,
+  pub is_compressed: bool
+}
+
+impl DataPageV2Header {
+  /// Extracts header fields from data page V2, returns `None` for other pages.
+  pub fn from_page(page: &Page) -> Option<Self> {
+    match page {
+      &Page::DataPageV2 {
+        num_values,
+        num_nulls,
+        num_rows,
+        def_levels_byte_len,
+        rep_levels_byte_len,
+        is_compressed,
+        ..
+      } => Some(DataPageV2Header {
+        num_values: num_values,
+        num_nulls: num_nulls,
+        num_rows: num_rows,
+        rep_levels_byte_len: rep_levels_byte_len,
+        def_levels_byte_len: def_levels_byte_len,
+        is_compressed: is_compressed
+      }),
+      _ => None
     }
   }
+
+  /// Returns total number of bytes of repetition and definition levels.
+  #[inline]
+  pub fn levels_byte_len(&self) -> usize {
+    self.rep_levels_byte_len as usize + self.def_levels_byte_len as usize
+  }
+
+  /// Returns offset of the values region within the page buffer, levels are stored
+  /// uncompressed before values.
+  #[inline]
+  pub fn values_offset(&self) -> usize {
+    self.levels_byte_len()
+  }
+
+  /// Returns offset and length of the values region within the page buffer of
+  /// `page_len` bytes. Returns error if levels do not fit into the page.
+  pub fn values_range(&self, page_len: usize) -> Result<(usize, usize)> {
+    let offset = self.values_offset();
+    if offset > page_len {
+      return Err(general_err!(
+        "Levels length {} exceeds page length {}",
+        offset,
+        page_len
+      ));
+    }
+    Ok((offset, page_len - offset))
+  }
 }
 
 /// A decoder for definition/repetition levels.
@@ -159,10 +424,14 @@ impl LevelDecoder {
   /// This method only initializes level decoder, `set_data()` method must be called
   /// before reading any value.
   ///
+  /// When `max_level` is 0, levels are not stored in the page: decoder does not read
+  /// any bytes and returns all levels as 0.
+  ///
   /// Panics if encoding is not supported
   pub fn new(encoding: Encoding, max_level: i16) -> Self {
-    let bit_width = log2(max_level as u64 + 1) as u8;
+    let bit_width = bit_width_for_max(max_level as u64);
     let decoder = match encoding {
+      _ if max_level == 0 => InternalDecoder::EMPTY,
       Encoding::RLE => InternalDecoder::RLE(RleDecoder::new(bit_width)),
       Encoding::BIT_PACKED => InternalDecoder::BIT_PACKED(BitReader::from(Vec::new())),
       _ => panic!("Unsupported encoding type {}", encoding),
@@ -176,7 +445,8 @@ impl LevelDecoder {
   /// of values that is expected.
   ///
   /// Both RLE and BIT_PACKED level decoders set `num_buffered_values` as total number of
-  /// values that they can return and track num values.
+  /// values that they can return and track num values. If max level is 0, no bytes are
+  /// read from `data` and 0 is returned.
   #[inline]
   pub fn set_data(&mut self, num_buffered_values: usize, data: ByteBufferPtr) -> usize {
     self.num_values = Some(num_buffered_values);
@@ -195,6 +465,7 @@ impl LevelDecoder {
         bit_packed_decoder.reset(data.range(data.start(), data_size));
         data_size
       },
+      InternalDecoder::EMPTY => 0
     }
   }
 
@@ -215,6 +486,10 @@ impl LevelDecoder {
         self.num_values = Some(num_buffered_values);
         len
       },
+      InternalDecoder::EMPTY => {
+        self.num_values = Some(num_buffered_values);
+        0
+      },
       _ => panic!("set_data_range() method is only supported by RLE encoding type")
     }
   }
@@ -240,6 +515,13 @@ impl LevelDecoder {
           &mut buffer[..len], self.bit_width as usize
         );
         values_read
+      },
+      InternalDecoder::EMPTY => {
+        let len = cmp::min(self.num_values.unwrap(), buffer.len());
+        for level in &mut buffer[..len] {
+          *level = 0;
+        }
+        len
       }
     };
     // Update current num_values
@@ -252,7 +534,16 @@ impl LevelDecoder {
 #[cfg(test)]
 mod tests {
   use super::*;
-  use util::test_common::random_numbers_range;
+
+  use std::rc::Rc;
+
+  use basic::{LogicalType, Type as PhysicalType};
+  use data_type::{ByteArray, ByteArrayType, Int32Type};
+  use encodings::decoding::{Decoder, PlainDecoder};
+  use encodings::encoding::PlainEncoder;
+  use schema::types::{ColumnDescriptor, ColumnPath, Type as SchemaType};
+  use util::memory::MemTracker;
+  use util::test_common::{make_col_desc, random_numbers_range};
 
   fn test_internal_roundtrip(enc: Encoding, levels: &[i16], max_level: i16) {
     let size = LevelEncoder::max_buffer_size(enc, max_level, levels.len());
@@ -459,4 +750,325 @@ mod tests {
     let mut buffer = vec![0; 16];
     decoder.get(&mut buffer).unwrap();
   }
+
+  fn test_page_size_tracker(enc: Encoding, data_page_v2: bool) {
+    let num_values = 1000;
+    let rep_levels: Vec<i16> = (0..num_values).map(|i| (i % 2) as i16).collect();
+    let def_levels: Vec<i16> = (0..num_values).map(|i| (i % 3) as i16).collect();
+    let values: Vec<i32> = (0..num_values).map(|i| i as i32).collect();
+
+    let size = LevelEncoder::max_buffer_size(enc, 1, num_values);
+    let mut rep_encoder = LevelEncoder::new(enc, 1, vec![0; size]);
+    let size = LevelEncoder::max_buffer_size(enc, 2, num_values);
+    let mut def_encoder = LevelEncoder::new(enc, 2, vec![0; size]);
+    let ty = SchemaType::primitive_type_builder("t", PhysicalType::INT32)
+      .build()
+      .unwrap();
+    let desc = ColumnDescriptor::new(Rc::new(ty), None, 2, 1, ColumnPath::new(vec![]));
+    let mut encoder =
+      PlainEncoder::<Int32Type>::new(Rc::new(desc), Rc::new(MemTracker::new()), vec![]);
+
+    let mut tracker = PageSizeTracker::new(data_page_v2);
+    assert_eq!(tracker.total_estimated_bytes(), 0);
+
+    rep_encoder.put(&rep_levels).unwrap();
+    def_encoder.put(&def_levels).unwrap();
+    encoder.put(&values).unwrap();
+    tracker.update_rep_levels(&rep_encoder);
+    tracker.update_def_levels(&def_encoder);
+    tracker.update_values::<Int32Type>(&encoder);
+    let estimated = tracker.total_estimated_bytes();
+
+    // Assemble page body
+    let mut page = Vec::new();
+    for levels in vec![rep_encoder.consume().unwrap(), def_encoder.consume().unwrap()] {
+      if data_page_v2 && enc == Encoding::RLE {
+        // Data page V2 does not store length of the levels
+        page.extend_from_slice(&levels[mem::size_of::<i32>()..]);
+      } else {
+        page.extend_from_slice(&levels);
+      }
+    }
+    let levels_len = page.len();
+    page.extend_from_slice(encoder.flush_buffer().unwrap().data());
+
+    assert_eq!(tracker.levels_byte_len(), levels_len);
+    assert_eq!(tracker.values_byte_len(), values.len() * mem::size_of::<i32>());
+    assert_eq!(estimated, page.len());
+
+    tracker.reset();
+    assert_eq!(tracker.total_estimated_bytes(), 0);
+  }
+
+  #[test]
+  fn test_page_size_tracker_v1() {
+    test_page_size_tracker(Encoding::RLE, false);
+    test_page_size_tracker(Encoding::BIT_PACKED, false);
+  }
+
+  #[test]
+  fn test_page_size_tracker_v2() {
+    test_page_size_tracker(Encoding::RLE, true);
+  }
+
+  #[test]
+  fn test_level_encoder_bytes_written() {
+    let mut levels = vec![];
+    random_numbers_range::<i16>(1000, 0, 4, &mut levels);
+    for enc in &[Encoding::RLE, Encoding::BIT_PACKED] {
+      let size = LevelEncoder::max_buffer_size(*enc, 3, levels.len());
+      let mut encoder = LevelEncoder::new(*enc, 3, vec![0; size]);
+      encoder.put(&levels).unwrap();
+      let bytes_written = encoder.bytes_written();
+      assert_eq!(bytes_written, encoder.consume().unwrap().len());
+    }
+  }
+
+  #[test]
+  fn test_level_encoder_bytes_written_pending_values() {
+    // Level count is not a multiple of 8, levels end with a long run that is not written
+    // until the encoder is flushed
+    let mut levels = vec![];
+    random_numbers_range::<i16>(1003, 0, 4, &mut levels);
+    levels.extend(vec![2; 500]);
+    for &(enc, num_levels) in &[
+      (Encoding::RLE, 1003),
+      (Encoding::RLE, 1503),
+      (Encoding::RLE, 1005),
+      (Encoding::BIT_PACKED, 1503)
+    ] {
+      let size = LevelEncoder::max_buffer_size(enc, 3, num_levels);
+      let mut encoder = LevelEncoder::new(enc, 3, vec![0; size]);
+      encoder.put(&levels[..num_levels]).unwrap();
+      let bytes_written = encoder.bytes_written();
+      let len = encoder.consume().unwrap().len();
+      assert!(bytes_written >= len, "{}: {} < {}", enc, bytes_written, len);
+      if enc == Encoding::BIT_PACKED {
+        assert_eq!(bytes_written, len);
+      }
+    }
+
+    // Page size tracker does not undercount levels of a long run
+    let mut tracker = PageSizeTracker::new(true);
+    let mut encoder = LevelEncoder::new(Encoding::RLE, 1, vec![0; 1024]);
+    encoder.put(&vec![1; 500]).unwrap();
+    tracker.update_def_levels(&encoder);
+    assert!(tracker.levels_byte_len() >= encoder.consume().unwrap().len() - 4);
+  }
+
+  #[test]
+  fn test_data_page_v2_header() {
+    let header = DataPageV2Header {
+      num_values: 10,
+      num_nulls: 2,
+      num_rows: 4,
+      rep_levels_byte_len: 3,
+      def_levels_byte_len: 5,
+      is_compressed: true
+    };
+    assert_eq!(header.levels_byte_len(), 8);
+    assert_eq!(header.values_offset(), 8);
+    assert_eq!(header.values_range(20).unwrap(), (8, 12));
+    assert_eq!(header.values_range(8).unwrap(), (8, 0));
+    assert_eq!(
+      header.values_range(7).unwrap_err(),
+      general_err!("Levels length 8 exceeds page length 7")
+    );
+
+    let page = Page::DataPageV2 {
+      buf: ByteBufferPtr::new(vec![0; 20]),
+      num_values: 10,
+      encoding: Encoding::PLAIN,
+      num_nulls: 2,
+      num_rows: 4,
+      def_levels_byte_len: 5,
+      rep_levels_byte_len: 3,
+      is_compressed: true
+    };
+    assert_eq!(DataPageV2Header::from_page(&page), Some(header));
+
+    let page = Page::DictionaryPage {
+      buf: ByteBufferPtr::new(vec![0; 20]),
+      num_values: 10,
+      encoding: Encoding::PLAIN,
+      is_sorted: false
+    };
+    assert_eq!(DataPageV2Header::from_page(&page), None);
+  }
+
+  #[test]
+  fn test_empty_levels() {
+    // Flat optional column: no repetition levels, definition levels with max level 1
+    let num_values = 100;
+    let rep_levels = vec![0; num_values];
+    let def_levels: Vec<i16> = (0..num_values).map(|i| (i % 3 != 0) as i16).collect();
+
+    for enc in &[Encoding::RLE, Encoding::BIT_PACKED] {
+      assert_eq!(LevelEncoder::max_buffer_size(*enc, 0, num_values), 0);
+      let mut rep_encoder = LevelEncoder::new(*enc, 0, vec![]);
+      assert_eq!(rep_encoder.put(&rep_levels).unwrap(), num_values);
+      assert_eq!(rep_encoder.bytes_written(), 0);
+      assert_eq!(
+        rep_encoder.put(&[0, 1]).unwrap_err(),
+        general_err!("Level 1 exceeds max level 0")
+      );
+
+      let size = LevelEncoder::max_buffer_size(*enc, 1, num_values);
+      let mut def_encoder = LevelEncoder::new(*enc, 1, vec![0; size]);
+      def_encoder.put(&def_levels).unwrap();
+
+      let mut tracker = PageSizeTracker::new(false);
+      tracker.update_rep_levels(&rep_encoder);
+      tracker.update_def_levels(&def_encoder);
+      assert_eq!(tracker.levels_byte_len(), def_encoder.bytes_written());
+
+      let rep_bytes = rep_encoder.consume().unwrap();
+      assert!(rep_bytes.is_empty());
+      let def_bytes = def_encoder.consume().unwrap();
+      let def_len = def_bytes.len();
+
+      // Page starts with definition levels, since repetition levels are omitted
+      let buffer = ByteBufferPtr::new(def_bytes);
+      let mut rep_decoder = LevelDecoder::new(*enc, 0);
+      assert_eq!(rep_decoder.set_data(num_values, buffer.all()), 0);
+      let mut def_decoder = LevelDecoder::new(*enc, 1);
+      assert_eq!(def_decoder.set_data(num_values, buffer.all()), def_len);
+
+      let mut result = vec![1; num_values + 10];
+      assert_eq!(rep_decoder.get(&mut result).unwrap(), num_values);
+      assert_eq!(&result[..num_values], &rep_levels[..]);
+      assert_eq!(rep_decoder.get(&mut result).unwrap(), 0);
+      assert_eq!(def_decoder.get(&mut result).unwrap(), num_values);
+      assert_eq!(&result[..num_values], &def_levels[..]);
+    }
+
+    let mut decoder = LevelDecoder::new(Encoding::RLE, 0);
+    let buffer = ByteBufferPtr::new(vec![]);
+    assert_eq!(decoder.set_data_range(5, &buffer, 0, 0), 0);
+    let mut result = vec![1; 3];
+    assert_eq!(decoder.get(&mut result).unwrap(), 3);
+    assert_eq!(result, vec![0; 3]);
+  }
+
+  #[test]
+  fn test_options_to_levels_and_values() {
+    let values = vec![Some(1), None, None, Some(2), Some(3), None];
+    let (def_levels, non_null_values) =
+      options_to_levels_and_values::<Int32Type>(&values, 1);
+    assert_eq!(def_levels, vec![1, 0, 0, 1, 1, 0]);
+    assert_eq!(non_null_values, vec![1, 2, 3]);
+
+    let (def_levels, non_null_values) =
+      options_to_levels_and_values::<Int32Type>(&values, 3);
+    assert_eq!(def_levels, vec![3, 2, 2, 3, 3, 2]);
+    assert_eq!(non_null_values, vec![1, 2, 3]);
+
+    let values = vec![None, Some(ByteArray::from("a")), None];
+    let (def_levels, non_null_values) =
+      options_to_levels_and_values::<ByteArrayType>(&values, 1);
+    assert_eq!(def_levels, vec![0, 1, 0]);
+    assert_eq!(non_null_values, vec![ByteArray::from("a")]);
+
+    let (def_levels, non_null_values) =
+      options_to_levels_and_values::<Int32Type>(&[], 1);
+    assert!(def_levels.is_empty());
+    assert!(non_null_values.is_empty());
+  }
+
+  #[test]
+  fn test_count_nulls() {
+    assert_eq!(count_nulls(&[1, 0, 0, 1, 1, 0, 1], 1), 3);
+    assert_eq!(count_nulls(&[3, 2, 0, 3, 1, 3], 3), 3);
+    assert_eq!(count_nulls(&[3, 3, 3], 3), 0);
+    assert_eq!(count_nulls(&[], 2), 0);
+    // Required column has no nulls
+    assert_eq!(count_nulls(&[0, 0, 0], 0), 0);
+  }
+
+  #[test]
+  fn test_encode_all_null_page() {
+    for &(num_values, max_def_level) in &[(0, 1), (1, 1), (1000, 1), (1000, 3)] {
+      let (levels, null_count) = encode_all_null_page(num_values, max_def_level).unwrap();
+      assert_eq!(null_count, num_values as u64);
+
+      let body = build_v1_page_body(None, Some(ByteBufferPtr::new(levels)),
+        ByteBufferPtr::new(vec![]));
+      let mut decoder = LevelDecoder::new(Encoding::RLE, max_def_level);
+      let offset = decoder.set_data(num_values, body.all());
+      let mut def_levels = vec![max_def_level; num_values];
+      assert_eq!(decoder.get(&mut def_levels).unwrap(), num_values);
+      assert_eq!(count_nulls(&def_levels, max_def_level), num_values as u64);
+      // No values are stored in the page
+      assert_eq!(offset, body.len());
+      let mut decoder = PlainDecoder::<Int32Type>::new(-1);
+      decoder.set_data(body.start_from(offset), 0).unwrap();
+      assert_eq!(decoder.get(&mut [0; 1]).unwrap(), 0);
+    }
+
+    assert_eq!(
+      encode_all_null_page(10, 0).unwrap_err(),
+      general_err!("Column with max definition level 0 cannot contain nulls")
+    );
+  }
+
+  #[test]
+  #[should_panic(expected = "Max definition level must be positive")]
+  fn test_options_to_levels_and_values_required() {
+    options_to_levels_and_values::<Int32Type>(&[Some(1)], 0);
+  }
+
+  #[test]
+  fn test_build_v1_page_body() {
+    let rep_levels = vec![0, 1, 1, 0, 1, 0, 0, 1, 1, 1];
+    let def_levels = vec![2, 2, 1, 0, 2, 2, 1, 2, 2, 0];
+    let values = vec![1, 2, 3, 4, 5, 6];
+    let num_levels = rep_levels.len();
+
+    let encode_levels = |levels: &[i16], max_level: i16| {
+      let size = LevelEncoder::max_buffer_size(Encoding::RLE, max_level, levels.len());
+      let mut encoder = LevelEncoder::new(Encoding::RLE, max_level, vec![0; size]);
+      encoder.put(levels).unwrap();
+      ByteBufferPtr::new(encoder.consume().unwrap())
+    };
+    let desc = make_col_desc("a", PhysicalType::INT32, LogicalType::NONE, 0, 2, 1);
+    let mut encoder =
+      PlainEncoder::<Int32Type>::new(desc, Rc::new(MemTracker::new()), vec![]);
+    encoder.put(&values).unwrap();
+    let values_buf = encoder.flush_buffer().unwrap();
+
+    // Decodes levels and values from the body, absent level streams have max level 0
+    let decode = |body: ByteBufferPtr, max_rep: i16, max_def: i16| {
+      let mut offset = 0;
+      let mut levels = vec![];
+      for max_level in &[max_rep, max_def] {
+        let mut decoder = LevelDecoder::new(Encoding::RLE, *max_level);
+        offset += decoder.set_data(num_levels, body.start_from(offset));
+        let mut buffer = vec![0; num_levels];
+        decoder.get(&mut buffer).unwrap();
+        levels.push(buffer);
+      }
+      let mut decoder = PlainDecoder::<Int32Type>::new(-1);
+      decoder.set_data(body.start_from(offset), values.len()).unwrap();
+      let mut result = vec![0; values.len()];
+      assert_eq!(decoder.get(&mut result).unwrap(), values.len());
+      (levels, result)
+    };
+
+    let body = build_v1_page_body(
+      Some(encode_levels(&rep_levels, 1)),
+      Some(encode_levels(&def_levels, 2)),
+      values_buf.all()
+    );
+    let (levels, result) = decode(body, 1, 2);
+    assert_eq!(levels, vec![rep_levels, def_levels.clone()]);
+    assert_eq!(result, values);
+
+    // Repetition levels are absent
+    let rep_buf = encode_levels(&[0; 10], 0);
+    assert_eq!(rep_buf.len(), 0);
+    let body = build_v1_page_body(None, Some(encode_levels(&def_levels, 2)), values_buf);
+    let (levels, result) = decode(body, 0, 2);
+    assert_eq!(levels, vec![vec![0; num_levels], def_levels]);
+    assert_eq!(result, values);
+  }
 }
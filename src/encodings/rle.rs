@@ -45,6 +45,13 @@ use util::memory::ByteBufferPtr;
 const MAX_GROUPS_PER_BIT_PACKED_RUN: usize = 1 << 6;
 const MAX_VALUES_PER_BIT_PACKED_RUN: usize = MAX_GROUPS_PER_BIT_PACKED_RUN * 8;
 const MAX_WRITER_BUF_SIZE: usize = 1 << 10;
+/// Default minimum number of repeated values encoded as RLE run. Values are buffered in
+/// groups of 8, and a run is detected once a whole group has the same value.
+pub const MIN_RLE_RUN_LENGTH: usize = 8;
+/// Largest minimum RLE run length, see `RleEncoder::with_min_run_length()`. A run that
+/// is too short to be RLE encoded is bit-packed at once, and must fit into the space
+/// that the encoder reserves for a single bit-packed run.
+pub const MAX_MIN_RLE_RUN_LENGTH: usize = MAX_VALUES_PER_BIT_PACKED_RUN;
 
 /// A RLE/Bit-Packing hybrid encoder.
 // TODO: tracking memory usage
@@ -80,7 +87,10 @@ pub struct RleEncoder {
   bit_packed_count: usize,
 
   // The position of the indicator byte in the `bit_writer`.
-  indicator_byte_pos: i64
+  indicator_byte_pos: i64,
+
+  // Runs shorter than this are bit-packed, see `with_min_run_length()`.
+  min_run_length: usize
 }
 
 impl RleEncoder {
@@ -110,10 +120,35 @@ impl RleEncoder {
       current_value: 0,
       repeat_count: 0,
       bit_packed_count: 0,
-      indicator_byte_pos: -1
+      indicator_byte_pos: -1,
+      min_run_length: MIN_RLE_RUN_LENGTH
     }
   }
 
+  /// Sets minimum number of repeated values that are encoded as RLE run, 8 by default,
+  /// and returns itself. Shorter runs are bit-packed together with the surrounding
+  /// values, which avoids the overhead of switching between short RLE runs and
+  /// bit-packed runs. Output is a valid RLE/bit-packing hybrid for any setting.
+  ///
+  /// Panics if `min_run_length` is less than 8, because runs are detected in groups of
+  /// 8 values, or greater than `MAX_MIN_RLE_RUN_LENGTH`.
+  pub fn with_min_run_length(mut self, min_run_length: usize) -> Self {
+    assert!(
+      min_run_length >= MIN_RLE_RUN_LENGTH,
+      "Minimum RLE run length {} must be at least {}",
+      min_run_length,
+      MIN_RLE_RUN_LENGTH
+    );
+    assert!(
+      min_run_length <= MAX_MIN_RLE_RUN_LENGTH,
+      "Minimum RLE run length {} must be at most {}",
+      min_run_length,
+      MAX_MIN_RLE_RUN_LENGTH
+    );
+    self.min_run_length = min_run_length;
+    self
+  }
+
   /// Returns the minimum buffer size needed to use the encoder for `bit_width`.
   /// This is the maximum length of a single run for `bit_width`.
   pub fn min_buffer_size(bit_width: u8) -> usize {
@@ -156,10 +191,13 @@ impl RleEncoder {
         return Ok(true);
       }
     } else {
-      if self.repeat_count >= 8 {
+      if self.repeat_count >= self.min_run_length {
         // The current RLE run has ended and we've gathered enough. Flush first.
         assert_eq!(self.bit_packed_count, 0);
         self.flush_rle_run()?;
+      } else if self.repeat_count >= 8 {
+        // The current run has ended, but it is too short to be encoded as RLE run.
+        self.bit_pack_run()?;
       }
       self.repeat_count = 1;
       self.current_value = value;
@@ -176,6 +214,72 @@ impl RleEncoder {
     Ok(true)
   }
 
+  /// Adds `count` repetitions of `value` to the current RLE run in one step, if `value`
+  /// continues the run, i.e. putting it would not buffer the value. The result is the
+  /// same as calling `put` `count` times. Returns false, if `value` does not continue
+  /// the current run, in which case nothing is encoded.
+  #[inline]
+  pub fn extend_run(&mut self, value: u64, count: usize) -> bool {
+    if self.buffer_full || self.current_value != value || self.repeat_count < 8 {
+      return false;
+    }
+    self.repeat_count += count;
+    true
+  }
+
+  /// Encodes `value` the same way as `put`, but first checks that the buffer has
+  /// enough space left for the largest possible runs. Once it does not, the encoder is
+  /// marked as full and this method returns false without encoding the value; values
+  /// that have already been put can still be flushed.
+  ///
+  /// Use `buffer_full_ratio` to grow or flush the buffer before this happens.
+  #[inline]
+  pub fn put_checked(&mut self, value: u64) -> Result<bool> {
+    // A single `put` may flush a run, and the values buffered after it need to be
+    // flushed as another run, so we reserve space for two runs.
+    let remaining = self.bit_writer.capacity() - self.bit_writer.bytes_written();
+    if remaining < 2 * self.max_run_byte_size {
+      self.buffer_full = true;
+    }
+    self.put(value)
+  }
+
+  /// Encodes `value` the same way as `put_checked`, but grows the underlying buffer
+  /// instead of returning false when there is not enough space left, so any number of
+  /// values can be encoded. Buffer size is at least doubled every time it grows.
+  #[inline]
+  pub fn put_growing(&mut self, value: u64) -> Result<()> {
+    if !self.put_checked(value)? {
+      let increment = cmp::max(self.capacity(), 2 * self.max_run_byte_size);
+      self.extend_buffer(increment);
+      if !self.put_checked(value)? {
+        return Err(general_err!("RLE buffer is full"));
+      }
+    }
+    Ok(())
+  }
+
+  /// Extends the underlying buffer by `increment` bytes and resets the full flag set by
+  /// `put_checked`, so that encoding can continue without flushing.
+  #[inline]
+  pub fn extend_buffer(&mut self, increment: usize) {
+    self.bit_writer.extend(increment);
+    self.buffer_full = false;
+  }
+
+  /// Returns size of the underlying buffer in bytes.
+  #[inline]
+  pub fn capacity(&self) -> usize {
+    self.bit_writer.capacity()
+  }
+
+  /// Returns the fraction of the buffer that has been written so far, between 0.0 and
+  /// 1.0. Values buffered by the encoder that are not flushed yet are not included.
+  #[inline]
+  pub fn buffer_full_ratio(&self) -> f32 {
+    self.bit_writer.bytes_written() as f32 / self.bit_writer.capacity() as f32
+  }
+
   #[inline]
   pub fn buffer(&self) -> &[u8] {
     self.bit_writer.buffer()
@@ -186,6 +290,31 @@ impl RleEncoder {
     self.bit_writer.bytes_written()
   }
 
+  /// Returns an upper bound of the number of bytes that `flush()` would write at this
+  /// point. Unlike `len()`, this includes the values buffered in the current group and
+  /// the current run, which are not written until the run ends or the encoder is
+  /// flushed.
+  pub fn max_flushed_len(&self) -> usize {
+    let bit_width = self.bit_width as i64;
+    let mut pending_len = 0;
+    if self.repeat_count >= 8 {
+      // Current run is written either as RLE run or bit-packed, if it is too short
+      let rle_run_len =
+        bit_util::MAX_VLQ_BYTE_LEN + bit_util::ceil(bit_width, 8) as usize;
+      let num_groups = bit_util::ceil(self.repeat_count as i64, 8) as usize;
+      // Bit-packed runs hold at most 63 groups, each run starts with an indicator byte
+      let num_indicators = num_groups / (MAX_GROUPS_PER_BIT_PACKED_RUN - 1) + 1;
+      let bit_packed_len = num_groups * bit_width as usize + num_indicators;
+      pending_len += cmp::max(rle_run_len, bit_packed_len);
+    }
+    if self.num_buffered_values > 0 {
+      // Buffered values are padded to a group of 8 values, which may start a new
+      // bit-packed run, or written as a short RLE run, which is not longer
+      pending_len += 1 + bit_width as usize;
+    }
+    self.len() + pending_len
+  }
+
   #[inline]
   pub fn consume(mut self) -> Result<Vec<u8>> {
     self.flush()?;
@@ -216,6 +345,9 @@ impl RleEncoder {
   /// internal writer.
   #[inline]
   pub fn flush(&mut self) -> Result<()> {
+    if self.repeat_count >= 8 && self.repeat_count < self.min_run_length {
+      self.bit_pack_run()?;
+    }
     if self.bit_packed_count > 0 || self.repeat_count > 0 ||
         self.num_buffered_values > 0 {
       let all_repeat = self.bit_packed_count == 0 &&
@@ -261,7 +393,9 @@ impl RleEncoder {
 
     // Write all buffered values as bit-packed literals
     for i in 0..self.num_buffered_values {
-      let _ = self.bit_writer.put_value(self.buffered_values[i], self.bit_width as usize);
+      if !self.bit_writer.put_value(self.buffered_values[i], self.bit_width as usize) {
+        return Err(general_err!("Not enough space to write bit-packed values"));
+      }
     }
     self.num_buffered_values = 0;
     if update_indicator_byte {
@@ -278,6 +412,23 @@ impl RleEncoder {
     Ok(())
   }
 
+  // Bit-packs the current run of `repeat_count` values, which starts at the beginning
+  // of a group, instead of encoding it as RLE run. Values of the run that do not fill
+  // a whole group are left buffered.
+  fn bit_pack_run(&mut self) -> Result<()> {
+    let num_values = self.repeat_count;
+    self.repeat_count = 0;
+    self.num_buffered_values = 0;
+    for _ in 0..num_values {
+      self.buffered_values[self.num_buffered_values] = self.current_value;
+      self.num_buffered_values += 1;
+      if self.num_buffered_values == 8 {
+        self.flush_buffered_values()?;
+      }
+    }
+    Ok(())
+  }
+
   #[inline]
   fn flush_buffered_values(&mut self) -> Result<()> {
     if self.repeat_count >= 8 {
@@ -434,6 +585,9 @@ impl RleDecoder {
         assert!(self.current_value.is_some());
         let num_values = cmp::min(max_values - values_read, self.rle_left as usize);
         let dict_idx = self.current_value.unwrap() as usize;
+        if dict_idx >= dict.len() {
+          return Err(Self::dict_index_err(dict_idx, dict.len()));
+        }
         for i in 0..num_values {
           buffer[values_read + i] = dict[dict_idx].clone();
         }
@@ -452,7 +606,11 @@ impl RleDecoder {
               &mut index_buf[..num_values], self.bit_width as usize
             );
             for i in 0..num_values {
-              buffer[values_read + i] = dict[index_buf[i] as usize].clone();
+              let dict_idx = index_buf[i] as usize;
+              if dict_idx >= dict.len() {
+                return Err(Self::dict_index_err(dict_idx, dict.len()));
+              }
+              buffer[values_read + i] = dict[dict_idx].clone();
             }
             self.bit_packed_left -= num_values as u32;
             values_read += num_values;
@@ -471,6 +629,15 @@ impl RleDecoder {
     Ok(values_read)
   }
 
+  #[inline]
+  fn dict_index_err(index: usize, dict_len: usize) -> ParquetError {
+    general_err!(
+      "Dictionary index {} is out of range for dictionary with {} entries",
+      index,
+      dict_len
+    )
+  }
+
   #[inline]
   fn reload(&mut self) -> bool {
     assert!(self.bit_reader.is_some());
@@ -576,6 +743,37 @@ mod tests {
     assert_eq!(buffer, expected);
   }
 
+  #[test]
+  fn test_rle_decode_bit_packed_only() {
+    // Two bit-packed runs with bit width 3 and no RLE runs, 20 values in total:
+    // 1 group with values 0..8, followed by 2 groups with 12 values and 4 padding 0s
+    let data = ByteBufferPtr::new(
+      vec![0x03, 0x88, 0xC6, 0xFA, 0x05, 0x77, 0x39, 0x05, 0xD1, 0x08, 0x00]
+    );
+    let expected = vec![0, 1, 2, 3, 4, 5, 6, 7, 7, 6, 5, 4, 3, 2, 1, 0, 1, 2, 3, 4];
+
+    let mut decoder = RleDecoder::new(3);
+    decoder.set_data(data.all());
+    let mut buffer = vec![0; 20];
+    assert_eq!(decoder.get_batch::<i32>(&mut buffer).unwrap(), 20);
+    assert_eq!(buffer, expected);
+
+    // Batches that do not align with groups or runs
+    let mut decoder = RleDecoder::new(3);
+    decoder.set_data(data.all());
+    let mut buffer = vec![0; 20];
+    for chunk in buffer.chunks_mut(3) {
+      assert_eq!(decoder.get_batch::<i32>(chunk).unwrap(), chunk.len());
+    }
+    assert_eq!(buffer, expected);
+
+    let mut decoder = RleDecoder::new(3);
+    decoder.set_data(data);
+    for value in &expected {
+      assert_eq!(decoder.get::<i32>().unwrap(), Some(*value));
+    }
+  }
+
   #[test]
   fn test_rle_decode_with_dict_int32() {
     // Test RLE encoding: 3 0s followed by 4 1s followed by 5 2s
@@ -737,6 +935,90 @@ mod tests {
     assert_eq!(actual_values, values);
   }
 
+  #[test]
+  fn test_rle_min_run_length() {
+    let values: Vec<u64> = vec![0; 12].into_iter().chain(vec![1; 4]).collect();
+    let encode = |min_run_length: usize| {
+      let mut encoder = RleEncoder::new(1, 256).with_min_run_length(min_run_length);
+      for v in &values {
+        assert!(encoder.put(*v).unwrap());
+      }
+      encoder.consume().unwrap()
+    };
+
+    // RLE runs of 12 zeros and 4 ones
+    let rle = encode(8);
+    assert_eq!(rle, vec![12 << 1, 0, 4 << 1, 1]);
+    // Run of 12 zeros is too short, all values are bit-packed in 2 groups
+    let bit_packed = encode(16);
+    assert_eq!(bit_packed, vec![(2 << 1) | 1, 0b00000000, 0b11110000]);
+
+    for buffer in vec![rle, bit_packed] {
+      let mut decoder = RleDecoder::new(1);
+      decoder.set_data(ByteBufferPtr::new(buffer));
+      let mut result = vec![0; values.len()];
+      assert_eq!(decoder.get_batch(&mut result[..]).unwrap(), values.len());
+      assert_eq!(result, values);
+    }
+
+    // Long runs are still encoded as RLE runs
+    let mut encoder = RleEncoder::new(1, 256).with_min_run_length(16);
+    for v in vec![0; 20].into_iter().chain(vec![1; 3]) {
+      assert!(encoder.put(v).unwrap());
+    }
+    assert_eq!(encoder.consume().unwrap(), vec![20 << 1, 0, 3 << 1, 1]);
+  }
+
+  #[test]
+  fn test_rle_max_min_run_length() {
+    // Run that is one value too short is bit-packed while the buffer grows
+    let values: Vec<u64> =
+      (0..96).chain(vec![100; MAX_MIN_RLE_RUN_LENGTH - 1]).chain(0..100).collect();
+    let buffer_len = RleEncoder::min_buffer_size(7);
+    let mut encoder = RleEncoder::new(7, buffer_len)
+      .with_min_run_length(MAX_MIN_RLE_RUN_LENGTH);
+    for v in &values {
+      encoder.put_growing(*v).unwrap();
+    }
+    assert!(encoder.capacity() > buffer_len);
+    let buffer = encoder.consume().unwrap();
+    // All values are bit-packed in 89 groups of 7 bytes and 3 runs
+    assert_eq!(buffer.len(), 89 * 7 + 3);
+
+    let mut decoder = RleDecoder::new(7);
+    decoder.set_data(ByteBufferPtr::new(buffer));
+    let mut result = vec![0; values.len()];
+    assert_eq!(decoder.get_batch(&mut result[..]).unwrap(), values.len());
+    assert_eq!(result, values);
+  }
+
+  #[test]
+  fn test_rle_bit_packed_values_do_not_fit() {
+    // The first bit-packed run of 504 values and the indicator byte of the next run
+    // leave space for 7 values only, the last value of the group does not fit, which is
+    // reported instead of truncating the output
+    let mut encoder = RleEncoder::new(8, RleEncoder::min_buffer_size(8));
+    for v in 0..511 {
+      assert!(encoder.put(v % 256).unwrap());
+    }
+    assert_eq!(
+      encoder.put(255).unwrap_err(),
+      general_err!("Not enough space to write bit-packed values")
+    );
+  }
+
+  #[test]
+  #[should_panic(expected = "Minimum RLE run length 7 must be at least 8")]
+  fn test_rle_invalid_min_run_length() {
+    RleEncoder::new(1, 256).with_min_run_length(7);
+  }
+
+  #[test]
+  #[should_panic(expected = "Minimum RLE run length 513 must be at most 512")]
+  fn test_rle_too_large_min_run_length() {
+    RleEncoder::new(1, 256).with_min_run_length(513);
+  }
+
   fn test_round_trip(values: &[i32], bit_width: u8) {
     let buffer_len = 64 * 1024;
     let mut encoder = RleEncoder::new(bit_width, buffer_len);
@@ -795,4 +1077,79 @@ mod tests {
       test_round_trip(&values[..], bit_width as u8);
     }
   }
+
+  #[test]
+  fn test_put_checked() {
+    let mut values: Vec<u64> = vec![];
+    for i in 0..1000 {
+      if i % 3 == 0 {
+        values.extend(vec![i % 5; 20]);
+      } else {
+        values.push(i % 7);
+      }
+    }
+    let bit_width = 3;
+
+    // Behaves like `put` when buffer is large enough
+    let mut encoder = RleEncoder::new(bit_width, 64 * 1024);
+    let mut checked_encoder = RleEncoder::new(bit_width, 64 * 1024);
+    for v in &values {
+      assert!(encoder.put(*v).unwrap());
+      assert!(checked_encoder.put_checked(*v).unwrap());
+    }
+    assert_eq!(checked_encoder.consume().unwrap(), encoder.consume().unwrap());
+
+    // Ratio increases while buffer fills up, and `put_checked` eventually returns false
+    let mut encoder = RleEncoder::new(bit_width, 1024);
+    let mut prev_ratio = encoder.buffer_full_ratio();
+    assert_eq!(prev_ratio, 0.0);
+    let mut num_values = 0;
+    for v in &values {
+      if !encoder.put_checked(*v).unwrap() {
+        break;
+      }
+      let ratio = encoder.buffer_full_ratio();
+      assert!(ratio >= prev_ratio && ratio <= 1.0);
+      prev_ratio = ratio;
+      num_values += 1;
+    }
+    assert!(num_values < values.len());
+    assert!(prev_ratio > 0.5, "ratio {} is too low", prev_ratio);
+    assert!(!encoder.put_checked(0).unwrap());
+
+    // Values that have been put can be flushed and decoded
+    let buffer = ByteBufferPtr::new(encoder.consume().unwrap());
+    let mut decoder = RleDecoder::new(bit_width);
+    decoder.set_data(buffer);
+    let mut result = vec![0u64; num_values];
+    assert_eq!(decoder.get_batch(&mut result[..]).unwrap(), num_values);
+    assert_eq!(&result[..], &values[..num_values]);
+  }
+
+  #[test]
+  fn test_put_growing() {
+    let mut rng = thread_rng();
+    for bit_width in &[1, 3, 8, 20, 32] {
+      let max_value = 1u64 << *bit_width;
+      let values: Vec<u64> = (0..10000).map(|i| {
+        // Mix of bit-packed values and RLE runs
+        if i % 100 < 50 { rng.gen::<u64>() % max_value } else { (i / 100) % max_value }
+      }).collect();
+
+      // Encoder starts with the smallest buffer and grows it as needed
+      let initial_len = RleEncoder::min_buffer_size(*bit_width);
+      let mut encoder = RleEncoder::new(*bit_width, initial_len);
+      for v in &values {
+        encoder.put_growing(*v).unwrap();
+      }
+      assert!(encoder.capacity() > initial_len);
+      let buffer = ByteBufferPtr::new(encoder.consume().unwrap());
+
+      let mut decoder = RleDecoder::new(*bit_width);
+      decoder.set_data(buffer);
+      let mut result = vec![0u64; values.len()];
+      assert_eq!(decoder.get_batch(&mut result[..]).unwrap(), values.len());
+      assert_eq!(result, values);
+    }
+  }
 }
@@ -52,6 +52,13 @@ quick_error! {
       display("EOF: {}", message)
       description(message)
     }
+    /// "Type mismatch" Parquet error.
+    /// Returned when data does not match the expected physical type, e.g. when a page
+    /// is too small to contain the declared number of values of the decoder type.
+    TypeMismatch(message: String) {
+      display("Type mismatch: {}", message)
+      description(message)
+    }
   }
 }
 
@@ -87,3 +94,9 @@ macro_rules! eof_err {
   ($fmt:expr) => (ParquetError::EOF($fmt.to_owned()));
   ($fmt:expr, $($args:expr),*) => (ParquetError::EOF(format!($fmt, $($args),*)));
 }
+
+macro_rules! type_mismatch_err {
+  ($fmt:expr) => (ParquetError::TypeMismatch($fmt.to_owned()));
+  ($fmt:expr, $($args:expr),*) => (
+    ParquetError::TypeMismatch(format!($fmt, $($args),*)));
+}
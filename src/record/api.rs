@@ -21,7 +21,7 @@ use std::fmt;
 
 use basic::{LogicalType, Type as PhysicalType};
 use chrono::{Local, TimeZone};
-use data_type::{ByteArray, Decimal, Int96};
+use data_type::{f16_from_bytes, ByteArray, Decimal, Int96};
 use errors::{ParquetError, Result};
 use num_bigint::{BigInt, Sign};
 use schema::types::ColumnDescPtr;
@@ -477,6 +477,11 @@ impl Field {
               descr.type_scale()
             ))
           },
+          LogicalType::FLOAT16 => {
+            let data = value.data();
+            Field::Float(f16_from_bytes([data[0], data[1]]))
+          },
+          LogicalType::UUID => Field::Bytes(value),
           _ => nyi!(descr, value)
         }
       },
@@ -739,6 +744,20 @@ mod tests {
     let value = ByteArray::from(vec![0, 0, 0, 0, 0, 4, 147, 224]);
     let row = Field::convert_byte_array(&descr, value.clone());
     assert_eq!(row, Field::Decimal(Decimal::from_bytes(value, 17, 5)));
+
+    // FLOAT16
+    let descr = make_column_descr![
+      PhysicalType::FIXED_LEN_BYTE_ARRAY, LogicalType::FLOAT16, 2, 0, 0];
+    let value = ByteArray::from(vec![0x00, 0xBC]);
+    let row = Field::convert_byte_array(&descr, value);
+    assert_eq!(row, Field::Float(-1.0));
+
+    // UUID
+    let descr = make_column_descr![
+      PhysicalType::FIXED_LEN_BYTE_ARRAY, LogicalType::UUID, 16, 0, 0];
+    let value = ByteArray::from(vec![7; 16]);
+    let row = Field::convert_byte_array(&descr, value.clone());
+    assert_eq!(row, Field::Bytes(value));
   }
 
   #[test]
@@ -21,6 +21,8 @@ use std::collections::HashMap;
 use std::convert::From;
 use std::fmt;
 use std::rc::Rc;
+use std::result;
+use std::str;
 
 use basic::{LogicalType, Repetition, Type as PhysicalType};
 use errors::{ParquetError, Result};
@@ -64,6 +66,26 @@ impl Type {
     PrimitiveTypeBuilder::new(name, physical_type)
   }
 
+  /// Creates primitive type with provided field name, physical type, repetition,
+  /// logical type and type length, which is only used for FIXED_LEN_BYTE_ARRAY and
+  /// INTERVAL. Shortcut for the same calls on
+  /// [`PrimitiveTypeBuilder`](`PrimitiveTypeBuilder`), and returns `Err` if the
+  /// attributes are not valid. DECIMAL also requires precision and scale, use the
+  /// builder to create DECIMAL types.
+  pub fn primitive(
+    name: &str,
+    physical_type: PhysicalType,
+    repetition: Repetition,
+    logical_type: LogicalType,
+    length: i32
+  ) -> Result<Type> {
+    PrimitiveTypeBuilder::new(name, physical_type)
+      .with_repetition(repetition)
+      .with_logical_type(logical_type)
+      .with_length(length)
+      .build()
+  }
+
   /// Creates group type builder with provided column name.
   pub fn group_type_builder(name: &str) -> GroupTypeBuilder {
     GroupTypeBuilder::new(name)
@@ -336,6 +358,16 @@ impl<'a> PrimitiveTypeBuilder<'a> {
           return Err(general_err!("INTERVAL can only annotate FIXED(12)"));
         }
       }
+      LogicalType::FLOAT16 => {
+        if self.physical_type != PhysicalType::FIXED_LEN_BYTE_ARRAY || self.length != 2 {
+          return Err(general_err!("FLOAT16 can only annotate FIXED(2)"));
+        }
+      }
+      LogicalType::UUID => {
+        if self.physical_type != PhysicalType::FIXED_LEN_BYTE_ARRAY || self.length != 16 {
+          return Err(general_err!("UUID can only annotate FIXED(16)"));
+        }
+      }
       LogicalType::ENUM => {
         if self.physical_type != PhysicalType::BYTE_ARRAY {
           return Err(general_err!("ENUM can only annotate BYTE_ARRAY fields"));
@@ -359,6 +391,23 @@ impl<'a> PrimitiveTypeBuilder<'a> {
   }
 }
 
+/// Returns the minimum number of bytes required to store unscaled value of DECIMAL with
+/// `precision` as two's complement, e.g. the minimum length of FIXED_LEN_BYTE_ARRAY
+/// column for the precision.
+///
+/// ```rust
+/// use parquet::schema::types::decimal_min_byte_width;
+///
+/// assert_eq!(decimal_min_byte_width(9), 4);
+/// assert_eq!(decimal_min_byte_width(38), 16);
+/// ```
+pub fn decimal_min_byte_width(precision: i32) -> usize {
+  assert!(precision > 0, "Invalid DECIMAL precision: {}", precision);
+  // Number of bits for the magnitude plus one bit for the sign
+  let num_bits = precision as f64 * 10f64.log2() + 1f64;
+  (num_bits / 8f64).ceil() as usize
+}
+
 /// A builder for group types. All attributes are optional except the name.
 /// Note that if not specified explicitly, `None` is used as the repetition of the group,
 /// which means it is a root (message) type.
@@ -497,6 +546,31 @@ impl ColumnPath {
   pub fn string(&self) -> String {
     self.parts.join(".")
   }
+
+  /// Returns field names of this column path.
+  pub fn parts(&self) -> &[String] {
+    &self.parts
+  }
+}
+
+/// Parses column path from a dotted string, e.g. "a.b.c" for field `c` nested in
+/// fields `a` and `b`. Empty string results in an empty path. `string()` returns the
+/// path in the same format, while `to_string()` returns it quoted.
+/// ```rust
+/// use parquet::schema::types::ColumnPath;
+///
+/// let path = "a.b.c".parse::<ColumnPath>().unwrap();
+/// assert_eq!(path.parts(), &["a".to_string(), "b".to_string(), "c".to_string()]);
+/// assert_eq!(&path.string(), "a.b.c");
+/// ```
+impl str::FromStr for ColumnPath {
+  type Err = ParquetError;
+  fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+    if s.is_empty() {
+      return Ok(ColumnPath::new(vec![]));
+    }
+    Ok(ColumnPath::new(s.split('.').map(|part| part.to_string()).collect()))
+  }
 }
 
 impl fmt::Display for ColumnPath {
@@ -568,6 +642,70 @@ impl ColumnDescriptor {
     }
   }
 
+  /// Creates new descriptor for leaf-level column, validating that maximum definition
+  /// and repetition levels are consistent with the repetition of the column.
+  ///
+  /// Levels must be non-negative, a repeated column must have a non-zero repetition
+  /// level, and repetition level can never exceed definition level. For a top-level
+  /// column (`path` with a single part) the levels are derived from the column's own
+  /// repetition only and must match exactly.
+  pub fn try_new(
+    primitive_type: TypePtr,
+    root_type: Option<TypePtr>,
+    max_def_level: i16,
+    max_rep_level: i16,
+    path: ColumnPath
+  ) -> Result<Self> {
+    if !primitive_type.is_primitive() {
+      return Err(general_err!(
+        "Column {}: expected primitive type, found group type",
+        path
+      ));
+    }
+    if max_def_level < 0 || max_rep_level < 0 {
+      return Err(general_err!(
+        "Column {}: invalid max levels (definition: {}, repetition: {})",
+        path,
+        max_def_level,
+        max_rep_level
+      ));
+    }
+    if max_rep_level > max_def_level {
+      return Err(general_err!(
+        "Column {}: max repetition level {} is greater than max definition level {}",
+        path,
+        max_rep_level,
+        max_def_level
+      ));
+    }
+
+    // Minimum levels contributed by the leaf field itself
+    let (min_def_level, min_rep_level) =
+      match primitive_type.get_basic_info().repetition() {
+        Repetition::REQUIRED => (0, 0),
+        Repetition::OPTIONAL => (1, 0),
+        Repetition::REPEATED => (1, 1)
+      };
+    let is_top_level = path.parts.len() == 1;
+    let is_consistent = if is_top_level {
+      max_def_level == min_def_level && max_rep_level == min_rep_level
+    } else {
+      max_def_level >= min_def_level && max_rep_level >= min_rep_level
+    };
+    if !is_consistent {
+      return Err(general_err!(
+        "Column {}: max levels (definition: {}, repetition: {}) are inconsistent with \
+          {} repetition",
+        path,
+        max_def_level,
+        max_rep_level,
+        primitive_type.get_basic_info().repetition()
+      ));
+    }
+
+    Ok(Self::new(primitive_type, root_type, max_def_level, max_rep_level, path))
+  }
+
   /// Returns maximum definition level for this column.
   pub fn max_def_level(&self) -> i16 {
     self.max_def_level
@@ -1044,6 +1182,35 @@ mod tests {
       assert_eq!(e.description(), "INTERVAL can only annotate FIXED(12)");
     }
 
+    result = Type::primitive_type_builder("foo", PhysicalType::FIXED_LEN_BYTE_ARRAY)
+      .with_repetition(Repetition::REQUIRED)
+      .with_logical_type(LogicalType::FLOAT16)
+      .with_length(4)
+      .build();
+    assert!(result.is_err());
+    if let Err(e) = result {
+      assert_eq!(e.description(), "FLOAT16 can only annotate FIXED(2)");
+    }
+
+    result = Type::primitive_type_builder("foo", PhysicalType::FIXED_LEN_BYTE_ARRAY)
+      .with_repetition(Repetition::REQUIRED)
+      .with_logical_type(LogicalType::UUID)
+      .with_length(12)
+      .build();
+    assert!(result.is_err());
+    if let Err(e) = result {
+      assert_eq!(e.description(), "UUID can only annotate FIXED(16)");
+    }
+
+    result = Type::primitive_type_builder("foo", PhysicalType::BYTE_ARRAY)
+      .with_repetition(Repetition::REQUIRED)
+      .with_logical_type(LogicalType::UUID)
+      .build();
+    assert!(result.is_err());
+    if let Err(e) = result {
+      assert_eq!(e.description(), "UUID can only annotate FIXED(16)");
+    }
+
     result = Type::primitive_type_builder("foo", PhysicalType::INT32)
       .with_repetition(Repetition::REQUIRED)
       .with_logical_type(LogicalType::ENUM)
@@ -1073,6 +1240,76 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_primitive_type_shortcut() {
+    let tp = Type::primitive(
+      "ts",
+      PhysicalType::INT64,
+      Repetition::OPTIONAL,
+      LogicalType::TIMESTAMP_MILLIS,
+      0
+    ).unwrap();
+    assert!(tp.is_primitive());
+    assert_eq!(tp.name(), "ts");
+    assert_eq!(tp.get_physical_type(), PhysicalType::INT64);
+    assert_eq!(tp.get_basic_info().repetition(), Repetition::OPTIONAL);
+    assert_eq!(tp.get_basic_info().logical_type(), LogicalType::TIMESTAMP_MILLIS);
+    assert_eq!(
+      tp,
+      Type::primitive_type_builder("ts", PhysicalType::INT64)
+        .with_repetition(Repetition::OPTIONAL)
+        .with_logical_type(LogicalType::TIMESTAMP_MILLIS)
+        .build()
+        .unwrap()
+    );
+
+    let tp = Type::primitive(
+      "interval",
+      PhysicalType::FIXED_LEN_BYTE_ARRAY,
+      Repetition::REQUIRED,
+      LogicalType::INTERVAL,
+      12
+    ).unwrap();
+    match tp {
+      Type::PrimitiveType { type_length, .. } => assert_eq!(type_length, 12),
+      _ => panic!("Expected primitive type")
+    }
+
+    let result = Type::primitive(
+      "ts",
+      PhysicalType::INT32,
+      Repetition::OPTIONAL,
+      LogicalType::TIMESTAMP_MILLIS,
+      0
+    );
+    assert_eq!(
+      result.unwrap_err(),
+      general_err!("TIMESTAMP_MILLIS can only annotate INT64")
+    );
+  }
+
+  #[test]
+  fn test_decimal_min_byte_width() {
+    assert_eq!(decimal_min_byte_width(1), 1);
+    assert_eq!(decimal_min_byte_width(2), 1);
+    assert_eq!(decimal_min_byte_width(3), 2);
+    assert_eq!(decimal_min_byte_width(9), 4);
+    assert_eq!(decimal_min_byte_width(10), 5);
+    assert_eq!(decimal_min_byte_width(18), 8);
+    assert_eq!(decimal_min_byte_width(19), 9);
+    assert_eq!(decimal_min_byte_width(38), 16);
+
+    // Minimum width is always accepted by the primitive type builder
+    for precision in 1..39 {
+      let result = Type::primitive_type_builder("foo", PhysicalType::FIXED_LEN_BYTE_ARRAY)
+        .with_logical_type(LogicalType::DECIMAL)
+        .with_length(decimal_min_byte_width(precision) as i32)
+        .with_precision(precision)
+        .build();
+      assert!(result.is_ok(), "precision {}: {:?}", precision, result);
+    }
+  }
+
   #[test]
   fn test_group_type() {
     let f1 = Type::primitive_type_builder("f1", PhysicalType::INT32)
@@ -1109,6 +1346,23 @@ mod tests {
     assert_eq!(tp.get_fields()[1].name(), "f2");
   }
 
+  #[test]
+  fn test_column_path_from_str() {
+    let path = "a.b.c".parse::<ColumnPath>().unwrap();
+    assert_eq!(path.parts(), &["a".to_string(), "b".to_string(), "c".to_string()]);
+    assert_eq!(&path.string(), "a.b.c");
+    assert_eq!(path.to_string(), "\"a.b.c\"");
+    assert_eq!(path.string().parse::<ColumnPath>().unwrap(), path);
+
+    let path = "a".parse::<ColumnPath>().unwrap();
+    assert_eq!(path, ColumnPath::from("a"));
+
+    let path = "".parse::<ColumnPath>().unwrap();
+    assert!(path.parts().is_empty());
+    assert_eq!(path, ColumnPath::new(vec![]));
+    assert_eq!(&path.string(), "");
+  }
+
   #[test]
   fn test_column_descriptor() {
     let result = test_column_descriptor_helper();
@@ -1146,6 +1400,80 @@ mod tests {
     Ok(())
   }
 
+  #[test]
+  fn test_column_descriptor_try_new() {
+    let optional = Rc::new(
+      Type::primitive_type_builder("a", PhysicalType::INT32)
+        .with_repetition(Repetition::OPTIONAL)
+        .build()
+        .unwrap()
+    );
+    let required = Rc::new(
+      Type::primitive_type_builder("b", PhysicalType::INT32)
+        .with_repetition(Repetition::REQUIRED)
+        .build()
+        .unwrap()
+    );
+    let repeated = Rc::new(
+      Type::primitive_type_builder("c", PhysicalType::INT32)
+        .with_repetition(Repetition::REPEATED)
+        .build()
+        .unwrap()
+    );
+
+    let descr =
+      ColumnDescriptor::try_new(optional.clone(), None, 1, 0, ColumnPath::from("a"));
+    assert!(descr.is_ok());
+    let descr = descr.unwrap();
+    assert_eq!(descr.max_def_level(), 1);
+    assert_eq!(descr.max_rep_level(), 0);
+
+    // Nested columns can have higher levels than their own repetition defines
+    let path = ColumnPath::new(vec!["x".to_string(), "c".to_string()]);
+    assert!(ColumnDescriptor::try_new(repeated.clone(), None, 3, 2, path).is_ok());
+    let path = ColumnPath::new(vec!["x".to_string(), "b".to_string()]);
+    assert!(ColumnDescriptor::try_new(required.clone(), None, 1, 0, path).is_ok());
+
+    let result =
+      ColumnDescriptor::try_new(required.clone(), None, 1, 0, ColumnPath::from("b"));
+    assert!(result.is_err());
+    if let Err(e) = result {
+      assert_eq!(
+        e.description(),
+        "Column \"b\": max levels (definition: 1, repetition: 0) are inconsistent \
+          with REQUIRED repetition"
+      );
+    }
+
+    let result =
+      ColumnDescriptor::try_new(optional.clone(), None, 0, 0, ColumnPath::from("a"));
+    assert!(result.is_err());
+
+    let path = ColumnPath::new(vec!["x".to_string(), "c".to_string()]);
+    let result = ColumnDescriptor::try_new(repeated.clone(), None, 1, 0, path);
+    assert!(result.is_err());
+
+    let result =
+      ColumnDescriptor::try_new(required.clone(), None, -1, 0, ColumnPath::from("b"));
+    assert!(result.is_err());
+    if let Err(e) = result {
+      assert_eq!(
+        e.description(),
+        "Column \"b\": invalid max levels (definition: -1, repetition: 0)"
+      );
+    }
+
+    let result =
+      ColumnDescriptor::try_new(repeated.clone(), None, 1, 2, ColumnPath::from("c"));
+    assert!(result.is_err());
+    if let Err(e) = result {
+      assert_eq!(
+        e.description(),
+        "Column \"c\": max repetition level 2 is greater than max definition level 1"
+      );
+    }
+  }
+
   #[test]
   fn test_schema_descriptor() {
     let result = test_schema_descriptor_helper();
@@ -123,6 +123,11 @@ pub fn unset_array_bit(bits: &mut [u8], i: usize) {
   bits[i / 8] &= !(1 << (i % 8));
 }
 
+#[inline]
+pub fn get_array_bit(bits: &[u8], i: usize) -> bool {
+  bits[i / 8] & (1 << (i % 8)) != 0
+}
+
 /// Returns the minimum number of bits needed to represent the value 'x'
 #[inline]
 pub fn num_required_bits(x: u64) -> usize {
@@ -134,6 +139,15 @@ pub fn num_required_bits(x: u64) -> usize {
   0
 }
 
+/// Returns bit width needed to encode values in range `[0, max_value]`, e.g. levels
+/// with `max_value` as max level, or dictionary indices with the last index.
+///
+/// Max value 0 maps to width 0, i.e. values do not need any bits to be stored.
+#[inline]
+pub fn bit_width_for_max(max_value: u64) -> u8 {
+  num_required_bits(max_value) as u8
+}
+
 
 /// Utility class for writing bit/byte streams. This class can write data in either
 /// bit packed or byte aligned fashion.
@@ -246,6 +260,21 @@ impl BitWriter {
     self.byte_offset - self.start + ceil(self.bit_offset as i64, 8) as usize
   }
 
+  /// Returns the maximum number of bytes that can be written, excluding bytes before
+  /// the starting offset.
+  #[inline]
+  pub fn capacity(&self) -> usize {
+    self.max_bytes - self.start
+  }
+
+  /// Extends the internal buffer by `increment` bytes, so more data can be written
+  /// without consuming the buffer. Written bytes and offsets are preserved.
+  #[inline]
+  pub fn extend(&mut self, increment: usize) {
+    self.max_bytes += increment;
+    self.buffer.resize(self.max_bytes, 0);
+  }
+
   #[inline]
   pub fn buffer(&self) -> &[u8] {
     &self.buffer[self.start..]
@@ -293,6 +322,46 @@ impl BitWriter {
     true
   }
 
+  /// Writes `count` zero values of `num_bits` bits each to the internal buffer of this
+  /// writer. This is equivalent to calling `put_value(0, num_bits)` `count` times, but
+  /// writes whole bytes directly once the buffered bits are aligned.
+  ///
+  /// Returns false if there's not enough room left. True otherwise.
+  #[inline]
+  pub fn put_zeros(&mut self, count: usize, num_bits: usize) -> bool {
+    assert!(num_bits <= 64);
+    let mut num_zero_bits = count * num_bits;
+
+    if self.byte_offset * 8 + self.bit_offset + num_zero_bits > self.max_bytes * 8 {
+      return false;
+    }
+
+    // Fill the remaining bits of `buffered_values`, which are already zeros
+    let num_fill_bits = cmp::min(num_zero_bits, 64 - self.bit_offset);
+    self.bit_offset += num_fill_bits;
+    num_zero_bits -= num_fill_bits;
+    if self.bit_offset == 64 {
+      memcpy_value(&self.buffered_values, 8, &mut self.buffer[self.byte_offset..]);
+      self.byte_offset += 8;
+      self.bit_offset = 0;
+      self.buffered_values = 0;
+    }
+
+    // At this point, if there are bits left, writer is byte aligned, so we can zero out
+    // whole bytes directly. Buffer might contain data from the previous use, therefore
+    // bytes are always overwritten.
+    if num_zero_bits > 0 {
+      let num_bytes = num_zero_bits / 8;
+      for b in &mut self.buffer[self.byte_offset..self.byte_offset + num_bytes] {
+        *b = 0;
+      }
+      self.byte_offset += num_bytes;
+      self.bit_offset = num_zero_bits % 8;
+    }
+    assert!(self.bit_offset < 64);
+    true
+  }
+
   /// Writes `val` of `num_bytes` bytes to the next aligned byte. If size of `T` is
   /// larger than `num_bytes`, extra higher ordered bytes will be ignored.
   ///
@@ -358,6 +427,34 @@ impl BitWriter {
   }
 }
 
+/// Packs `values` into `out` using `bit_width` bits for each value. Produces the same
+/// bit layout as calling `put_value` for each value, but checks the available space
+/// only once, e.g. to pack a whole mini block at once.
+///
+/// Returns false, without writing any values, if there's not enough room left in
+/// `out`. True otherwise.
+pub fn bit_pack(values: &[u64], bit_width: u8, out: &mut BitWriter) -> bool {
+  let num_bits = bit_width as usize;
+  assert!(num_bits <= 64);
+  if out.byte_offset * 8 + out.bit_offset + values.len() * num_bits > out.max_bytes * 8 {
+    return false;
+  }
+
+  for &v in values {
+    debug_assert_eq!(v.checked_shr(num_bits as u32).unwrap_or(0), 0);
+    out.buffered_values |= v << out.bit_offset;
+    out.bit_offset += num_bits;
+    if out.bit_offset >= 64 {
+      memcpy_value(&out.buffered_values, 8, &mut out.buffer[out.byte_offset..]);
+      out.byte_offset += 8;
+      out.bit_offset -= 64;
+      // Keep the bits of `v` that did not fit, see `put_value`
+      out.buffered_values =
+        v.checked_shr((num_bits - out.bit_offset) as u32).unwrap_or(0);
+    }
+  }
+  true
+}
 
 /// Maximum byte length for a VLQ encoded integer
 /// MAX_VLQ_BYTE_LEN = 5 for i32, and MAX_VLQ_BYTE_LEN = 10 for i64
@@ -715,6 +812,10 @@ mod tests {
     assert_eq!(buffer, vec![16, 12, 0]);
     unset_array_bit(&mut buffer[..], 10);
     assert_eq!(buffer, vec![16, 8, 0]);
+    assert!(get_array_bit(&buffer[..], 4));
+    assert!(get_array_bit(&buffer[..], 11));
+    assert!(!get_array_bit(&buffer[..], 10));
+    assert!(!get_array_bit(&buffer[..], 23));
   }
 
   #[test]
@@ -729,6 +830,20 @@ mod tests {
     assert_eq!(num_required_bits(16), 5);
   }
 
+  #[test]
+  fn test_bit_width_for_max() {
+    assert_eq!(bit_width_for_max(0), 0);
+    assert_eq!(bit_width_for_max(1), 1);
+    assert_eq!(bit_width_for_max(2), 2);
+    assert_eq!(bit_width_for_max(3), 2);
+    assert_eq!(bit_width_for_max(255), 8);
+    assert_eq!(bit_width_for_max(256), 9);
+    assert_eq!(bit_width_for_max(u64::max_value()), 64);
+    for max_value in 0..1024 {
+      assert_eq!(bit_width_for_max(max_value), log2(max_value + 1) as u8);
+    }
+  }
+
   #[test]
   fn test_log2() {
     assert_eq!(log2(1), 0);
@@ -867,6 +982,92 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_bit_pack() {
+    for &bit_width in &[0, 1, 3, 7, 8, 13, 31, 32, 33, 63, 64] {
+      // Start at an unaligned offset to cover values split between buffered words
+      let values: Vec<u64> = random_numbers::<u64>(100)
+        .iter().map(|v| trailing_bits(*v, bit_width)).collect();
+      let num_bytes = 1 + ceil((values.len() * bit_width) as i64, 8) as usize;
+
+      let mut expected = BitWriter::new(num_bytes);
+      assert!(expected.put_value(1, 3));
+      for v in &values {
+        assert!(expected.put_value(*v, bit_width));
+      }
+      let mut writer = BitWriter::new(num_bytes);
+      assert!(writer.put_value(1, 3));
+      assert!(bit_pack(&values, bit_width as u8, &mut writer));
+      let bytes = writer.consume();
+      assert_eq!(bytes, expected.consume(), "bit width {}", bit_width);
+
+      let mut reader = BitReader::from(bytes);
+      assert_eq!(reader.get_value::<u64>(3), Some(1));
+      for v in &values {
+        let value = reader.get_value::<u64>(bit_width);
+        assert_eq!(value, Some(*v), "bit width {}", bit_width);
+      }
+    }
+
+    // Nothing is written if values do not fit
+    let mut writer = BitWriter::new(2);
+    assert!(!bit_pack(&[1, 2, 3, 4, 5], 4, &mut writer));
+    assert_eq!(writer.bytes_written(), 0);
+    assert!(bit_pack(&[1, 2, 3, 4], 4, &mut writer));
+    assert_eq!(writer.consume(), vec![0x21, 0x43]);
+  }
+
+  #[test]
+  fn test_put_zeros() {
+    test_put_zeros_internal(0, 5);
+    test_put_zeros_internal(1, 0);
+    test_put_zeros_internal(1, 1);
+    test_put_zeros_internal(7, 3);
+    test_put_zeros_internal(32, 2);
+    test_put_zeros_internal(31, 7);
+    test_put_zeros_internal(100, 13);
+    test_put_zeros_internal(64, 32);
+    test_put_zeros_internal(9, 64);
+  }
+
+  fn test_put_zeros_internal(count: usize, num_bits: usize) {
+    let max_bytes = 8 + ceil((count * num_bits) as i64, 8) as usize + 8;
+    for prefix_bits in 0..9 {
+      // Reuse writers with a dirty buffer to make sure zeros are actually written
+      let mut expected = BitWriter::new(max_bytes);
+      let mut actual = BitWriter::new(max_bytes);
+      for _ in 0..max_bytes {
+        assert!(expected.put_aligned::<u8>(0xFF, 1));
+        assert!(actual.put_aligned::<u8>(0xFF, 1));
+      }
+      expected.clear();
+      actual.clear();
+
+      for _ in 0..prefix_bits {
+        assert!(expected.put_value(1, 1));
+        assert!(actual.put_value(1, 1));
+      }
+      for _ in 0..count {
+        assert!(expected.put_value(0, num_bits));
+      }
+      assert!(actual.put_zeros(count, num_bits));
+      assert!(expected.put_value(1, 1));
+      assert!(actual.put_value(1, 1));
+
+      assert_eq!(actual.bytes_written(), expected.bytes_written());
+      assert_eq!(actual.flush_buffer(), expected.flush_buffer());
+    }
+  }
+
+  #[test]
+  fn test_put_zeros_not_enough_space() {
+    let mut writer = BitWriter::new(2);
+    assert!(writer.put_value(1, 3));
+    assert!(!writer.put_zeros(2, 7));
+    assert!(writer.put_zeros(1, 13));
+    assert!(!writer.put_zeros(1, 1));
+  }
+
   #[test]
   fn test_get_batch() {
     const SIZE: &[usize] = &[1, 31, 32, 33, 128, 129];
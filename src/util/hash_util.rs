@@ -74,6 +74,23 @@ fn murmur_hash2_64a<T: AsBytes>(data: &T, seed: u64) -> u64 {
   h
 }
 
+const CRC32_IEEE_POLY: u32 = 0xEDB88320;
+
+/// Computes standard CRC-32 checksum (IEEE 802.3 polynomial, as used by gzip) of
+/// `data`. This is the checksum Parquet uses for page CRCs, and it is not related to
+/// the `crc32_hash` function, which uses the Castagnoli polynomial for hashing.
+pub fn crc32_ieee(data: &[u8]) -> u32 {
+  let mut crc = !0u32;
+  for byte in data {
+    crc ^= *byte as u32;
+    for _ in 0..8 {
+      let mask = (crc & 1).wrapping_neg();
+      crc = (crc >> 1) ^ (CRC32_IEEE_POLY & mask);
+    }
+  }
+  !crc
+}
+
 /// CRC32 hash implementation using SSE4 instructions. Borrowed from Impala.
 #[cfg(target_feature = "sse4.2")]
 pub fn crc32_hash<T: AsBytes>(data: &T, seed: u32) -> u32 {
@@ -127,6 +144,13 @@ mod tests {
     assert_eq!(result, 2392198230801491746);
   }
 
+  #[test]
+  fn test_crc32_ieee() {
+    assert_eq!(crc32_ieee(&[]), 0);
+    assert_eq!(crc32_ieee(b"123456789"), 0xCBF43926);
+    assert_eq!(crc32_ieee(b"hello"), 0x3610A686);
+  }
+
   #[test]
   #[cfg(target_feature = "sse4.2")]
   fn test_crc32() {
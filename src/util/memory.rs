@@ -59,6 +59,16 @@ impl MemTracker {
     self.memory_usage.get().1
   }
 
+  /// Resets the maximum memory consumption to the current memory consumption, e.g. to
+  /// observe the peak of each row group separately.
+  ///
+  /// Memory tracker is not `Sync` and is only shared within a thread, so the reset
+  /// can not interleave with `alloc` calls.
+  pub fn reset_max(&self) {
+    let (current, _) = self.memory_usage.get();
+    self.memory_usage.set((current, current));
+  }
+
   /// Adds `num_bytes` to the memory consumption tracked by this memory tracker.
   #[inline]
   pub fn alloc(&self, num_bytes: i64) {
@@ -423,6 +433,29 @@ mod tests {
     assert_eq!(mem_tracker.memory_usage(), buffer.capacity() as i64);
   }
 
+  #[test]
+  fn test_mem_tracker_reset_max() {
+    let mem_tracker = MemTracker::new();
+    mem_tracker.alloc(100);
+    mem_tracker.alloc(-60);
+    assert_eq!(mem_tracker.max_memory_usage(), 100);
+
+    mem_tracker.reset_max();
+    assert_eq!(mem_tracker.memory_usage(), 40);
+    assert_eq!(mem_tracker.max_memory_usage(), 40);
+
+    mem_tracker.alloc(-40);
+    mem_tracker.alloc(30);
+    assert_eq!(mem_tracker.memory_usage(), 30);
+    assert_eq!(mem_tracker.max_memory_usage(), 40);
+
+    mem_tracker.reset_max();
+    mem_tracker.alloc(20);
+    mem_tracker.alloc(-50);
+    assert_eq!(mem_tracker.memory_usage(), 0);
+    assert_eq!(mem_tracker.max_memory_usage(), 50);
+  }
+
   #[test]
   fn test_byte_ptr_mem_tracker() {
     let mem_tracker = Rc::new(MemTracker::new());
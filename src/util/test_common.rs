@@ -17,11 +17,20 @@
 
 use rand::{thread_rng, Rng, Rand};
 use rand::distributions::range::SampleRange;
+use std::collections::HashSet;
 use std::env;
 use std::fs;
 use std::io::Write;
+use std::rc::Rc;
 
+use basic::{Compression, Encoding, LogicalType, Type};
+use compression::create_codec;
 use data_type::{ByteArray, DataType, FixedLenByteArrayType};
+use encodings::decoding::get_decoder;
+use encodings::encoding::get_encoder;
+use errors::ParquetError;
+use schema::types::{ColumnDescPtr, ColumnDescriptor, ColumnPath, Type as SchemaType};
+use util::memory::{ByteBufferPtr, MemTracker};
 
 pub trait RandGen<T: DataType> {
   fn gen(len: i32) -> T::T;
@@ -95,6 +104,94 @@ pub fn random_numbers_range<T>(
   }
 }
 
+/// Returns `total` byte arrays sampled from `distinct` unique random byte arrays, e.g. to
+/// test dictionary encoding. Each array has length `len`, or random length up to 128
+/// bytes if `len` is negative. Output has at most `distinct` unique values.
+pub fn gen_byte_arrays_with_cardinality(
+  distinct: usize,
+  total: usize,
+  len: i32
+) -> Vec<ByteArray> {
+  assert!(distinct > 0 || total == 0, "Cannot sample {} values from none", total);
+  // Each random byte is one of 255 values
+  assert!(
+    len < 0 || len >= 8 || distinct as u64 <= 255u64.pow(len as u32),
+    "Cannot generate {} unique byte arrays of length {}",
+    distinct,
+    len
+  );
+  let mut rng = thread_rng();
+  let mut seen = HashSet::new();
+  let mut uniques = vec![];
+  while uniques.len() < distinct {
+    let value_len = if len < 0 { rng.gen_range::<usize>(0, 128) } else { len as usize };
+    let value = random_bytes(value_len);
+    if seen.insert(value.clone()) {
+      uniques.push(ByteArray::from(value));
+    }
+  }
+  (0..total).map(|_| uniques[rng.gen_range(0, distinct)].clone()).collect()
+}
+
+/// Returns column descriptor for a primitive column `name` with provided physical and
+/// logical types, type length and max definition/repetition levels.
+pub fn make_col_desc(
+  name: &str,
+  physical: Type,
+  logical: LogicalType,
+  type_len: i32,
+  max_def: i16,
+  max_rep: i16
+) -> ColumnDescPtr {
+  let tpe = SchemaType::primitive_type_builder(name, physical)
+    .with_logical_type(logical)
+    .with_length(type_len)
+    .build()
+    .unwrap();
+  Rc::new(ColumnDescriptor::new(
+    Rc::new(tpe), None, max_def, max_rep, ColumnPath::from(name)))
+}
+
+/// Encodes `values` with `encoding`, compresses the encoded bytes with `compression`,
+/// then decompresses and decodes them, asserting that the result matches `values`.
+/// Does nothing if `compression` is not supported yet. Dictionary encodings are not
+/// supported, because dictionary page is not written.
+pub fn assert_roundtrip_compressed<T: DataType>(
+  encoding: Encoding,
+  compression: Compression,
+  values: &[T::T],
+  type_length: i32
+) where T: 'static {
+  let mut codec = match create_codec(compression) {
+    Ok(codec) => codec,
+    Err(ParquetError::NYI(_)) => return,
+    Err(e) => panic!("Failed to create codec {}: {}", compression, e)
+  };
+  let descr = make_col_desc(
+    "col", T::get_physical_type(), LogicalType::NONE, type_length, 0, 0);
+
+  let mut encoder = get_encoder::<T>(descr.clone(), encoding, Rc::new(MemTracker::new()))
+    .expect("get encoder");
+  encoder.put(values).expect("ok to encode");
+  let encoded = encoder.flush_buffer().expect("ok to flush buffer");
+
+  let bytes = match codec {
+    Some(ref mut codec) => {
+      let compressed = codec.compress(encoded.data()).expect("ok to compress");
+      let mut decompressed = vec![];
+      codec.decompress(&compressed, &mut decompressed).expect("ok to decompress");
+      decompressed
+    },
+    None => encoded.data().to_vec()
+  };
+  assert_eq!(bytes, encoded.data(), "Compression {} changed encoded data", compression);
+
+  let mut decoder = get_decoder::<T>(descr, encoding).expect("get decoder");
+  decoder.set_data(ByteBufferPtr::new(bytes), values.len()).expect("ok to set data");
+  let result = decoder.decode_all().expect("ok to decode");
+  assert_eq!(result, values);
+}
+
 /// Returns file handle for a test parquet file from 'data' directory
 pub fn get_test_file(file_name: &str) -> fs::File {
   let mut path_buf = env::current_dir().unwrap();
@@ -125,3 +222,57 @@ pub fn get_temp_file(file_name: &str, content: &[u8]) -> fs::File {
   assert!(file.is_ok());
   file.unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use data_type::{BoolType, ByteArrayType, Int64Type};
+
+  #[test]
+  fn test_make_col_desc() {
+    let descr = make_col_desc("a", Type::BYTE_ARRAY, LogicalType::UTF8, -1, 1, 2);
+    assert_eq!(descr.name(), "a");
+    assert_eq!(descr.physical_type(), Type::BYTE_ARRAY);
+    assert_eq!(descr.logical_type(), LogicalType::UTF8);
+    assert_eq!(descr.type_length(), -1);
+    assert_eq!(descr.max_def_level(), 1);
+    assert_eq!(descr.max_rep_level(), 2);
+    assert_eq!(descr.path(), &ColumnPath::from("a"));
+  }
+
+  #[test]
+  fn test_gen_byte_arrays_with_cardinality() {
+    let params = vec![(10, 1000, 4), (1, 100, 0), (100, 50, -1), (5, 0, 2)];
+    for (distinct, total, len) in params {
+      let values = gen_byte_arrays_with_cardinality(distinct, total, len);
+      assert_eq!(values.len(), total);
+      let uniques: HashSet<Vec<u8>> = values.iter().map(|v| v.data().to_vec()).collect();
+      assert!(uniques.len() <= distinct);
+      if len >= 0 {
+        assert!(values.iter().all(|v| v.len() == len as usize));
+      }
+    }
+
+    // All distinct values are used for a large enough sample
+    let values = gen_byte_arrays_with_cardinality(3, 1000, 8);
+    let uniques: HashSet<Vec<u8>> = values.iter().map(|v| v.data().to_vec()).collect();
+    assert_eq!(uniques.len(), 3);
+  }
+
+  #[test]
+  fn test_assert_roundtrip_compressed() {
+    let values = random_numbers::<i64>(1000);
+    assert_roundtrip_compressed::<Int64Type>(
+      Encoding::PLAIN, Compression::SNAPPY, &values, -1);
+
+    let values = gen_byte_arrays_with_cardinality(10, 1000, -1);
+    assert_roundtrip_compressed::<ByteArrayType>(
+      Encoding::DELTA_BYTE_ARRAY, Compression::ZSTD, &values, -1);
+    assert_roundtrip_compressed::<ByteArrayType>(
+      Encoding::DELTA_BYTE_ARRAY, Compression::GZIP, &values, -1);
+
+    let values = random_bools(1000);
+    assert_roundtrip_compressed::<BoolType>(
+      Encoding::RLE, Compression::UNCOMPRESSED, &values, -1);
+  }
+}